@@ -3,9 +3,12 @@
 //! Exactly follow current substrate code from `no_std` incompatible crates. Last confirmed on v7.0.0
 #[cfg(feature = "embed-display")]
 use base58::ToBase58;
-use parity_scale_codec::{Decode, Error, Input};
+use parity_scale_codec::{Decode, Encode, Error, Input, Output};
 
-pub use crate::special_types::{SIGNATURE_LEN_ECDSA, SIGNATURE_LEN_ED25519, SIGNATURE_LEN_SR25519};
+pub use crate::special_types::{
+    ACCOUNT_ID_32_LEN, PUBLIC_LEN_ECDSA, PUBLIC_LEN_ED25519, PUBLIC_LEN_SR25519,
+    SIGNATURE_LEN_ECDSA, SIGNATURE_LEN_ED25519, SIGNATURE_LEN_SR25519,
+};
 
 #[cfg(feature = "embed-display")]
 use crate::std::{string::String, vec::Vec};
@@ -43,14 +46,37 @@ impl Decode for Era {
     }
 }
 
+/// [`Encode`] implementation, same as in `sp_runtime::generic::Era`.
+impl Encode for Era {
+    fn encode_to<T: Output + ?Sized>(&self, output: &mut T) {
+        match self {
+            Self::Immortal => output.push_byte(0),
+            Self::Mortal(period, phase) => {
+                // Period is rounded to a power of two and phase is reduced
+                // the same way as in `sp_runtime::generic::Era::mortal`,
+                // values not produced by `Era::mortal` are still encodable.
+                let period = period
+                    .checked_next_power_of_two()
+                    .unwrap_or(1 << 16)
+                    .clamp(4, 1 << 16);
+                let phase = phase % period;
+                let quantize_factor = (period >> 12).max(1);
+                let encoded = period.trailing_zeros().saturating_sub(1).clamp(1, 15) as u16
+                    | ((phase / quantize_factor) << 4) as u16;
+                encoded.encode_to(output);
+            }
+        }
+    }
+}
+
 /// Definitions for some special arrays from `sp_core`.
 macro_rules! define_array {
     ($(#[$attr:meta] $name: ident ($len: expr)), *) => {
         $(
             #[$attr]
             ///
-            /// Intended for `no_std`, for decoding and display of decoded data only.
-            #[derive(Clone, Debug, Eq, PartialEq)]
+            /// Intended for `no_std`, for decoding, encoding and display of decoded data only.
+            #[derive(Clone, Debug, Encode, Eq, PartialEq)]
             pub struct $name(pub [u8; $len]);
             impl $name {
                 pub const fn len_bytes() -> usize {
@@ -61,18 +87,6 @@ macro_rules! define_array {
     }
 }
 
-/// Known size for `sp_core::crypto::AccountId32`.
-pub const ACCOUNT_ID_32_LEN: usize = 32;
-
-/// Known size for `sp_core::ed25519::Public`.
-pub const PUBLIC_LEN_ED25519: usize = 32;
-
-/// Known size for `sp_core::sr25519::Public`.
-pub const PUBLIC_LEN_SR25519: usize = 32;
-
-/// Known size for `sp_core::ecdsa::Public`.
-pub const PUBLIC_LEN_ECDSA: usize = 33;
-
 define_array! {
     /// Placeholder for `sp_core::crypto::AccountId32`.
     AccountId32(ACCOUNT_ID_32_LEN)
@@ -182,4 +196,12 @@ mod tests {
         let data = hex::decode("1111").unwrap();
         assert!(Era::decode(&mut &data[..]).is_err());
     }
+
+    #[test]
+    fn era04() {
+        let data = hex::decode("b501").unwrap();
+        let era = Era::decode(&mut &data[..]).unwrap();
+        assert_eq!(era.encode(), data);
+        assert_eq!(Era::Immortal.encode(), [0]);
+    }
 }
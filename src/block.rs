@@ -0,0 +1,154 @@
+//! Decode blocks.
+//!
+//! Block consists of the header and the body. Block body is SCALE-encoded
+//! `Vec<UncheckedExtrinsic>`, i.e. compact of the number of extrinsics
+//! followed by the extrinsics themselves. Each unchecked extrinsic is an
+//! encoded opaque `Vec<u8>`, and starts with compact of its own length, see
+//! [`unchecked_extrinsic`](crate::unchecked_extrinsic).
+//!
+//! Extrinsic length is used to separate the extrinsics in block body, so that
+//! each extrinsic gets decoded independently. Extrinsic decoding failure is
+//! reported for this extrinsic only, and does not affect the decoding of the
+//! rest of the block.
+//!
+//! Block header is decoded same way as standalone headers, see
+//! [`header`](crate::header): header type is taken from the metadata if
+//! available, otherwise well-known header layout is used.
+#[cfg(feature = "std")]
+use std::ops::Range;
+
+#[cfg(not(feature = "std"))]
+use core::ops::Range;
+
+use external_memory_tools::{AddressableBuffer, ExternalMemory};
+
+use crate::std::vec::Vec;
+
+use crate::compacts::get_compact;
+use crate::error::{BlockError, ParserError, UncheckedExtrinsicError};
+use crate::header::{decode_header_at_position, Header};
+use crate::traits::AsMetadata;
+use crate::unchecked_extrinsic::{decode_as_unchecked_extrinsic, UncheckedExtrinsic};
+
+/// [`Path`](scale_info::Path) of `sp_runtime::generic::Header`.
+pub const HEADER_PATH: &[&str] = &["sp_runtime", "generic", "header", "Header"];
+
+/// Decoded block.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Block<E, M>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    pub header: Header,
+    pub extrinsics: Vec<BlockExtrinsic<E, M>>,
+}
+
+/// Extrinsic from block body, decoded or not.
+#[derive(Debug, Eq, PartialEq)]
+pub struct BlockExtrinsic<E, M>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    /// Extrinsic index in block body.
+    pub index: usize,
+
+    /// Extrinsic positions in input data, including the extrinsic length
+    /// compact.
+    pub range: Range<usize>,
+
+    /// Extrinsic decoding result.
+    pub extrinsic: Result<UncheckedExtrinsic, UncheckedExtrinsicError<E, M>>,
+}
+
+/// Decode block body.
+///
+/// All data is expected to be used. Extrinsic ranges correspond to the
+/// positions in the input data.
+pub fn decode_block_body<B, E, M>(
+    data: &B,
+    ext_memory: &mut E,
+    meta: &M,
+) -> Result<Vec<BlockExtrinsic<E, M>>, BlockError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let mut position: usize = 0;
+    let extrinsics =
+        decode_extrinsics_at_position::<B, E, M>(data, ext_memory, meta, &mut position)?;
+    if position != data.total_len() {
+        Err(BlockError::SomeDataNotUsed { from: position })
+    } else {
+        Ok(extrinsics)
+    }
+}
+
+/// Decode block, header followed by block body.
+///
+/// All data is expected to be used. Extrinsic ranges correspond to the
+/// positions in the input data.
+pub fn decode_block<B, E, M>(
+    data: &B,
+    ext_memory: &mut E,
+    meta: &M,
+) -> Result<Block<E, M>, BlockError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let mut position: usize = 0;
+    let header = decode_header_at_position::<B, E, M>(data, ext_memory, meta, &mut position)
+        .map_err(BlockError::Header)?;
+    let extrinsics =
+        decode_extrinsics_at_position::<B, E, M>(data, ext_memory, meta, &mut position)?;
+    if position != data.total_len() {
+        Err(BlockError::SomeDataNotUsed { from: position })
+    } else {
+        Ok(Block { header, extrinsics })
+    }
+}
+
+/// Decode block body starting at given position.
+///
+/// As decoding proceeds, `position` gets changed.
+fn decode_extrinsics_at_position<B, E, M>(
+    data: &B,
+    ext_memory: &mut E,
+    meta: &M,
+    position: &mut usize,
+) -> Result<Vec<BlockExtrinsic<E, M>>, BlockError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let number_of_extrinsics = get_compact::<u32, B, E>(data, ext_memory, position)
+        .map_err(|_| BlockError::BodyNoCompact)? as usize;
+    let mut extrinsics: Vec<BlockExtrinsic<E, M>> = Vec::new();
+    for index in 0..number_of_extrinsics {
+        let extrinsic_start = *position;
+        let extrinsic_length = get_compact::<u32, B, E>(data, ext_memory, position)
+            .map_err(|_| BlockError::ExtrinsicNoCompact { index })?
+            as usize;
+        let extrinsic_end = *position + extrinsic_length;
+        let extrinsic_data = data
+            .read_slice(ext_memory, extrinsic_start, extrinsic_end - extrinsic_start)
+            .map_err(|e| BlockError::Parsing(ParserError::Buffer(e)))?;
+        let extrinsic = decode_as_unchecked_extrinsic::<&[u8], E, M>(
+            &extrinsic_data.as_ref(),
+            ext_memory,
+            meta,
+        );
+        *position = extrinsic_end;
+        extrinsics.push(BlockExtrinsic {
+            index,
+            range: extrinsic_start..extrinsic_end,
+            extrinsic,
+        })
+    }
+    Ok(extrinsics)
+}
@@ -0,0 +1,290 @@
+//! Tree of nested calls, for calls wrapping other calls.
+//!
+//! Calls such as `Utility::batch_all`, `Proxy::proxy`, `Multisig::as_multi`,
+//! `Sudo::sudo` or `Scheduler::schedule` contain other calls, as
+//! [`ParsedData::Call`] values in call fields, or as sequences of them.
+//! [`Call::call_tree`] walks the parsed call and collects all contained calls
+//! into a tree of [`CallNode`]s.
+//!
+//! Each node has a [`CallPath`] showing where the call is located, e.g.
+//! `batch_all[2] > proxy.call`, and the [`CallWrapper`] describing how the
+//! wrapper call dispatches its nested calls, e.g. which account is being
+//! proxied, or which origin is used.
+//!
+//! Wrapper semantics is recognized by pallet and call names. Unknown calls
+//! containing other calls are marked as [`CallWrapper::Other`].
+use crate::std::{
+    borrow::ToOwned,
+    fmt::{Display, Formatter, Result as FmtResult},
+    string::String,
+    vec::Vec,
+};
+
+use crate::cards::{Call, ExtendedData, FieldData, ParsedData};
+
+/// Location of a nested call within the root call.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CallPath(pub Vec<CallPathSegment>);
+
+impl Display for CallPath {
+    /// Segments are separated by ` > `, e.g. `batch_all[2] > proxy.call`.
+    /// Path of the root call is empty.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (i, segment) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, " > ")?;
+            }
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Single step from the wrapper call into the nested call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallPathSegment {
+    /// Name of the wrapper call, e.g. `batch_all`.
+    pub call_name: String,
+
+    /// Name of the wrapper call field containing the nested call, if the
+    /// field is named.
+    pub field_name: Option<String>,
+
+    /// Position of the nested call in a sequence, if the nested call is a
+    /// sequence element.
+    pub index: Option<usize>,
+}
+
+impl Display for CallPathSegment {
+    /// Sequence elements are displayed with index, e.g. `batch_all[2]`, other
+    /// nested calls are displayed with field name, e.g. `proxy.call`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match (self.index, &self.field_name) {
+            (Some(index), _) => write!(f, "{}[{index}]", self.call_name),
+            (None, Some(field_name)) => write!(f, "{}.{field_name}", self.call_name),
+            (None, None) => write!(f, "{}", self.call_name),
+        }
+    }
+}
+
+/// Batch execution mode for `Utility` batch calls.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchMode {
+    /// `batch`: calls are dispatched until the first failure.
+    Interrupted,
+
+    /// `batch_all`: all calls are dispatched atomically.
+    Atomic,
+
+    /// `force_batch`: all calls are dispatched regardless of failures.
+    Forced,
+}
+
+/// How the wrapper call dispatches its nested calls.
+///
+/// Wrapper data refers to the parsed fields of the wrapper call.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CallWrapper<'a> {
+    /// `Utility` batch, nested calls are dispatched with the wrapper origin.
+    Batch(BatchMode),
+
+    /// `Utility::as_derivative`, nested call is dispatched from the derivative
+    /// account of the wrapper origin.
+    AsDerivative { index: &'a ExtendedData },
+
+    /// `Utility::dispatch_as`, nested call is dispatched with the given
+    /// origin.
+    DispatchAs { as_origin: &'a ExtendedData },
+
+    /// `Proxy::proxy` and `Proxy::proxy_announced`, nested call is dispatched
+    /// on behalf of the `real` account.
+    Proxy {
+        real: &'a ExtendedData,
+        force_proxy_type: Option<&'a ExtendedData>,
+    },
+
+    /// `Multisig::as_multi` and `Multisig::as_multi_threshold_1`, nested call
+    /// is dispatched from the multisig account.
+    Multisig {
+        threshold: Option<&'a ExtendedData>,
+        other_signatories: &'a ExtendedData,
+    },
+
+    /// `Sudo::sudo` and `Sudo::sudo_unchecked_weight`, nested call is
+    /// dispatched with `Root` origin.
+    Sudo,
+
+    /// `Sudo::sudo_as`, nested call is dispatched with signed origin of `who`.
+    SudoAs { who: &'a ExtendedData },
+
+    /// `Scheduler` calls, nested call is dispatched later, with the wrapper
+    /// origin.
+    Scheduled,
+
+    /// Other call containing nested calls.
+    Other,
+}
+
+/// Node of the call tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CallNode<'a> {
+    /// Location of the call within the root call.
+    pub path: CallPath,
+
+    /// Parsed call.
+    pub call: &'a Call,
+
+    /// Wrapper semantics, if the call is a known wrapper or contains nested
+    /// calls.
+    pub wrapper: Option<CallWrapper<'a>>,
+
+    /// Nested calls, in order of appearance.
+    pub children: Vec<CallNode<'a>>,
+}
+
+impl<'a> CallNode<'a> {
+    /// All nodes of the tree, depth-first, starting with the node itself.
+    pub fn flatten(&self) -> Vec<&CallNode<'a>> {
+        let mut out = vec![self];
+        for child in self.children.iter() {
+            out.extend(child.flatten());
+        }
+        out
+    }
+}
+
+impl Call {
+    /// Tree of the call and all calls nested in it.
+    pub fn call_tree(&self) -> CallNode<'_> {
+        node(self, CallPath::default())
+    }
+}
+
+/// Build tree node for a call at given path.
+fn node(call: &Call, path: CallPath) -> CallNode<'_> {
+    let mut found: Vec<(&Call, CallPathSegment)> = Vec::new();
+    for field in call.0.fields.iter() {
+        collect_calls(
+            &field.data.data,
+            &call.0.variant_name,
+            &field.field_name,
+            None,
+            &mut found,
+        );
+    }
+    let children: Vec<CallNode> = found
+        .into_iter()
+        .map(|(nested_call, segment)| {
+            let mut nested_path = path.clone();
+            nested_path.0.push(segment);
+            node(nested_call, nested_path)
+        })
+        .collect();
+    let wrapper = known_wrapper(call).or(if children.is_empty() {
+        None
+    } else {
+        Some(CallWrapper::Other)
+    });
+    CallNode {
+        path,
+        call,
+        wrapper,
+        children,
+    }
+}
+
+/// Search calls nested in parsed data of a call field.
+///
+/// Search does not go into the nested calls themselves, those are processed
+/// as separate nodes.
+fn collect_calls<'a>(
+    parsed_data: &'a ParsedData,
+    call_name: &str,
+    field_name: &Option<String>,
+    index: Option<usize>,
+    found: &mut Vec<(&'a Call, CallPathSegment)>,
+) {
+    match parsed_data {
+        ParsedData::Call(nested_call) => found.push((
+            nested_call,
+            CallPathSegment {
+                call_name: call_name.to_owned(),
+                field_name: field_name.to_owned(),
+                index,
+            },
+        )),
+        ParsedData::Composite(fields) => {
+            collect_calls_in_fields(fields, call_name, field_name, index, found)
+        }
+        ParsedData::SequenceRaw(sequence_raw) => {
+            for (i, element) in sequence_raw.data.iter().enumerate() {
+                collect_calls(element, call_name, field_name, Some(i), found)
+            }
+        }
+        ParsedData::Tuple(set) => {
+            for element in set.iter() {
+                collect_calls(&element.data, call_name, field_name, index, found)
+            }
+        }
+        ParsedData::Variant(variant_data) => {
+            collect_calls_in_fields(&variant_data.fields, call_name, field_name, index, found)
+        }
+        _ => {}
+    }
+}
+
+/// Search calls nested in inner fields of a call field.
+fn collect_calls_in_fields<'a>(
+    fields: &'a [FieldData],
+    call_name: &str,
+    field_name: &Option<String>,
+    index: Option<usize>,
+    found: &mut Vec<(&'a Call, CallPathSegment)>,
+) {
+    for field in fields.iter() {
+        collect_calls(&field.data.data, call_name, field_name, index, found)
+    }
+}
+
+/// Wrapper semantics for known wrapper calls.
+fn known_wrapper(call: &Call) -> Option<CallWrapper<'_>> {
+    match (call.0.pallet_name.as_str(), call.0.variant_name.as_str()) {
+        ("Utility", "batch") => Some(CallWrapper::Batch(BatchMode::Interrupted)),
+        ("Utility", "batch_all") => Some(CallWrapper::Batch(BatchMode::Atomic)),
+        ("Utility", "force_batch") => Some(CallWrapper::Batch(BatchMode::Forced)),
+        ("Utility", "as_derivative") => Some(CallWrapper::AsDerivative {
+            index: field(call, "index")?,
+        }),
+        ("Utility", "dispatch_as") => Some(CallWrapper::DispatchAs {
+            as_origin: field(call, "as_origin")?,
+        }),
+        ("Proxy", "proxy") | ("Proxy", "proxy_announced") => Some(CallWrapper::Proxy {
+            real: field(call, "real")?,
+            force_proxy_type: field(call, "force_proxy_type"),
+        }),
+        ("Multisig", "as_multi") | ("Multisig", "as_multi_threshold_1") => {
+            Some(CallWrapper::Multisig {
+                threshold: field(call, "threshold"),
+                other_signatories: field(call, "other_signatories")?,
+            })
+        }
+        ("Sudo", "sudo") | ("Sudo", "sudo_unchecked_weight") => Some(CallWrapper::Sudo),
+        ("Sudo", "sudo_as") => Some(CallWrapper::SudoAs {
+            who: field(call, "who")?,
+        }),
+        ("Scheduler", "schedule")
+        | ("Scheduler", "schedule_named")
+        | ("Scheduler", "schedule_after")
+        | ("Scheduler", "schedule_named_after") => Some(CallWrapper::Scheduled),
+        _ => None,
+    }
+}
+
+/// Parsed data of a call field with given name.
+fn field<'a>(call: &'a Call, field_name: &str) -> Option<&'a ExtendedData> {
+    call.0
+        .fields
+        .iter()
+        .find(|field| field.field_name.as_deref() == Some(field_name))
+        .map(|field| &field.data)
+}
@@ -1,6 +1,9 @@
 //! Types for parsed data (nested) and parser cards (flat and formatted).
 use bitvec::prelude::{BitVec, Lsb0, Msb0};
-use frame_metadata::v14::StorageEntryMetadata;
+use frame_metadata::{
+    v14::{PalletConstantMetadata, StorageEntryMetadata},
+    v15::{RuntimeApiMetadata, RuntimeApiMethodMetadata},
+};
 use num_bigint::{BigInt, BigUint};
 use primitive_types::{H160, H256, H512};
 use scale_info::{form::PortableForm, Field, Path, Type, Variant};
@@ -34,8 +37,11 @@ use crate::std::{
     vec::Vec,
 };
 
+use crate::header::EngineId;
+use crate::mortality::{Mortality, MortalityWindow};
 use crate::printing_balance::{AsBalance, Currency};
 use crate::special_indicators::{PalletSpecificItem, SpecialtyStr, SpecialtyUnsignedInteger};
+use crate::special_types::AccountId20;
 use crate::ShortSpecs;
 
 /// Type-associated information from the metadata.
@@ -95,7 +101,9 @@ pub trait Documented {
 }
 
 /// Collect documentation from documented [`scale_info`] entity ([`Type`],
-/// [`Field`], [`Variant`], [`StorageEntryMetadata<PortableForm>`]).
+/// [`Field`], [`Variant`], [`StorageEntryMetadata<PortableForm>`],
+/// [`PalletConstantMetadata<PortableForm>`], [`RuntimeApiMetadata<PortableForm>`],
+/// [`RuntimeApiMethodMetadata<PortableForm>`]).
 macro_rules! impl_documented {
     ($($ty: ty), *) => {
         $(
@@ -112,7 +120,10 @@ impl_documented!(
     Type<PortableForm>,
     Field<PortableForm>,
     Variant<PortableForm>,
-    StorageEntryMetadata<PortableForm>
+    StorageEntryMetadata<PortableForm>,
+    PalletConstantMetadata<PortableForm>,
+    RuntimeApiMetadata<PortableForm>,
+    RuntimeApiMethodMetadata<PortableForm>
 );
 
 /// Parsed data and collected relevant type information.
@@ -143,6 +154,10 @@ pub struct Call(pub PalletSpecificData);
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Event(pub PalletSpecificData);
 
+/// Parsed pallet error data, from `DispatchError::Module`. Nested.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PalletError(pub PalletSpecificData);
+
 /// List of pallets in which the currency-related value gets displayed with
 /// with chain units and decimals.
 pub const PALLETS_BALANCE_VALID: &[&str] = &["Balances", "Staking"];
@@ -180,28 +195,39 @@ impl PalletSpecificData {
             indent: indent + 1,
             info_flat: info_with_docs_only(&self.variant_docs),
         });
+        self.card_fields(&mut out, indent + 2, short_specs, spec_name);
+        out
+    }
 
+    /// Card `PalletSpecificData` fields and add the cards into already
+    /// existing `Vec<ExtendedCard>`.
+    fn card_fields(
+        &self,
+        out: &mut Vec<ExtendedCard>,
+        indent: u32,
+        short_specs: &ShortSpecs,
+        spec_name: &str,
+    ) {
         if self.fields.len() == 1 && self.fields[0].field_name.is_none() {
             card_unnamed_single_field(
-                &mut out,
+                out,
                 Vec::new(),
                 &self.fields[0],
-                indent + 2,
+                indent,
                 self.is_balance_display(),
                 short_specs,
                 spec_name,
             );
         } else {
             card_field_set(
-                &mut out,
+                out,
                 &self.fields,
-                indent + 2,
+                indent,
                 self.is_balance_display(),
                 short_specs,
                 spec_name,
             )
         }
-        out
     }
 }
 
@@ -231,6 +257,158 @@ impl Event {
     }
 }
 
+impl PalletError {
+    /// Transform `PalletError` into a set of flat formatted [`ExtendedCard`]s.
+    ///
+    /// Pallet name and error name are carded together.
+    pub fn card(
+        &self,
+        indent: u32,
+        short_specs: &ShortSpecs,
+        spec_name: &str,
+    ) -> Vec<ExtendedCard> {
+        let mut info_flat = vec![self.0.pallet_info.flatten()];
+        info_flat.extend_from_slice(&info_with_docs_only(&self.0.variant_docs));
+        let mut out = vec![ExtendedCard {
+            parser_card: ParserCard::PalletError {
+                pallet_name: self.0.pallet_name.to_owned(),
+                error_name: self.0.variant_name.to_owned(),
+            },
+            indent,
+            info_flat,
+        }];
+        self.0
+            .card_fields(&mut out, indent + 1, short_specs, spec_name);
+        out
+    }
+}
+
+/// Parsed pallet constant data. Nested.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PalletConstant {
+    pub pallet_name: String,
+    pub constant_name: String,
+    pub docs: String,
+    pub value: ExtendedData,
+}
+
+impl PalletConstant {
+    /// Should the balance value in the `PalletConstant` be displayed as a
+    /// balance with chain decimals and units?
+    ///
+    /// Determined by the pallet name.
+    fn is_balance_display(&self) -> bool {
+        PALLETS_BALANCE_VALID.contains(&self.pallet_name.as_str())
+    }
+
+    /// Transform `PalletConstant` into a set of flat formatted
+    /// [`ExtendedCard`]s.
+    pub fn card(
+        &self,
+        indent: u32,
+        short_specs: &ShortSpecs,
+        spec_name: &str,
+    ) -> Vec<ExtendedCard> {
+        let mut out = vec![
+            ExtendedCard {
+                parser_card: ParserCard::PalletName(self.pallet_name.to_owned()),
+                indent,
+                info_flat: Vec::new(),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::ConstantName(self.constant_name.to_owned()),
+                indent: indent + 1,
+                info_flat: info_with_docs_only(&self.docs),
+            },
+        ];
+        out.extend_from_slice(&self.value.card(
+            indent + 2,
+            self.is_balance_display(),
+            short_specs,
+            spec_name,
+        ));
+        out
+    }
+}
+
+/// Parsed runtime api call data. Nested.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RuntimeApiCall {
+    pub api_name: String,
+    pub api_docs: String,
+    pub method_name: String,
+    pub method_docs: String,
+
+    /// Method inputs, in order of appearance. Each input has a name and no
+    /// type name.
+    pub inputs: Vec<FieldData>,
+}
+
+impl RuntimeApiCall {
+    /// Transform `RuntimeApiCall` into a set of flat formatted
+    /// [`ExtendedCard`]s.
+    pub fn card(
+        &self,
+        indent: u32,
+        display_balance: bool,
+        short_specs: &ShortSpecs,
+        spec_name: &str,
+    ) -> Vec<ExtendedCard> {
+        let mut out = vec![
+            ExtendedCard {
+                parser_card: ParserCard::RuntimeApiName(self.api_name.to_owned()),
+                indent,
+                info_flat: info_with_docs_only(&self.api_docs),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::RuntimeApiMethodName(self.method_name.to_owned()),
+                indent: indent + 1,
+                info_flat: info_with_docs_only(&self.method_docs),
+            },
+        ];
+        card_field_set(
+            &mut out,
+            &self.inputs,
+            indent + 2,
+            display_balance,
+            short_specs,
+            spec_name,
+        );
+        out
+    }
+}
+
+/// Parsed signed extension data. Nested.
+///
+/// Contains both the `ty` part, which is a part of the extrinsic, and the
+/// `additional_signed` part, which is only a part of the signed payload.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionData {
+    /// Signed extension identifier, as in
+    /// [`SignedExtensionMetadata`](frame_metadata::v14::SignedExtensionMetadata).
+    pub identifier: String,
+
+    /// Decoded `ty` part of the signed extension.
+    pub extra: ExtendedData,
+
+    /// Decoded `additional_signed` part of the signed extension.
+    pub additional_signed: ExtendedData,
+}
+
+impl ExtensionData {
+    /// Transform `ExtensionData` into [`ExtensionCards`], with parts carded
+    /// separately.
+    pub fn card(&self, indent: u32, short_specs: &ShortSpecs, spec_name: &str) -> ExtensionCards {
+        ExtensionCards {
+            identifier: self.identifier.to_owned(),
+            extra: self.extra.card(indent, true, short_specs, spec_name),
+            additional_signed: self
+                .additional_signed
+                .card(indent, true, short_specs, spec_name),
+        }
+    }
+}
+
 /// Parsed data for a [`Field`].
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FieldData {
@@ -269,6 +447,17 @@ pub struct SequenceData {
     pub data: Sequence,
 }
 
+/// Decoded value of a user-registered
+/// [`CustomType`](crate::special_indicators::CustomType), ready for display.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomValue {
+    /// Card title, e.g. type name.
+    pub title: String,
+
+    /// Readable value.
+    pub value: String,
+}
+
 /// Wrapped sequence.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Sequence {
@@ -289,6 +478,7 @@ pub enum Sequence {
 /// Parsed data variants. As many types as possible are preserved.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParsedData {
+    AccountId20(AccountId20),
     BitVecU8Lsb0(BitVec<u8, Lsb0>),
     BitVecU16Lsb0(BitVec<u16, Lsb0>),
     BitVecU32Lsb0(BitVec<u32, Lsb0>),
@@ -306,14 +496,31 @@ pub enum ParsedData {
     BlockHash(H256),
     Call(Call),
     Composite(Vec<FieldData>),
+    /// Value of user-registered
+    /// [`CustomType`](crate::special_indicators::CustomType), with complete
+    /// encoded value in `encoded`.
+    Custom {
+        custom_value: CustomValue,
+        encoded: Vec<u8>,
+    },
     EmptyEnum,
     Era(Era),
+    /// Decoding failure, recorded in tolerant decoding.
+    ///
+    /// `position` is where the failed item starts, `raw_remaining` is the
+    /// data skipped because of the failure.
+    Error {
+        position: usize,
+        error: String,
+        raw_remaining: Vec<u8>,
+    },
     Event(Event),
     GenesisHash(H256),
     H160(H160),
     H256(H256),
     H512(H512),
     Id(AccountId32),
+    PalletError(PalletError),
     PerU16(PerU16),
     Percent(Percent),
     Permill(Permill),
@@ -461,6 +668,11 @@ impl ParsedData {
         spec_name: &str,
     ) -> Vec<ExtendedCard> {
         match &self {
+            ParsedData::AccountId20(value) => vec![ExtendedCard {
+                parser_card: ParserCard::AccountId20(AccountId20Data::from_account_id20(value)),
+                indent,
+                info_flat,
+            }],
             ParsedData::BitVecU8Lsb0(value) => single_card!(BitVecU8Lsb0, value, indent, info_flat),
             ParsedData::BitVecU16Lsb0(value) => {
                 single_card!(BitVecU16Lsb0, value, indent, info_flat)
@@ -515,12 +727,34 @@ impl ParsedData {
                     out
                 }
             }
+            ParsedData::Custom { custom_value, .. } => vec![ExtendedCard {
+                parser_card: ParserCard::Custom(custom_value.to_owned()),
+                indent,
+                info_flat,
+            }],
             ParsedData::EmptyEnum => vec![ExtendedCard {
                 parser_card: ParserCard::EmptyEnum,
                 indent,
                 info_flat,
             }],
-            ParsedData::Era(value) => single_card!(Era, value, indent, info_flat),
+            ParsedData::Era(value) => vec![ExtendedCard {
+                parser_card: ParserCard::Era(Mortality::from_era(value, None)),
+                indent,
+                info_flat,
+            }],
+            ParsedData::Error {
+                position,
+                error,
+                raw_remaining,
+            } => vec![ExtendedCard {
+                parser_card: ParserCard::Error {
+                    position: *position,
+                    error: error.to_owned(),
+                    raw_remaining: hex::encode(raw_remaining),
+                },
+                indent,
+                info_flat,
+            }],
             ParsedData::Event(event) => event.card(indent, short_specs, spec_name),
             ParsedData::GenesisHash(value) => single_card!(GenesisHash, value, indent, info_flat),
             ParsedData::H160(value) => single_card!(H160, value, indent, info_flat),
@@ -536,6 +770,9 @@ impl ParsedData {
                     info_flat,
                 }]
             }
+            ParsedData::PalletError(pallet_error) => {
+                pallet_error.card(indent, short_specs, spec_name)
+            }
             ParsedData::PerU16(value) => single_card!(PerU16, value, indent, info_flat),
             ParsedData::Percent(value) => single_card!(Percent, value, indent, info_flat),
             ParsedData::Permill(value) => single_card!(Permill, value, indent, info_flat),
@@ -892,6 +1129,39 @@ pub struct ExtendedCard {
     pub info_flat: Vec<InfoFlat>,
 }
 
+/// Signed extension data represented as formatted flat cards, grouped by
+/// signed extension.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionCards {
+    pub identifier: String,
+    pub extra: Vec<ExtendedCard>,
+    pub additional_signed: Vec<ExtendedCard>,
+}
+
+impl ExtensionCards {
+    /// Add [`MortalityWindow`](crate::mortality::MortalityWindow), calculated
+    /// for the reference block, into `Era` cards.
+    ///
+    /// Reference block is typically the current block, or the block used as
+    /// the block hash in extensions.
+    pub fn set_reference_block(&mut self, reference_block: u64) {
+        for card in self
+            .extra
+            .iter_mut()
+            .chain(self.additional_signed.iter_mut())
+        {
+            if let ParserCard::Era(Mortality::Mortal {
+                period,
+                phase,
+                window,
+            }) = &mut card.parser_card
+            {
+                *window = MortalityWindow::new(*period, *phase, reference_block);
+            }
+        }
+    }
+}
+
 /// Flat [`Type`] information.
 ///
 /// At least one of the fields is non-`None`.
@@ -954,9 +1224,33 @@ make_id_data!(from_public_ed25519, PublicEd25519);
 make_id_data!(from_public_sr25519, PublicSr25519);
 make_id_data!(from_public_ecdsa, PublicEcdsa);
 
+/// [`AccountId20`]-associated data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccountId20Data {
+    /// Address with EIP-55 checksum
+    pub checksummed: String,
+
+    /// Identicon `png` data
+    #[cfg(feature = "std")]
+    pub identicon: Vec<u8>,
+}
+
+impl AccountId20Data {
+    pub fn from_account_id20(value: &AccountId20) -> Self {
+        Self {
+            checksummed: value.to_checksummed(),
+            #[cfg(feature = "std")]
+            identicon: generate_png_scaled_default(&value.0),
+        }
+    }
+}
+
 /// Flat cards content.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParserCard {
+    AccountId20(AccountId20Data),
+    AuthorityIndex(u32),
+    BabePreDigest(String),
     Balance(Currency),
     BalanceRaw(String),
     BitVecU8Lsb0(BitVec<u8, Lsb0>),
@@ -974,13 +1268,28 @@ pub enum ParserCard {
     #[cfg(target_pointer_width = "32")]
     BitVecU64Msb0(BitVec<u32, Msb0>),
     BlockHash(H256),
+    BlockNumber(u64),
     CallName(String),
     CompositeAnnounced(usize),
+    ConstantName(String),
+    Custom(CustomValue),
+    DigestAnnounced(usize),
+    DigestConsensus(EngineId),
+    DigestOther,
+    DigestPreRuntime(EngineId),
+    DigestRuntimeEnvironmentUpdated,
+    DigestSeal(EngineId),
     EmptyEnum,
     EnumAnnounced,
     EnumVariantName(String),
-    Era(Era),
+    Era(Mortality),
+    Error {
+        position: usize,
+        error: String,
+        raw_remaining: String,
+    },
     EventName(String),
+    ExtrinsicsRoot(H256),
     FieldName(String),
     FieldNumber(usize),
     GenesisHash(H256),
@@ -993,7 +1302,12 @@ pub enum ParserCard {
         version: String,
     },
     Nonce(String),
+    PalletError {
+        pallet_name: String,
+        error_name: String,
+    },
     PalletName(String),
+    ParentHash(H256),
     PerU16(PerU16),
     Percent(Percent),
     Permill(Permill),
@@ -1016,6 +1330,8 @@ pub enum ParserCard {
     PublicEd25519(IdData),
     PublicSr25519(IdData),
     PublicEcdsa(IdData),
+    RuntimeApiMethodName(String),
+    RuntimeApiName(String),
     SequenceAnnounced {
         len: usize,
         element_info_flat: Vec<InfoFlat>,
@@ -1028,11 +1344,15 @@ pub enum ParserCard {
     SignatureEd25519(SignatureEd25519),
     SignatureSr25519(SignatureSr25519),
     SignatureEcdsa(SignatureEcdsa),
+    Slot(u64),
     SpecName(String),
+    StateRoot(H256),
     Text(String),
     Tip(Currency),
     TupleAnnounced(usize),
     TxVersion(String),
+    VrfOutput(H256),
+    VrfProof(H512),
 }
 
 impl ExtendedData {
@@ -1086,6 +1406,11 @@ impl ExtendedCard {
     /// Display without associated type info.
     pub fn show(&self) -> String {
         match &self.parser_card {
+            ParserCard::AccountId20(a) => readable(self.indent, "AccountId20", &a.checksummed),
+            ParserCard::AuthorityIndex(a) => {
+                readable(self.indent, "Authority Index", &a.to_string())
+            }
+            ParserCard::BabePreDigest(a) => readable(self.indent, "BABE Pre-Digest", a),
             ParserCard::Balance(a) => {
                 readable(self.indent, "Balance", &format!("{} {}", a.number, a.units))
             }
@@ -1115,24 +1440,70 @@ impl ExtendedCard {
                 readable(self.indent, "BitVec<u64, Msb0>", &a.to_string())
             }
             ParserCard::BlockHash(a) => readable(self.indent, "Block Hash", &hex::encode(a)),
+            ParserCard::BlockNumber(a) => readable(self.indent, "Block Number", &a.to_string()),
             ParserCard::CallName(a) => readable(self.indent, "Call", a),
             ParserCard::CompositeAnnounced(a) => {
                 readable(self.indent, "Struct", &format!("{a} field(s)"))
             }
+            ParserCard::ConstantName(a) => readable(self.indent, "Constant", a),
+            ParserCard::Custom(a) => readable(self.indent, &a.title, &a.value),
+            ParserCard::DigestAnnounced(a) => {
+                readable(self.indent, "Digest", &format!("{a} item(s)"))
+            }
+            ParserCard::DigestConsensus(a) => readable(self.indent, "Consensus", &a.to_string()),
+            ParserCard::DigestOther => format!("{}Other Digest", "  ".repeat(self.indent as usize)),
+            ParserCard::DigestPreRuntime(a) => readable(self.indent, "Pre-Runtime", &a.to_string()),
+            ParserCard::DigestRuntimeEnvironmentUpdated => format!(
+                "{}Runtime Environment Updated",
+                "  ".repeat(self.indent as usize)
+            ),
+            ParserCard::DigestSeal(a) => readable(self.indent, "Seal", &a.to_string()),
             ParserCard::EmptyEnum => {
                 format!("{}Enum With No Variants", "  ".repeat(self.indent as usize))
             }
             ParserCard::EnumAnnounced => format!("{}Enum", "  ".repeat(self.indent as usize)),
             ParserCard::EnumVariantName(a) => readable(self.indent, "Enum Variant Name", a),
             ParserCard::Era(a) => match a {
-                Era::Immortal => readable(self.indent, "Era", "Immortal"),
-                Era::Mortal(period, phase) => readable(
+                Mortality::Immortal => readable(
+                    self.indent,
+                    "Era",
+                    "Immortal, warning: transaction never expires",
+                ),
+                Mortality::Mortal {
+                    period,
+                    phase,
+                    window: None,
+                } => readable(
                     self.indent,
                     "Era",
                     &format!("Mortal, phase: {phase}, period: {period}"),
                 ),
+                Mortality::Mortal {
+                    period,
+                    phase,
+                    window: Some(window),
+                } => readable(
+                    self.indent,
+                    "Era",
+                    &format!(
+                        "Mortal, phase: {phase}, period: {period}, valid from #{} to #{}",
+                        window.birth, window.death
+                    ),
+                ),
             },
+            ParserCard::Error {
+                position,
+                error,
+                raw_remaining,
+            } => readable(
+                self.indent,
+                "Decoding Error",
+                &format!("at position {position}: {error}, raw data: {raw_remaining}"),
+            ),
             ParserCard::EventName(a) => readable(self.indent, "Event", a),
+            ParserCard::ExtrinsicsRoot(a) => {
+                readable(self.indent, "Extrinsics Root", &hex::encode(a))
+            }
             ParserCard::FieldName(a) => readable(self.indent, "Field Name", a),
             ParserCard::FieldNumber(a) => readable(self.indent, "Field Number", &a.to_string()),
             ParserCard::GenesisHash(a) => readable(self.indent, "Genesis Hash", &hex::encode(a)),
@@ -1147,7 +1518,16 @@ impl ExtendedCard {
                 readable(self.indent, "Chain", &format!("{name}{version}"))
             }
             ParserCard::Nonce(a) => readable(self.indent, "Nonce", a),
+            ParserCard::PalletError {
+                pallet_name,
+                error_name,
+            } => readable(
+                self.indent,
+                "Pallet Error",
+                &format!("{pallet_name}::{error_name}"),
+            ),
             ParserCard::PalletName(a) => readable(self.indent, "Pallet", a),
+            ParserCard::ParentHash(a) => readable(self.indent, "Parent Hash", &hex::encode(a)),
             ParserCard::PerU16(a) => readable(self.indent, "PerU16", &a.deconstruct().to_string()),
             ParserCard::Percent(a) => {
                 readable(self.indent, "Percent", &a.deconstruct().to_string())
@@ -1187,6 +1567,8 @@ impl ExtendedCard {
             ParserCard::PublicEcdsa(a) => readable(self.indent, "PublicKey Ecdsa", &a.base58),
             #[cfg(all(not(feature = "std"), not(feature = "embed-display")))]
             ParserCard::PublicEcdsa(a) => readable(self.indent, "PublicKey Ecdsa", &a.hex),
+            ParserCard::RuntimeApiMethodName(a) => readable(self.indent, "Method", a),
+            ParserCard::RuntimeApiName(a) => readable(self.indent, "Runtime Api", a),
             ParserCard::SequenceAnnounced {
                 len,
                 element_info_flat: _,
@@ -1208,7 +1590,9 @@ impl ExtendedCard {
             ParserCard::SignatureEcdsa(a) => {
                 readable(self.indent, "Signature Ecdsa", &hex::encode(a.0))
             }
+            ParserCard::Slot(a) => readable(self.indent, "Slot", &a.to_string()),
             ParserCard::SpecName(a) => readable(self.indent, "Spec Name", a),
+            ParserCard::StateRoot(a) => readable(self.indent, "State Root", &hex::encode(a)),
             ParserCard::Text(a) => readable(self.indent, "Text", a),
             ParserCard::Tip(a) => {
                 readable(self.indent, "Tip", &format!("{} {}", a.number, a.units))
@@ -1217,6 +1601,8 @@ impl ExtendedCard {
                 readable(self.indent, "Tuple", &format!("{a} element(s)"))
             }
             ParserCard::TxVersion(a) => readable(self.indent, "Tx Version", a),
+            ParserCard::VrfOutput(a) => readable(self.indent, "VRF Output", &hex::encode(a.0)),
+            ParserCard::VrfProof(a) => readable(self.indent, "VRF Proof", &hex::encode(a.0)),
         }
     }
 
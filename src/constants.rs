@@ -0,0 +1,114 @@
+//! Interpret pallet constants.
+//!
+//! Pallet constants (for example, `Balances` pallet `ExistentialDeposit` or
+//! `System` pallet `BlockHashCount`) are stored in metadata itself, as
+//! SCALE-encoded values with associated type, see
+//! [`PalletConstantMetadata`](frame_metadata::v14::PalletConstantMetadata).
+//!
+//! Constant values are decoded here using the types from metadata. Constants
+//! known to be currency-related are marked as balances, and are displayed with
+//! chain decimals and units for appropriate
+//! [pallets](crate::cards::PALLETS_BALANCE_VALID).
+use external_memory_tools::ExternalMemory;
+use frame_metadata::v14::PalletConstantMetadata;
+use scale_info::form::PortableForm;
+
+use crate::std::{borrow::ToOwned, vec::Vec};
+
+use crate::cards::{Documented, PalletConstant};
+use crate::decoding_sci::{decode_with_type, Ty};
+use crate::error::{ConstantError, ParserError};
+use crate::propagated::Propagated;
+use crate::traits::AsMetadata;
+
+/// Decode pallet constant by pallet name and constant name.
+pub fn decode_constant<E, M>(
+    pallet_name: &str,
+    constant_name: &str,
+    ext_memory: &mut E,
+    meta: &M,
+) -> Result<PalletConstant, ConstantError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let constants = meta
+        .pallet_constants(pallet_name)
+        .ok_or_else(|| ConstantError::NoPallet {
+            pallet_name: pallet_name.to_owned(),
+        })?;
+    let constant_meta = constants
+        .iter()
+        .find(|constant_meta| constant_meta.name == constant_name)
+        .ok_or_else(|| ConstantError::NoConstant {
+            pallet_name: pallet_name.to_owned(),
+            constant_name: constant_name.to_owned(),
+        })?;
+    decode_constant_meta::<E, M>(pallet_name, constant_meta, ext_memory, &meta.types())
+}
+
+/// Decode all constants of a pallet, in order of appearance in metadata.
+pub fn decode_pallet_constants<E, M>(
+    pallet_name: &str,
+    ext_memory: &mut E,
+    meta: &M,
+) -> Result<Vec<PalletConstant>, ConstantError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let constants = meta
+        .pallet_constants(pallet_name)
+        .ok_or_else(|| ConstantError::NoPallet {
+            pallet_name: pallet_name.to_owned(),
+        })?;
+    let registry = meta.types();
+    constants
+        .iter()
+        .map(|constant_meta| {
+            decode_constant_meta::<E, M>(pallet_name, constant_meta, ext_memory, &registry)
+        })
+        .collect()
+}
+
+/// Decode constant value from [`PalletConstantMetadata`].
+///
+/// All value data is expected to be used.
+fn decode_constant_meta<E, M>(
+    pallet_name: &str,
+    constant_meta: &PalletConstantMetadata<PortableForm>,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<PalletConstant, ConstantError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let data = constant_meta.value.as_slice();
+    let mut position: usize = 0;
+    let value = decode_with_type::<&[u8], E, M>(
+        &Ty::Symbol(&constant_meta.ty),
+        &data,
+        ext_memory,
+        &mut position,
+        registry,
+        Propagated::from_constant_meta(constant_meta),
+    )
+    .and_then(|value| {
+        if position != data.len() {
+            Err(ParserError::SomeDataNotUsedBlob { from: position })
+        } else {
+            Ok(value)
+        }
+    })
+    .map_err(|parser_error| ConstantError::Parsing {
+        constant_name: constant_meta.name.to_owned(),
+        parser_error,
+    })?;
+    Ok(PalletConstant {
+        pallet_name: pallet_name.to_owned(),
+        constant_name: constant_meta.name.to_owned(),
+        docs: constant_meta.collect_docs(),
+        value,
+    })
+}
@@ -34,20 +34,19 @@ use core::{any::TypeId, mem::size_of};
 #[cfg(feature = "std")]
 use std::{any::TypeId, mem::size_of};
 
-use crate::cards::{
-    Call, Documented, Event, ExtendedData, FieldData, Info, PalletSpecificData, ParsedData,
-    SequenceData, SequenceRawData, VariantData,
-};
+use crate::cards::{Call, ExtendedData, Info, ParsedData, VariantData};
 use crate::compacts::{find_compact, get_compact};
-use crate::error::{ParserError, SignableError};
+use crate::error::{ParserError, SignableError, TypeCrumb};
 use crate::propagated::{Checker, Propagated, SpecialtySet};
+use crate::skipping::{fixed_encoded_len, skip_with_type};
 use crate::special_indicators::{
-    Hint, PalletSpecificItem, SpecialtyTypeChecked, SpecialtyTypeHinted, ENUM_INDEX_ENCODED_LEN,
+    CustomType, Hint, SpecialtyTypeChecked, SpecialtyTypeHinted, ENUM_INDEX_ENCODED_LEN,
 };
 use crate::special_types::{
-    special_case_era, special_case_h256, wrap_sequence, CheckCompact, UnsignedInteger,
+    special_case_era, special_case_h256, AccountId20, CheckCompact, UnsignedInteger,
 };
 use crate::traits::{AsMetadata, ResolveType};
+use crate::visitor::{variant_data, ExtendedDataBuilder, TolerantDataBuilder, Visitor};
 use crate::MarkedData;
 
 /// Finalize parsing of primitives (variants of [`TypeDefPrimitive`]).
@@ -250,8 +249,13 @@ where
     E: ExternalMemory,
     M: AsMetadata<E>,
 {
-    let husked_extrinsic_ty =
-        husk_type::<E, M>(extrinsic_ty, meta_v14_types, ext_memory, Checker::new())?;
+    let husked_extrinsic_ty = husk_type::<E, M>(
+        extrinsic_ty,
+        meta_v14_types,
+        ext_memory,
+        Checker::new(),
+        false,
+    )?;
 
     // check here that the underlying type is really `Vec<u8>`
     match husked_extrinsic_ty.ty.type_def {
@@ -306,6 +310,42 @@ pub const CALL_INDICATOR: &str = "Call";
 /// Processes input data byte-by-byte, starting at given position, selecting and
 /// decoding data chunks. Position changes as decoding proceeds.
 ///
+/// Decoded data is assembled into [`ExtendedData`] with
+/// [`ExtendedDataBuilder`], see [`visit_with_type`] for details.
+///
+/// Values of user-registered [`CustomTypes`](crate::special_indicators::CustomTypes),
+/// available through [`ResolveType::custom_types`] of the `registry`, are
+/// decoded into [`ParsedData::Custom`].
+pub fn decode_with_type<B, E, M>(
+    ty_input: &Ty,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    propagated: Propagated,
+) -> Result<ExtendedData, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    visit_with_type::<B, E, M, ExtendedDataBuilder>(
+        ty_input,
+        data,
+        ext_memory,
+        position,
+        registry,
+        propagated,
+        &mut ExtendedDataBuilder::new(),
+    )
+}
+
+/// General visiting function. Parse part of data as [`Ty`], passing decoded
+/// data to [`Visitor`].
+///
+/// Processes input data byte-by-byte, starting at given position, selecting and
+/// decoding data chunks. Position changes as decoding proceeds.
+///
 /// This function is sometimes used recursively. Specifically, it could be
 /// called on inner element(s) when decoding deals with:
 ///
@@ -333,66 +373,120 @@ pub const CALL_INDICATOR: &str = "Call";
 /// no repeating types that would cause an endless cycle. Cycle tracker gets
 /// nullified if the parser position gets changed, e.g. if new enum, vector,
 /// primitive or special type is encountered.
-pub fn decode_with_type<B, E, M>(
+///
+/// Errors occurring within the type get wrapped into
+/// [`ParserError::WithTrail`], with the path from the decoded type to the
+/// failure point: types, fields, variants, and element indices.
+pub fn visit_with_type<B, E, M, V>(
     ty_input: &Ty,
     data: &B,
     ext_memory: &mut E,
     position: &mut usize,
     registry: &M::TypeRegistry,
     mut propagated: Propagated,
-) -> Result<ExtendedData, ParserError<E>>
+    visitor: &mut V,
+) -> Result<V::Value, ParserError<E>>
 where
     B: AddressableBuffer<E>,
     E: ExternalMemory,
     M: AsMetadata<E>,
+    V: Visitor<E>,
 {
     let (ty, id) = match ty_input {
         Ty::Resolved(resolved_ty) => (resolved_ty.ty.to_owned(), resolved_ty.id),
         Ty::Symbol(ty_symbol) => (registry.resolve_ty(ty_symbol.id, ext_memory)?, ty_symbol.id),
     };
-    let info_ty = Info::from_ty(&ty);
-    propagated.add_info(&info_ty);
-    match SpecialtyTypeChecked::from_type::<B, E, M>(&ty, data, ext_memory, position, registry) {
+    if visitor.needs_info() {
+        let info_ty = Info::from_ty(&ty);
+        propagated.add_info(&info_ty);
+    }
+    visit_resolved::<B, E, M, V>(
+        &ty, id, data, ext_memory, position, registry, propagated, visitor,
+    )
+    .map_err(|error| {
+        error.add_crumb(TypeCrumb::Ty {
+            id,
+            path: ty.path.segments.to_owned(),
+        })
+    })
+}
+
+/// Parse part of data as resolved [`Type`] with known id, passing decoded
+/// data to [`Visitor`].
+///
+/// Current parser position gets changed.
+#[allow(clippy::too_many_arguments)]
+fn visit_resolved<B, E, M, V>(
+    ty: &Type<PortableForm>,
+    id: u32,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    mut propagated: Propagated,
+    visitor: &mut V,
+) -> Result<V::Value, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+    V: Visitor<E>,
+{
+    let custom_data = match registry
+        .custom_types()
+        .and_then(|custom_types| custom_types.find(&ty.path))
+    {
+        Some(custom_type) => decode_custom::<B, E, M>(
+            custom_type,
+            ty,
+            id,
+            data,
+            ext_memory,
+            position,
+            registry,
+            propagated.checker.to_owned(),
+        )?,
+        None => None,
+    };
+    if let Some(parsed_data) = custom_data {
+        return visitor.visit_special(parsed_data, propagated.info);
+    }
+
+    match SpecialtyTypeChecked::from_type::<B, E, M>(ty, data, ext_memory, position, registry) {
         SpecialtyTypeChecked::None => match &ty.type_def {
             TypeDef::Composite(x) => {
-                let field_data_set = decode_fields::<B, E, M>(
+                let values = visit_fields::<B, E, M, V>(
                     &x.fields,
                     data,
                     ext_memory,
                     position,
                     registry,
                     propagated.checker,
+                    visitor,
                 )?;
-                Ok(ExtendedData {
-                    data: ParsedData::Composite(field_data_set),
-                    info: propagated.info,
-                })
+                visitor.visit_composite(&x.fields, values, propagated.info)
             }
             TypeDef::Variant(x) => {
                 propagated.reject_compact()?;
                 if !x.variants.is_empty() {
-                    let variant_data = decode_variant::<B, E, M>(
+                    let (found_variant, values) = visit_variant::<B, E, M, V>(
                         &x.variants,
                         data,
                         ext_memory,
                         position,
                         registry,
+                        visitor,
+                        None,
                     )?;
-                    Ok(ExtendedData {
-                        data: ParsedData::Variant(variant_data),
-                        info: propagated.info,
-                    })
+                    visitor.visit_variant(found_variant, values, propagated.info)
                 } else {
-                    Ok(ExtendedData {
-                        data: ParsedData::EmptyEnum,
-                        info: propagated.info,
-                    })
+                    visitor.visit_empty_enum(propagated.info)
                 }
             }
             TypeDef::Sequence(x) => {
                 let number_of_elements = get_compact::<u32, B, E>(data, ext_memory, position)?;
                 propagated.checker.drop_cycle_check();
-                decode_elements_set::<B, E, M>(
+                visit_elements_set::<B, E, M, V>(
                     &x.type_param,
                     number_of_elements,
                     data,
@@ -400,9 +494,10 @@ where
                     position,
                     registry,
                     propagated,
+                    visitor,
                 )
             }
-            TypeDef::Array(x) => decode_elements_set::<B, E, M>(
+            TypeDef::Array(x) => visit_elements_set::<B, E, M, V>(
                 &x.type_param,
                 x.len,
                 data,
@@ -410,17 +505,21 @@ where
                 position,
                 registry,
                 propagated,
+                visitor,
             ),
             TypeDef::Tuple(x) => {
                 if x.fields.len() > 1 {
                     propagated.reject_compact()?;
                     propagated.forget_hint();
                 }
-                let mut tuple_data_set: Vec<ExtendedData> = Vec::new();
-                for inner_ty_symbol in x.fields.iter() {
+                let mut values: Vec<V::Value> = Vec::new();
+                for (index, inner_ty_symbol) in x.fields.iter().enumerate() {
+                    let add_crumb = move |error: ParserError<E>| {
+                        error.add_crumb(TypeCrumb::TupleElement(index))
+                    };
                     let id = inner_ty_symbol.id;
-                    let ty = registry.resolve_ty(id, ext_memory)?;
-                    let tuple_data_element = decode_with_type::<B, E, M>(
+                    let ty = registry.resolve_ty(id, ext_memory).map_err(add_crumb)?;
+                    let tuple_element_value = visit_tolerant::<B, E, M, V>(
                         &Ty::Resolved(ResolvedTy {
                             ty: ty.to_owned(),
                             id,
@@ -429,94 +528,101 @@ where
                         ext_memory,
                         position,
                         registry,
-                        Propagated::for_ty(&propagated.checker, &ty, id)?,
-                    )?;
-                    tuple_data_set.push(tuple_data_element);
+                        Propagated::for_ty(&propagated.checker, &ty, id).map_err(add_crumb)?,
+                        visitor,
+                        None,
+                    )
+                    .map_err(add_crumb)?;
+                    values.push(tuple_element_value);
+                    if visitor.is_halted() {
+                        break;
+                    }
                 }
-                Ok(ExtendedData {
-                    data: ParsedData::Tuple(tuple_data_set),
-                    info: propagated.info,
-                })
+                visitor.visit_tuple(values, propagated.info)
             }
-            TypeDef::Primitive(x) => Ok(ExtendedData {
-                data: decode_type_def_primitive::<B, E>(
+            TypeDef::Primitive(x) => {
+                let parsed_data = decode_type_def_primitive::<B, E>(
                     x,
                     data,
                     ext_memory,
                     position,
                     propagated.checker.specialty_set,
-                )?,
-                info: propagated.info,
-            }),
+                )?;
+                visitor.visit_primitive(parsed_data, propagated.info)
+            }
             TypeDef::Compact(x) => {
                 propagated.reject_compact()?;
                 propagated.checker.specialty_set.compact_at = Some(id);
                 propagated.checker.check_id(x.type_param.id)?;
-                decode_with_type::<B, E, M>(
+                visit_with_type::<B, E, M, V>(
                     &Ty::Symbol(&x.type_param),
                     data,
                     ext_memory,
                     position,
                     registry,
                     propagated,
+                    visitor,
                 )
             }
             TypeDef::BitSequence(x) => {
                 propagated.reject_compact()?;
-                Ok(ExtendedData {
-                    data: decode_type_def_bit_sequence::<B, E, M>(
-                        x, id, data, ext_memory, position, registry,
-                    )?,
-                    info: propagated.info,
-                })
+                let parsed_data = decode_type_def_bit_sequence::<B, E, M>(
+                    x, id, data, ext_memory, position, registry,
+                )?;
+                visitor.visit_bit_sequence(parsed_data, propagated.info)
             }
         },
-        SpecialtyTypeChecked::AccountId32 => Ok(ExtendedData {
-            data: AccountId32::parse_check_compact::<B, E>(
+        SpecialtyTypeChecked::AccountId20 => {
+            let parsed_data = AccountId20::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::AccountId32 => {
+            let parsed_data = AccountId32::parse_check_compact::<B, E>(
+                data,
+                ext_memory,
+                position,
+                propagated.compact_at(),
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
         SpecialtyTypeChecked::Era => {
             propagated.reject_compact()?;
-            Ok(ExtendedData {
-                data: special_case_era::<B, E>(data, ext_memory, position)?,
-                info: propagated.info,
-            })
+            let parsed_data = special_case_era::<B, E>(data, ext_memory, position)?;
+            visitor.visit_special(parsed_data, propagated.info)
         }
-        SpecialtyTypeChecked::H160 => Ok(ExtendedData {
-            data: H160::parse_check_compact::<B, E>(
+        SpecialtyTypeChecked::H160 => {
+            let parsed_data = H160::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
         SpecialtyTypeChecked::H256 => {
             propagated.reject_compact()?;
-            Ok(ExtendedData {
-                data: special_case_h256::<B, E>(
-                    data,
-                    ext_memory,
-                    position,
-                    propagated.checker.specialty_set.hash256(),
-                )?,
-                info: propagated.info,
-            })
+            let parsed_data = special_case_h256::<B, E>(
+                data,
+                ext_memory,
+                position,
+                propagated.checker.specialty_set.hash256(),
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
         }
-        SpecialtyTypeChecked::H512 => Ok(ExtendedData {
-            data: H512::parse_check_compact::<B, E>(
+        SpecialtyTypeChecked::H512 => {
+            let parsed_data = H512::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
         SpecialtyTypeChecked::PalletSpecific {
             pallet_name,
             pallet_info,
@@ -526,144 +632,279 @@ where
             item,
         } => {
             propagated.reject_compact()?;
-            let variant_data =
-                decode_variant::<B, E, M>(&variants, data, ext_memory, position, registry)?;
-            let pallet_specific_data = PalletSpecificData {
-                pallet_info,
-                variant_docs: variant_data.variant_docs.to_owned(),
+            let (found_variant, values) = visit_variant::<B, E, M, V>(
+                &variants,
+                data,
+                ext_memory,
+                position,
+                registry,
+                visitor,
+                Some(&pallet_name),
+            )?;
+            visitor.visit_pallet_specific(
+                item,
                 pallet_name,
-                variant_name: variant_data.variant_name.to_owned(),
-                fields: variant_data.fields,
-            };
-            match item {
-                PalletSpecificItem::Call => Ok(ExtendedData {
-                    data: ParsedData::Call(Call(pallet_specific_data)),
-                    info: propagated.info,
-                }),
-                PalletSpecificItem::Event => Ok(ExtendedData {
-                    data: ParsedData::Event(Event(pallet_specific_data)),
-                    info: propagated.info,
-                }),
-            }
+                pallet_info,
+                found_variant,
+                values,
+                propagated.info,
+            )
         }
-        SpecialtyTypeChecked::Perbill => Ok(ExtendedData {
-            data: Perbill::parse_check_compact::<B, E>(
+        SpecialtyTypeChecked::Perbill => {
+            let parsed_data = Perbill::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::Percent => Ok(ExtendedData {
-            data: Percent::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::Percent => {
+            let parsed_data = Percent::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::Permill => Ok(ExtendedData {
-            data: Permill::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::Permill => {
+            let parsed_data = Permill::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::Perquintill => Ok(ExtendedData {
-            data: Perquintill::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::Perquintill => {
+            let parsed_data = Perquintill::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::PerU16 => Ok(ExtendedData {
-            data: PerU16::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::PerU16 => {
+            let parsed_data = PerU16::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::PublicEd25519 => Ok(ExtendedData {
-            data: PublicEd25519::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::PublicEd25519 => {
+            let parsed_data = PublicEd25519::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::PublicSr25519 => Ok(ExtendedData {
-            data: PublicSr25519::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::PublicSr25519 => {
+            let parsed_data = PublicSr25519::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::PublicEcdsa => Ok(ExtendedData {
-            data: PublicEcdsa::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::PublicEcdsa => {
+            let parsed_data = PublicEcdsa::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::SignatureEd25519 => Ok(ExtendedData {
-            data: SignatureEd25519::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::SignatureEd25519 => {
+            let parsed_data = SignatureEd25519::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::SignatureSr25519 => Ok(ExtendedData {
-            data: SignatureSr25519::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::SignatureSr25519 => {
+            let parsed_data = SignatureSr25519::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
-        }),
-        SpecialtyTypeChecked::SignatureEcdsa => Ok(ExtendedData {
-            data: SignatureEcdsa::parse_check_compact::<B, E>(
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+        SpecialtyTypeChecked::SignatureEcdsa => {
+            let parsed_data = SignatureEcdsa::parse_check_compact::<B, E>(
                 data,
                 ext_memory,
                 position,
                 propagated.compact_at(),
-            )?,
-            info: propagated.info,
+            )?;
+            visitor.visit_special(parsed_data, propagated.info)
+        }
+    }
+}
+
+/// Parse part of data as user-registered [`CustomType`].
+///
+/// Encoded value length is found by skipping the value as a regular type. If
+/// the custom type does not accept the encoded value, the parser position is
+/// restored and `None` is returned, so that the value could be decoded as a
+/// regular type.
+#[allow(clippy::too_many_arguments)]
+fn decode_custom<B, E, M>(
+    custom_type: &dyn CustomType,
+    ty: &Type<PortableForm>,
+    id: u32,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    checker: Checker,
+) -> Result<Option<ParsedData>, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let start = *position;
+    skip_with_type::<B, E, M>(
+        &Ty::Resolved(ResolvedTy {
+            ty: ty.to_owned(),
+            id,
         }),
+        data,
+        ext_memory,
+        position,
+        registry,
+        checker,
+    )?;
+    let encoded = data
+        .read_slice(ext_memory, start, *position - start)?
+        .as_ref()
+        .to_vec();
+    match custom_type.decode(&ty.path, &encoded) {
+        Some(custom_value) => Ok(Some(ParsedData::Custom {
+            custom_value,
+            encoded,
+        })),
+        None => {
+            *position = start;
+            Ok(None)
+        }
+    }
+}
+
+/// Tolerant decoder function. Parse part of data as [`Ty`], recording decoding
+/// failures as [`ParsedData::Error`] nodes instead of returning an error.
+///
+/// Whatever was decoded before the failure is kept. Decoding resumes past the
+/// failed item only for vector and array elements with fixed encoded length,
+/// otherwise decoding halts and all remaining data goes into the error node.
+///
+/// Error could still be returned if the error node could not be built, i.e.
+/// if remaining data could not be accessed.
+pub fn decode_with_type_tolerant<B, E, M>(
+    ty_input: &Ty,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    propagated: Propagated,
+) -> Result<ExtendedData, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    visit_tolerant::<B, E, M, TolerantDataBuilder>(
+        ty_input,
+        data,
+        ext_memory,
+        position,
+        registry,
+        propagated,
+        &mut TolerantDataBuilder::new(),
+        None,
+    )
+}
+
+/// Parse part of data as [`Ty`], passing decoding failure to [`Visitor`] if
+/// the visitor is tolerant (see [`Visitor::is_tolerant`]).
+///
+/// On failure, if `resync_ty` is known and has fixed encoded length, the
+/// data of the failed item is skipped and decoding could proceed. Otherwise
+/// all remaining data is consumed, and decoding halts.
+///
+/// Current parser position gets changed.
+#[allow(clippy::too_many_arguments)]
+fn visit_tolerant<B, E, M, V>(
+    ty_input: &Ty,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    propagated: Propagated,
+    visitor: &mut V,
+    resync_ty: Option<&UntrackedSymbol<TypeId>>,
+) -> Result<V::Value, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+    V: Visitor<E>,
+{
+    let start = *position;
+    let error = match visit_with_type::<B, E, M, V>(
+        ty_input, data, ext_memory, position, registry, propagated, visitor,
+    ) {
+        Ok(value) => return Ok(value),
+        Err(error) => error,
+    };
+    if !visitor.is_tolerant() || start > data.total_len() {
+        return Err(error);
     }
+    let resync_end = match resync_ty {
+        Some(ty_symbol) => match fixed_encoded_len::<E, M>(ty_symbol, ext_memory, registry) {
+            Ok(Some(len)) if start + len <= data.total_len() => Some(start + len),
+            _ => None,
+        },
+        None => None,
+    };
+    let end = resync_end.unwrap_or(data.total_len());
+    let raw_remaining = match data.read_slice(ext_memory, start, end - start) {
+        Ok(a) => a.as_ref().to_vec(),
+        Err(_) => return Err(error),
+    };
+    *position = end;
+    visitor.visit_error(start, error, raw_remaining, resync_end.is_some())
 }
 
 /// Parse part of data as a set of [`Field`]s. Used for structs, enums and call
 /// decoding.
 ///
 /// Current parser position gets changed.
-fn decode_fields<B, E, M>(
+fn visit_fields<B, E, M, V>(
     fields: &[Field<PortableForm>],
     data: &B,
     ext_memory: &mut E,
     position: &mut usize,
     registry: &M::TypeRegistry,
     mut checker: Checker,
-) -> Result<Vec<FieldData>, ParserError<E>>
+    visitor: &mut V,
+) -> Result<Vec<V::Value>, ParserError<E>>
 where
     B: AddressableBuffer<E>,
     E: ExternalMemory,
     M: AsMetadata<E>,
+    V: Visitor<E>,
 {
     if fields.len() > 1 {
         // Only single-field structs can be processed as a compact.
@@ -675,24 +916,30 @@ where
         // Note: checker gets renewed when fields of enum are processed.
         checker.forget_hint();
     }
-    let mut out: Vec<FieldData> = Vec::new();
-    for field in fields.iter() {
-        let field_name = field.name.to_owned();
-        let type_name = field.type_name.to_owned();
-        let this_field_data = decode_with_type::<B, E, M>(
+    let mut out: Vec<V::Value> = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let add_crumb = |error: ParserError<E>| {
+            let crumb = match &field.name {
+                Some(field_name) => TypeCrumb::Field(field_name.to_owned()),
+                None => TypeCrumb::FieldNumber(index),
+            };
+            error.add_crumb(crumb)
+        };
+        let this_field_value = visit_tolerant::<B, E, M, V>(
             &Ty::Symbol(&field.ty),
             data,
             ext_memory,
             position,
             registry,
-            Propagated::for_field(&checker, field)?,
-        )?;
-        out.push(FieldData {
-            field_name,
-            type_name,
-            field_docs: field.collect_docs(),
-            data: this_field_data,
-        })
+            Propagated::for_field(&checker, field).map_err(add_crumb)?,
+            visitor,
+            None,
+        )
+        .map_err(add_crumb)?;
+        out.push(this_field_value);
+        if visitor.is_halted() {
+            break;
+        }
     }
     Ok(out)
 }
@@ -701,7 +948,8 @@ where
 /// and arrays.
 ///
 /// Current parser position gets changed.
-fn decode_elements_set<B, E, M>(
+#[allow(clippy::too_many_arguments)]
+fn visit_elements_set<B, E, M, V>(
     element: &UntrackedSymbol<TypeId>,
     number_of_elements: u32,
     data: &B,
@@ -709,54 +957,46 @@ fn decode_elements_set<B, E, M>(
     position: &mut usize,
     registry: &M::TypeRegistry,
     propagated: Propagated,
-) -> Result<ExtendedData, ParserError<E>>
+    visitor: &mut V,
+) -> Result<V::Value, ParserError<E>>
 where
     B: AddressableBuffer<E>,
     E: ExternalMemory,
     M: AsMetadata<E>,
+    V: Visitor<E>,
 {
     propagated.reject_compact()?;
 
-    let husked = husk_type::<E, M>(element, registry, ext_memory, propagated.checker)?;
+    let husked = husk_type::<E, M>(
+        element,
+        registry,
+        ext_memory,
+        propagated.checker,
+        visitor.needs_info(),
+    )?;
 
-    let data = {
-        if number_of_elements == 0 {
-            ParsedData::SequenceRaw(SequenceRawData {
-                element_info: husked.info,
-                data: Vec::new(),
-            })
-        } else {
-            let mut out: Vec<ParsedData> = Vec::new();
-            for _i in 0..number_of_elements {
-                let element_extended_data = decode_with_type::<B, E, M>(
-                    &Ty::Resolved(ResolvedTy {
-                        ty: husked.ty.to_owned(),
-                        id: husked.id,
-                    }),
-                    data,
-                    ext_memory,
-                    position,
-                    registry,
-                    Propagated::with_checker(husked.checker.clone()),
-                )?;
-                out.push(element_extended_data.data);
-            }
-            match wrap_sequence(&out) {
-                Some(sequence) => ParsedData::Sequence(SequenceData {
-                    element_info: husked.info,
-                    data: sequence,
-                }),
-                None => ParsedData::SequenceRaw(SequenceRawData {
-                    element_info: husked.info,
-                    data: out,
-                }),
-            }
+    let mut values: Vec<V::Value> = Vec::new();
+    for index in 0..number_of_elements {
+        let element_value = visit_tolerant::<B, E, M, V>(
+            &Ty::Resolved(ResolvedTy {
+                ty: husked.ty.to_owned(),
+                id: husked.id,
+            }),
+            data,
+            ext_memory,
+            position,
+            registry,
+            Propagated::with_checker(husked.checker.clone()),
+            visitor,
+            Some(element),
+        )
+        .map_err(|error| error.add_crumb(TypeCrumb::Element(index as usize)))?;
+        values.push(element_value);
+        if visitor.is_halted() {
+            break;
         }
-    };
-    Ok(ExtendedData {
-        data,
-        info: propagated.info,
-    })
+    }
+    visitor.visit_sequence(husked.info, values, propagated.info)
 }
 
 /// Select an enum variant based on data.
@@ -792,7 +1032,7 @@ where
 /// Parse part of data as a variant. Used for enums and call decoding.
 ///
 /// Current parser position gets changed.
-fn decode_variant<B, E, M>(
+pub(crate) fn decode_variant<B, E, M>(
     variants: &[Variant<PortableForm>],
     data: &B,
     ext_memory: &mut E,
@@ -803,29 +1043,68 @@ where
     B: AddressableBuffer<E>,
     E: ExternalMemory,
     M: AsMetadata<E>,
+{
+    let (found_variant, values) = visit_variant::<B, E, M, ExtendedDataBuilder>(
+        variants,
+        data,
+        ext_memory,
+        position,
+        registry,
+        &mut ExtendedDataBuilder::new(),
+        None,
+    )?;
+    Ok(variant_data(found_variant, values))
+}
+
+/// Select a variant based on data and parse its fields, passing decoded data
+/// to [`Visitor`].
+///
+/// `pallet_name` is known for calls and events, and is used only in
+/// [`TypeTrail`](crate::error::TypeTrail) of errors in variant fields.
+///
+/// Current parser position gets changed.
+#[allow(clippy::type_complexity)]
+fn visit_variant<'a, B, E, M, V>(
+    variants: &'a [Variant<PortableForm>],
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    visitor: &mut V,
+    pallet_name: Option<&str>,
+) -> Result<(&'a Variant<PortableForm>, Vec<V::Value>), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+    V: Visitor<E>,
 {
     let found_variant = pick_variant::<B, E>(variants, data, ext_memory, *position)?;
     *position += ENUM_INDEX_ENCODED_LEN;
-    let variant_name = found_variant.name.to_owned();
-    let variant_docs = found_variant.collect_docs();
-    let fields = decode_fields::<B, E, M>(
+    let values = visit_fields::<B, E, M, V>(
         &found_variant.fields,
         data,
         ext_memory,
         position,
         registry,
         Checker::new(),
-    )?;
-
-    Ok(VariantData {
-        variant_name,
-        variant_docs,
-        fields,
-    })
+        visitor,
+    )
+    .map_err(|error| {
+        let crumb = match pallet_name {
+            Some(pallet_name) => TypeCrumb::PalletSpecific {
+                pallet_name: pallet_name.to_owned(),
+                variant_name: found_variant.name.to_owned(),
+            },
+            None => TypeCrumb::Variant(found_variant.name.to_owned()),
+        };
+        error.add_crumb(crumb)
+    })?;
+    Ok((found_variant, values))
 }
 
 /// `BitOrder` as determined by the `bit_order_type` for [`TypeDefBitSequence`].
-enum FoundBitOrder {
+pub(crate) enum FoundBitOrder {
     Lsb0,
     Msb0,
 }
@@ -838,6 +1117,31 @@ const MSB0: &str = "Msb0";
 /// [bitvec::order::Lsb0].
 const LSB0: &str = "Lsb0";
 
+/// Find `BitOrder` for [`TypeDefBitSequence`].
+pub(crate) fn find_bit_order<E, M>(
+    bit_ty: &TypeDefBitSequence<PortableForm>,
+    id: u32,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<FoundBitOrder, ParserError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let bitorder_type = registry.resolve_ty(bit_ty.bit_order_type.id, ext_memory)?;
+    match &bitorder_type.type_def {
+        TypeDef::Composite(_) => match bitorder_type.path.ident() {
+            Some(x) => match x.as_str() {
+                LSB0 => Ok(FoundBitOrder::Lsb0),
+                MSB0 => Ok(FoundBitOrder::Msb0),
+                _ => Err(ParserError::NotBitOrderType { id }),
+            },
+            None => Err(ParserError::NotBitOrderType { id }),
+        },
+        _ => Err(ParserError::NotBitOrderType { id }),
+    }
+}
+
 /// Parse part of data as a bitvec.
 fn decode_type_def_bit_sequence<B, E, M>(
     bit_ty: &TypeDefBitSequence<PortableForm>,
@@ -855,18 +1159,7 @@ where
     let bitvec_start = *position;
 
     // BitOrder
-    let bitorder_type = registry.resolve_ty(bit_ty.bit_order_type.id, ext_memory)?;
-    let bitorder = match &bitorder_type.type_def {
-        TypeDef::Composite(_) => match bitorder_type.path.ident() {
-            Some(x) => match x.as_str() {
-                LSB0 => FoundBitOrder::Lsb0,
-                MSB0 => FoundBitOrder::Msb0,
-                _ => return Err(ParserError::NotBitOrderType { id }),
-            },
-            None => return Err(ParserError::NotBitOrderType { id }),
-        },
-        _ => return Err(ParserError::NotBitOrderType { id }),
-    };
+    let bitorder = find_bit_order::<E, M>(bit_ty, id, ext_memory, registry)?;
 
     // BitStore
     let bitstore_type = registry.resolve_ty(bit_ty.bit_store_type.id, ext_memory)?;
@@ -1185,22 +1478,26 @@ impl_patched!(Msb0, reform_vec_msb0);
 /// Element [`Info`] is collected while resolving the type. No identical
 /// [`Type`] `id`s are expected to be encountered (these are collected and
 /// checked in [`Checker`]), otherwise the resolving would go indefinitely.
-struct HuskedType {
-    info: Vec<Info>,
-    checker: Checker,
-    ty: Type<PortableForm>,
-    id: u32,
+pub(crate) struct HuskedType {
+    pub(crate) info: Vec<Info>,
+    pub(crate) checker: Checker,
+    pub(crate) ty: Type<PortableForm>,
+    pub(crate) id: u32,
 }
 
 /// Resolve [`Type`] of set element.
 ///
 /// Compact and single-field structs are resolved into corresponding inner
-/// types. All available [`Info`] is collected.
-fn husk_type<E, M>(
+/// types. All available [`Info`] is collected, if `collect_info` is set.
+///
+/// Resolving stops at special types, both built-in and user-registered
+/// [`CustomTypes`](crate::special_indicators::CustomTypes) of the `registry`.
+pub(crate) fn husk_type<E, M>(
     entry_symbol: &UntrackedSymbol<TypeId>,
     registry: &M::TypeRegistry,
     ext_memory: &mut E,
     mut checker: Checker,
+    collect_info: bool,
 ) -> Result<HuskedType, ParserError<E>>
 where
     E: ExternalMemory,
@@ -1218,9 +1515,19 @@ where
     let mut info: Vec<Info> = Vec::new();
 
     loop {
-        let info_ty = Info::from_ty(&ty);
-        if !info_ty.is_empty() {
-            info.push(info_ty)
+        if collect_info {
+            let info_ty = Info::from_ty(&ty);
+            if !info_ty.is_empty() {
+                info.push(info_ty)
+            }
+        }
+
+        if registry
+            .custom_types()
+            .and_then(|custom_types| custom_types.find(&ty.path))
+            .is_some()
+        {
+            break;
         }
 
         if let SpecialtyTypeHinted::None = SpecialtyTypeHinted::from_type(&ty) {
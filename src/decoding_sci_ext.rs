@@ -1,6 +1,8 @@
 //! Decode signable transaction extensions using `RuntimeMetadataV14`.
 use external_memory_tools::{AddressableBuffer, ExternalMemory};
+use frame_metadata::v14::SignedExtensionMetadata;
 use primitive_types::H256;
+use scale_info::form::PortableForm;
 
 #[cfg(not(feature = "std"))]
 use crate::additional_types::Era;
@@ -9,17 +11,18 @@ use sp_runtime::generic::Era;
 
 use crate::std::{
     borrow::ToOwned,
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
 
-use crate::cards::{ExtendedData, ParsedData};
+use crate::cards::{ExtendedData, ExtensionData, ParsedData};
 use crate::decoding_sci::{decode_with_type, Ty};
 use crate::error::{ExtensionsError, SignableError};
 use crate::propagated::Propagated;
-use crate::special_indicators::SpecialtyUnsignedInteger;
+use crate::special_indicators::{Hint, SpecialtyUnsignedInteger};
 use crate::special_types::UnsignedInteger;
-use crate::traits::AsMetadata;
+use crate::traits::{AsMetadata, SpecNameVersion};
 use crate::MarkedData;
 
 /// Parse extensions part of the signable transaction [`MarkedData`] using
@@ -34,13 +37,40 @@ use crate::MarkedData;
 /// [`ExtrinsicMetadata`](frame_metadata::v14::ExtrinsicMetadata).
 ///
 /// Whole `signed_extensions` set is scanned first for types in `ty` field, and
-/// then the second time, for types in `additional_signed` field.
+/// then the second time, for types in `additional_signed` field. Decoded
+/// parts are grouped into [`ExtensionData`], one entry per signed extension.
 pub fn decode_extensions<B, E, M>(
     marked_data: &MarkedData<B, E, M>,
     ext_memory: &mut E,
     meta_v14: &M,
     optional_genesis_hash: Option<H256>,
-) -> Result<Vec<ExtendedData>, SignableError<E, M>>
+) -> Result<Vec<ExtensionData>, SignableError<E, M>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    decode_extensions_with_handlers(
+        marked_data,
+        ext_memory,
+        meta_v14,
+        optional_genesis_hash,
+        &ExtensionHandlers::new(),
+    )
+}
+
+/// Parse extensions part of the signable transaction [`MarkedData`] using
+/// provided `V14` metadata and custom [`ExtensionHandlers`].
+///
+/// Same as [`decode_extensions`], with registered handlers applied to
+/// extensions with matching identifiers.
+pub fn decode_extensions_with_handlers<B, E, M>(
+    marked_data: &MarkedData<B, E, M>,
+    ext_memory: &mut E,
+    meta_v14: &M,
+    optional_genesis_hash: Option<H256>,
+    handlers: &ExtensionHandlers,
+) -> Result<Vec<ExtensionData>, SignableError<E, M>>
 where
     B: AddressableBuffer<E>,
     E: ExternalMemory,
@@ -49,12 +79,13 @@ where
     let mut position = marked_data.extensions_start();
     let data = marked_data.data();
 
-    decode_extensions_unmarked(
+    decode_extensions_unmarked_with_handlers(
         data,
         &mut position,
         ext_memory,
         meta_v14,
         optional_genesis_hash,
+        handlers,
     )
 }
 
@@ -69,69 +100,221 @@ where
 /// [`ExtrinsicMetadata`](frame_metadata::v14::ExtrinsicMetadata).
 ///
 /// Whole `signed_extensions` set is scanned first for types in `ty` field, and
-/// then the second time, for types in `additional_signed` field.
+/// then the second time, for types in `additional_signed` field. Decoded
+/// parts are grouped into [`ExtensionData`], one entry per signed extension.
 pub fn decode_extensions_unmarked<B, E, M>(
     data: &B,
     position: &mut usize,
     ext_memory: &mut E,
     meta_v14: &M,
     optional_genesis_hash: Option<H256>,
-) -> Result<Vec<ExtendedData>, SignableError<E, M>>
+) -> Result<Vec<ExtensionData>, SignableError<E, M>>
 where
     B: AddressableBuffer<E>,
     E: ExternalMemory,
     M: AsMetadata<E>,
 {
-    let mut extensions: Vec<ExtendedData> = Vec::new();
+    decode_extensions_unmarked_with_handlers(
+        data,
+        position,
+        ext_memory,
+        meta_v14,
+        optional_genesis_hash,
+        &ExtensionHandlers::new(),
+    )
+}
+
+/// Parse extensions part of the signable transaction using provided metadata
+/// and custom [`ExtensionHandlers`].
+///
+/// Same as [`decode_extensions_unmarked`]. For extensions with registered
+/// handler:
+///
+/// - handler [`Hint`], if any, replaces the built-in one
+/// - handler check runs after the built-in checks of the whole extensions set
+/// - handler display adjustments are applied to the output
+pub fn decode_extensions_unmarked_with_handlers<B, E, M>(
+    data: &B,
+    position: &mut usize,
+    ext_memory: &mut E,
+    meta_v14: &M,
+    optional_genesis_hash: Option<H256>,
+    handlers: &ExtensionHandlers,
+) -> Result<Vec<ExtensionData>, SignableError<E, M>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let mut extra_set: Vec<ExtendedData> = Vec::new();
+    let mut additional_signed_set: Vec<ExtendedData> = Vec::new();
     let meta_v14_types = meta_v14.types();
     let extrinsic = meta_v14.extrinsic().map_err(SignableError::MetaStructure)?;
     for signed_extensions_metadata in extrinsic.signed_extensions.iter() {
-        extensions.push(decode_with_type::<B, E, M>(
+        extra_set.push(decode_with_type::<B, E, M>(
             &Ty::Symbol(&signed_extensions_metadata.ty),
             data,
             ext_memory,
             position,
             &meta_v14_types,
-            Propagated::from_ext_meta(signed_extensions_metadata),
+            handlers.propagated(signed_extensions_metadata),
         )?)
     }
     for signed_extensions_metadata in extrinsic.signed_extensions.iter() {
-        extensions.push(decode_with_type::<B, E, M>(
+        additional_signed_set.push(decode_with_type::<B, E, M>(
             &Ty::Symbol(&signed_extensions_metadata.additional_signed),
             data,
             ext_memory,
             position,
             &meta_v14_types,
-            Propagated::from_ext_meta(signed_extensions_metadata),
+            handlers.propagated(signed_extensions_metadata),
         )?)
     }
     // `position > data.total_len()` is ruled out elsewhere
     if *position != data.total_len() {
         return Err(SignableError::SomeDataNotUsedExtensions { from: *position });
     }
+    let extensions: Vec<ExtensionData> = extrinsic
+        .signed_extensions
+        .iter()
+        .zip(extra_set)
+        .zip(additional_signed_set)
+        .map(
+            |((signed_extensions_metadata, extra), additional_signed)| ExtensionData {
+                identifier: signed_extensions_metadata.identifier.to_owned(),
+                extra,
+                additional_signed,
+            },
+        )
+        .collect();
     let spec_name_version = meta_v14
         .spec_name_version()
         .map_err(SignableError::MetaStructure)?;
-    check_extensions::<E, M>(
-        &extensions,
-        &spec_name_version.printed_spec_version,
-        optional_genesis_hash,
-    )?;
-    Ok(extensions)
+    check_extensions::<E, M>(&extensions, &spec_name_version, optional_genesis_hash)?;
+
+    // Custom checks and display adjustments.
+    for extension in extensions.iter() {
+        if let Some(handler) = handlers.get(&extension.identifier) {
+            handler
+                .check(&extension.extra, &extension.additional_signed)
+                .map_err(|reason| {
+                    SignableError::ExtensionsList(ExtensionsError::CustomCheck {
+                        identifier: extension.identifier.to_owned(),
+                        reason,
+                    })
+                })?;
+        }
+    }
+    Ok(extensions
+        .into_iter()
+        .map(|extension| match handlers.get(&extension.identifier) {
+            Some(handler) => ExtensionData {
+                identifier: extension.identifier,
+                extra: handler.display_extra(extension.extra),
+                additional_signed: handler.display_additional_signed(extension.additional_signed),
+            },
+            None => extension,
+        })
+        .collect())
+}
+
+/// Custom processing of a signed extension, attached to the signed extension
+/// identifier with [`ExtensionHandlers::register`].
+///
+/// All methods have defaults that keep the built-in behavior, so only the
+/// relevant ones need to be implemented.
+pub trait ExtensionHandler {
+    /// [`Hint`] for the extension data, determines the specialties of
+    /// unsigned integers and hashes in both `ty` and `additional_signed`
+    /// parts.
+    ///
+    /// If `None`, the built-in [`Hint`] for the identifier is used.
+    fn hint(&self) -> Option<Hint> {
+        None
+    }
+
+    /// Check decoded extension, with `ty` part in `extra`, and
+    /// `additional_signed` part in `additional_signed`.
+    ///
+    /// Error contains human-readable reason of the check failure.
+    fn check(
+        &self,
+        _extra: &ExtendedData,
+        _additional_signed: &ExtendedData,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Adjust decoded `ty` part of the extension for display.
+    fn display_extra(&self, extra: ExtendedData) -> ExtendedData {
+        extra
+    }
+
+    /// Adjust decoded `additional_signed` part of the extension for display.
+    fn display_additional_signed(&self, additional_signed: ExtendedData) -> ExtendedData {
+        additional_signed
+    }
+}
+
+/// Set of custom [`ExtensionHandler`]s, by signed extension identifier.
+#[derive(Default)]
+pub struct ExtensionHandlers {
+    handlers: Vec<(String, Box<dyn ExtensionHandler>)>,
+}
+
+impl ExtensionHandlers {
+    /// New empty set, only built-in extension processing is used.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach handler to signed extension identifier.
+    ///
+    /// Handler previously attached to the same identifier gets replaced.
+    pub fn register(&mut self, identifier: &str, handler: Box<dyn ExtensionHandler>) {
+        match self.handlers.iter_mut().find(|(id, _)| id == identifier) {
+            Some(entry) => entry.1 = handler,
+            None => self.handlers.push((identifier.to_owned(), handler)),
+        }
+    }
+
+    /// Handler attached to signed extension identifier, if any.
+    pub fn get(&self, identifier: &str) -> Option<&dyn ExtensionHandler> {
+        self.handlers
+            .iter()
+            .find(|(id, _)| id == identifier)
+            .map(|(_, handler)| handler.as_ref())
+    }
+
+    /// [`Propagated`] for signed extension decoding, with handler [`Hint`] if
+    /// available.
+    fn propagated(&self, signed_ext_meta: &SignedExtensionMetadata<PortableForm>) -> Propagated {
+        match self
+            .get(&signed_ext_meta.identifier)
+            .and_then(|handler| handler.hint())
+        {
+            Some(hint) => Propagated::from_hint(hint),
+            None => Propagated::from_ext_meta(signed_ext_meta),
+        }
+    }
 }
 
 /// Check collected extensions.
 ///
 /// Extensions must include metadata spec version and chain genesis hash.
 /// If extensions also include `Era`, block hash for immortal `Era` must match
-/// chain genesis hash.
+/// chain genesis hash. If both extensions and metadata runtime version include
+/// tx version, these must match.
 fn check_extensions<E: ExternalMemory, M: AsMetadata<E>>(
-    extensions: &[ExtendedData],
-    version: &str,
+    extensions: &[ExtensionData],
+    spec_name_version: &SpecNameVersion,
     optional_genesis_hash: Option<H256>,
 ) -> Result<(), SignableError<E, M>> {
     let mut collected_ext = CollectedExt::new();
-    for ext in extensions.iter() {
+    for ext in extensions
+        .iter()
+        .flat_map(|extension| [&extension.extra, &extension.additional_signed])
+    {
         // single-field structs are also checked
         if let ParsedData::Composite(ref field_data) = ext.data {
             if field_data.len() == 1 {
@@ -143,10 +326,10 @@ fn check_extensions<E: ExternalMemory, M: AsMetadata<E>>(
     }
     match collected_ext.spec_version_printed {
         Some(spec_version_found) => {
-            if spec_version_found != version {
+            if spec_version_found != spec_name_version.printed_spec_version {
                 return Err(SignableError::WrongSpecVersion {
                     as_decoded: spec_version_found,
-                    in_metadata: version.to_owned(),
+                    in_metadata: spec_name_version.printed_spec_version.to_owned(),
                 });
             }
         }
@@ -156,6 +339,16 @@ fn check_extensions<E: ExternalMemory, M: AsMetadata<E>>(
             ))
         }
     }
+    if let Some(tx_version_found) = collected_ext.tx_version_printed {
+        if let Some(ref tx_version) = spec_name_version.printed_tx_version {
+            if &tx_version_found != tx_version {
+                return Err(SignableError::WrongTxVersion {
+                    as_decoded: tx_version_found,
+                    in_metadata: tx_version.to_owned(),
+                });
+            }
+        }
+    }
     match collected_ext.genesis_hash {
         Some(found_genesis_hash) => {
             if let Some(genesis_hash) = optional_genesis_hash {
@@ -191,6 +384,7 @@ struct CollectedExt {
     genesis_hash: Option<H256>,
     block_hash: Option<H256>,
     spec_version_printed: Option<String>,
+    tx_version_printed: Option<String>,
 }
 
 impl CollectedExt {
@@ -201,6 +395,7 @@ impl CollectedExt {
             genesis_hash: None,
             block_hash: None,
             spec_version_printed: None,
+            tx_version_printed: None,
         }
     }
 
@@ -233,6 +428,26 @@ impl CollectedExt {
                 value,
                 specialty: SpecialtyUnsignedInteger::SpecVersion,
             } => self.add_spec_version::<u128, E, M>(*value),
+            ParsedData::PrimitiveU8 {
+                value,
+                specialty: SpecialtyUnsignedInteger::TxVersion,
+            } => self.add_tx_version::<u8, E, M>(*value),
+            ParsedData::PrimitiveU16 {
+                value,
+                specialty: SpecialtyUnsignedInteger::TxVersion,
+            } => self.add_tx_version::<u16, E, M>(*value),
+            ParsedData::PrimitiveU32 {
+                value,
+                specialty: SpecialtyUnsignedInteger::TxVersion,
+            } => self.add_tx_version::<u32, E, M>(*value),
+            ParsedData::PrimitiveU64 {
+                value,
+                specialty: SpecialtyUnsignedInteger::TxVersion,
+            } => self.add_tx_version::<u64, E, M>(*value),
+            ParsedData::PrimitiveU128 {
+                value,
+                specialty: SpecialtyUnsignedInteger::TxVersion,
+            } => self.add_tx_version::<u128, E, M>(*value),
             _ => Ok(()),
         }
     }
@@ -294,4 +509,19 @@ impl CollectedExt {
             Ok(())
         }
     }
+
+    /// Add tx version to set.
+    fn add_tx_version<T: UnsignedInteger, E: ExternalMemory, M: AsMetadata<E>>(
+        &mut self,
+        tx_version: T,
+    ) -> Result<(), SignableError<E, M>> {
+        if self.tx_version_printed.is_some() {
+            Err(SignableError::ExtensionsList(
+                ExtensionsError::TxVersionTwice,
+            ))
+        } else {
+            self.tx_version_printed = Some(tx_version.to_string());
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,724 @@
+//! Encode parsed data back into SCALE-encoded bytes.
+//!
+//! Encoding is driven by the types from the metadata types registry, same way
+//! as the decoding in
+//! [`decode_with_type`](crate::decoding_sci::decode_with_type). Types define
+//! compacts, variant indices, sequence length prefixes and `BitVec` layout,
+//! the [`ParsedData`] provides the values. Data decoded with a type and then
+//! encoded with the same type results in the same bytes.
+//!
+//! Data must match the type structure, including the data simplifications made
+//! during the decoding: single-field structs and compacts in vector and array
+//! elements are resolved into inner types (see
+//! [`husk_type`](crate::decoding_sci)), and sets of unsigned integers could be
+//! wrapped into [`Sequence`].
+//!
+//! [`ParsedData::Custom`] is encoded as the preserved encoded value.
+//!
+//! [`ParsedData::PalletError`] could not be encoded, as pallet index is not
+//! preserved in it.
+#[cfg(target_pointer_width = "32")]
+use bitvec::prelude::{BitOrder, BitVec};
+use external_memory_tools::ExternalMemory;
+use num_bigint::{BigInt, BigUint, Sign};
+use parity_scale_codec::{Compact, Encode};
+use scale_info::{
+    form::PortableForm, interner::UntrackedSymbol, Field, TypeDef, TypeDefBitSequence,
+    TypeDefPrimitive, Variant,
+};
+
+#[cfg(not(feature = "std"))]
+use core::any::TypeId;
+#[cfg(feature = "std")]
+use std::any::TypeId;
+
+use crate::std::{borrow::ToOwned, string::ToString, vec::Vec};
+
+use crate::cards::{
+    Call, Event, ExtendedData, FieldData, PalletSpecificData, ParsedData, Sequence, SequenceData,
+};
+use crate::decoding_sci::{find_bit_order, husk_type, FoundBitOrder, ResolvedTy, Ty};
+use crate::error::EncodeError;
+use crate::propagated::{Checker, SpecialtySet};
+use crate::special_indicators::{
+    PalletSpecificItem, SpecialtyTypeHinted, SpecialtyUnsignedInteger,
+};
+use crate::traits::{AsMetadata, ResolveType};
+
+/// Encoded length of `U256` and `I256`.
+const BIG_INTEGER_ENCODED_LEN: usize = 32;
+
+/// Encode [`ParsedData`] as a known type.
+pub fn encode_as_type<E, M>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    parsed_data: &ParsedData,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<Vec<u8>, EncodeError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let mut output: Vec<u8> = Vec::new();
+    encode_with_type::<E, M>(
+        &Ty::Symbol(ty_symbol),
+        parsed_data,
+        ext_memory,
+        registry,
+        Checker::new(),
+        &mut output,
+    )?;
+    Ok(output)
+}
+
+/// Encode [`ExtendedData`] as a known type.
+///
+/// Associated [`Info`](crate::cards::Info) is not used.
+pub fn encode_extended_data_as_type<E, M>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    extended_data: &ExtendedData,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<Vec<u8>, EncodeError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    encode_as_type::<E, M>(ty_symbol, &extended_data.data, ext_memory, registry)
+}
+
+/// General encoding function. Encode [`ParsedData`] as [`Ty`], appending the
+/// encoded bytes to `output`.
+///
+/// Type resolving follows
+/// [`decode_with_type`](crate::decoding_sci::decode_with_type), including the
+/// cycle checks.
+pub fn encode_with_type<E, M>(
+    ty_input: &Ty,
+    parsed_data: &ParsedData,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    mut checker: Checker,
+    output: &mut Vec<u8>,
+) -> Result<(), EncodeError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let (ty, id) = match ty_input {
+        Ty::Resolved(resolved_ty) => (resolved_ty.ty.to_owned(), resolved_ty.id),
+        Ty::Symbol(ty_symbol) => (registry.resolve_ty(ty_symbol.id, ext_memory)?, ty_symbol.id),
+    };
+    if let ParsedData::Custom { encoded, .. } = parsed_data {
+        output.extend_from_slice(encoded);
+        return Ok(());
+    }
+    let compact = checker.specialty_set.compact_at.is_some();
+    match (SpecialtyTypeHinted::from_type(&ty), parsed_data) {
+        (SpecialtyTypeHinted::AccountId20, ParsedData::AccountId20(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::AccountId32, ParsedData::Id(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::Era, ParsedData::Era(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::H160, ParsedData::H160(a)) => {
+            checker.reject_compact()?;
+            output.extend_from_slice(a.as_bytes())
+        }
+        (
+            SpecialtyTypeHinted::H256,
+            ParsedData::H256(a) | ParsedData::BlockHash(a) | ParsedData::GenesisHash(a),
+        ) => {
+            checker.reject_compact()?;
+            output.extend_from_slice(a.as_bytes())
+        }
+        (SpecialtyTypeHinted::H512, ParsedData::H512(a)) => {
+            checker.reject_compact()?;
+            output.extend_from_slice(a.as_bytes())
+        }
+        (
+            SpecialtyTypeHinted::PalletSpecific(PalletSpecificItem::Call),
+            ParsedData::Call(Call(pallet_specific_data)),
+        )
+        | (
+            SpecialtyTypeHinted::PalletSpecific(PalletSpecificItem::Event),
+            ParsedData::Event(Event(pallet_specific_data)),
+        ) => {
+            checker.reject_compact()?;
+            if let TypeDef::Variant(x) = &ty.type_def {
+                encode_pallet_specific::<E, M>(
+                    &x.variants,
+                    id,
+                    pallet_specific_data,
+                    ext_memory,
+                    registry,
+                    output,
+                )?
+            } else {
+                return Err(EncodeError::TypeMismatch { id });
+            }
+        }
+        (SpecialtyTypeHinted::Perbill, ParsedData::Perbill(a)) => {
+            encode_unsigned::<u32>(a.deconstruct(), compact, output)
+        }
+        (SpecialtyTypeHinted::Percent, ParsedData::Percent(a)) => {
+            encode_unsigned::<u8>(a.deconstruct(), compact, output)
+        }
+        (SpecialtyTypeHinted::Permill, ParsedData::Permill(a)) => {
+            encode_unsigned::<u32>(a.deconstruct(), compact, output)
+        }
+        (SpecialtyTypeHinted::Perquintill, ParsedData::Perquintill(a)) => {
+            encode_unsigned::<u64>(a.deconstruct(), compact, output)
+        }
+        (SpecialtyTypeHinted::PerU16, ParsedData::PerU16(a)) => {
+            encode_unsigned::<u16>(a.deconstruct(), compact, output)
+        }
+        (SpecialtyTypeHinted::PublicEd25519, ParsedData::PublicEd25519(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::PublicSr25519, ParsedData::PublicSr25519(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::PublicEcdsa, ParsedData::PublicEcdsa(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::SignatureEd25519, ParsedData::SignatureEd25519(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::SignatureSr25519, ParsedData::SignatureSr25519(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        (SpecialtyTypeHinted::SignatureEcdsa, ParsedData::SignatureEcdsa(a)) => {
+            checker.reject_compact()?;
+            a.encode_to(output)
+        }
+        // Pallet-specific types that were not recognized as calls or events
+        // during the decoding are regular enums.
+        (
+            SpecialtyTypeHinted::None
+            | SpecialtyTypeHinted::PalletSpecific(_)
+            | SpecialtyTypeHinted::UncheckedExtrinsic,
+            _,
+        ) => match &ty.type_def {
+            TypeDef::Composite(x) => {
+                if let ParsedData::Composite(field_data_set) = parsed_data {
+                    encode_fields::<E, M>(
+                        &x.fields,
+                        field_data_set,
+                        id,
+                        ext_memory,
+                        registry,
+                        checker,
+                        output,
+                    )?
+                } else {
+                    return Err(EncodeError::TypeMismatch { id });
+                }
+            }
+            TypeDef::Variant(x) => {
+                checker.reject_compact()?;
+                match parsed_data {
+                    ParsedData::Variant(variant_data) => {
+                        let found_variant =
+                            find_variant::<E>(&x.variants, &variant_data.variant_name, id)?;
+                        output.push(found_variant.index);
+                        encode_fields::<E, M>(
+                            &found_variant.fields,
+                            &variant_data.fields,
+                            id,
+                            ext_memory,
+                            registry,
+                            Checker::new(),
+                            output,
+                        )?
+                    }
+                    ParsedData::EmptyEnum if x.variants.is_empty() => {}
+                    _ => return Err(EncodeError::TypeMismatch { id }),
+                }
+            }
+            TypeDef::Sequence(x) => {
+                let elements = sequence_elements::<E>(parsed_data, id)?;
+                Compact(elements.len() as u32).encode_to(output);
+                checker.drop_cycle_check();
+                encode_elements_set::<E, M>(
+                    &x.type_param,
+                    &elements,
+                    ext_memory,
+                    registry,
+                    checker,
+                    output,
+                )?
+            }
+            TypeDef::Array(x) => {
+                let elements = sequence_elements::<E>(parsed_data, id)?;
+                if elements.len() != x.len as usize {
+                    return Err(EncodeError::LengthMismatch {
+                        id,
+                        expected: x.len as usize,
+                        found: elements.len(),
+                    });
+                }
+                encode_elements_set::<E, M>(
+                    &x.type_param,
+                    &elements,
+                    ext_memory,
+                    registry,
+                    checker,
+                    output,
+                )?
+            }
+            TypeDef::Tuple(x) => {
+                if let ParsedData::Tuple(extended_data_set) = parsed_data {
+                    if extended_data_set.len() != x.fields.len() {
+                        return Err(EncodeError::LengthMismatch {
+                            id,
+                            expected: x.fields.len(),
+                            found: extended_data_set.len(),
+                        });
+                    }
+                    if x.fields.len() > 1 {
+                        checker.reject_compact()?;
+                        checker.forget_hint();
+                    }
+                    for (inner_ty_symbol, extended_data) in
+                        x.fields.iter().zip(extended_data_set.iter())
+                    {
+                        let inner_id = inner_ty_symbol.id;
+                        let inner_ty = registry.resolve_ty(inner_id, ext_memory)?;
+                        let inner_checker = checker.update_for_ty(&inner_ty, inner_id)?;
+                        encode_with_type::<E, M>(
+                            &Ty::Resolved(ResolvedTy {
+                                ty: inner_ty,
+                                id: inner_id,
+                            }),
+                            &extended_data.data,
+                            ext_memory,
+                            registry,
+                            inner_checker,
+                            output,
+                        )?;
+                    }
+                } else {
+                    return Err(EncodeError::TypeMismatch { id });
+                }
+            }
+            TypeDef::Primitive(x) => {
+                encode_type_def_primitive::<E>(x, parsed_data, id, checker.specialty_set, output)?
+            }
+            TypeDef::Compact(x) => {
+                checker.reject_compact()?;
+                checker.specialty_set.compact_at = Some(id);
+                checker.check_id(x.type_param.id)?;
+                encode_with_type::<E, M>(
+                    &Ty::Symbol(&x.type_param),
+                    parsed_data,
+                    ext_memory,
+                    registry,
+                    checker,
+                    output,
+                )?
+            }
+            TypeDef::BitSequence(x) => {
+                checker.reject_compact()?;
+                encode_type_def_bit_sequence::<E, M>(
+                    x,
+                    id,
+                    parsed_data,
+                    ext_memory,
+                    registry,
+                    output,
+                )?
+            }
+        },
+        _ => return Err(EncodeError::TypeMismatch { id }),
+    }
+    Ok(())
+}
+
+/// Encode a set of [`FieldData`] as corresponding [`Field`]s. Used for
+/// structs, enums, calls and events.
+fn encode_fields<E, M>(
+    fields: &[Field<PortableForm>],
+    field_data_set: &[FieldData],
+    id: u32,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    mut checker: Checker,
+    output: &mut Vec<u8>,
+) -> Result<(), EncodeError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    if fields.len() != field_data_set.len() {
+        return Err(EncodeError::LengthMismatch {
+            id,
+            expected: fields.len(),
+            found: field_data_set.len(),
+        });
+    }
+    if fields.len() > 1 {
+        // Only single-field structs can be processed as a compact.
+        checker.reject_compact()?;
+        checker.forget_hint();
+    }
+    for (field, field_data) in fields.iter().zip(field_data_set.iter()) {
+        encode_with_type::<E, M>(
+            &Ty::Symbol(&field.ty),
+            &field_data.data.data,
+            ext_memory,
+            registry,
+            checker.update_for_field(field)?,
+            output,
+        )?;
+    }
+    Ok(())
+}
+
+/// Encode a set of identical elements. Used for vectors and arrays.
+///
+/// Sequence length prefix, if any, is expected to be already in the `output`.
+fn encode_elements_set<E, M>(
+    element: &UntrackedSymbol<TypeId>,
+    elements: &[ParsedData],
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    checker: Checker,
+    output: &mut Vec<u8>,
+) -> Result<(), EncodeError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    checker.reject_compact()?;
+
+    let husked = husk_type::<E, M>(element, registry, ext_memory, checker, false)?;
+
+    for element_data in elements.iter() {
+        encode_with_type::<E, M>(
+            &Ty::Resolved(ResolvedTy {
+                ty: husked.ty.to_owned(),
+                id: husked.id,
+            }),
+            element_data,
+            ext_memory,
+            registry,
+            husked.checker.clone(),
+            output,
+        )?;
+    }
+    Ok(())
+}
+
+/// Find enum [`Variant`] by name.
+fn find_variant<'a, E: ExternalMemory>(
+    variants: &'a [Variant<PortableForm>],
+    variant_name: &str,
+    id: u32,
+) -> Result<&'a Variant<PortableForm>, EncodeError<E>> {
+    variants
+        .iter()
+        .find(|variant| variant.name == variant_name)
+        .ok_or_else(|| EncodeError::NoVariant {
+            id,
+            variant_name: variant_name.to_string(),
+        })
+}
+
+/// Encode call or event.
+///
+/// Pallet variant is found by pallet name in the outer enum, call or event
+/// variant is found by name in the pallet enum.
+fn encode_pallet_specific<E, M>(
+    pallet_variants: &[Variant<PortableForm>],
+    id: u32,
+    pallet_specific_data: &PalletSpecificData,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    output: &mut Vec<u8>,
+) -> Result<(), EncodeError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let pallet_variant = find_variant::<E>(pallet_variants, &pallet_specific_data.pallet_name, id)?;
+    if pallet_variant.fields.len() != 1 {
+        return Err(EncodeError::LengthMismatch {
+            id,
+            expected: 1,
+            found: pallet_variant.fields.len(),
+        });
+    }
+    let item_ty_id = pallet_variant.fields[0].ty.id;
+    let item_ty = registry.resolve_ty(item_ty_id, ext_memory)?;
+    if let TypeDef::Variant(x) = &item_ty.type_def {
+        let found_variant =
+            find_variant::<E>(&x.variants, &pallet_specific_data.variant_name, item_ty_id)?;
+        output.push(pallet_variant.index);
+        output.push(found_variant.index);
+        encode_fields::<E, M>(
+            &found_variant.fields,
+            &pallet_specific_data.fields,
+            item_ty_id,
+            ext_memory,
+            registry,
+            Checker::new(),
+            output,
+        )
+    } else {
+        Err(EncodeError::TypeMismatch { id: item_ty_id })
+    }
+}
+
+/// Unwrap a set of unsigned integers into individual [`ParsedData`] elements.
+macro_rules! unwrap_unsigned {
+    ($set: expr, $variant: ident) => {
+        $set.iter()
+            .map(|value| ParsedData::$variant {
+                value: *value,
+                specialty: SpecialtyUnsignedInteger::None,
+            })
+            .collect()
+    };
+}
+
+/// Get individual elements of a vector or an array.
+///
+/// Elements wrapped into [`Sequence`] are unwrapped.
+fn sequence_elements<E: ExternalMemory>(
+    parsed_data: &ParsedData,
+    id: u32,
+) -> Result<Vec<ParsedData>, EncodeError<E>> {
+    match parsed_data {
+        ParsedData::SequenceRaw(sequence_raw_data) => Ok(sequence_raw_data.data.to_owned()),
+        ParsedData::Sequence(sequence_data) => Ok(match &sequence_data.data {
+            Sequence::U8(set) => unwrap_unsigned!(set, PrimitiveU8),
+            Sequence::U16(set) => unwrap_unsigned!(set, PrimitiveU16),
+            Sequence::U32(set) => unwrap_unsigned!(set, PrimitiveU32),
+            Sequence::U64(set) => unwrap_unsigned!(set, PrimitiveU64),
+            Sequence::U128(set) => unwrap_unsigned!(set, PrimitiveU128),
+            Sequence::VecU8 {
+                sequence,
+                inner_element_info,
+            } => sequence
+                .iter()
+                .map(|inner_set| {
+                    ParsedData::Sequence(SequenceData {
+                        element_info: inner_element_info.to_owned(),
+                        data: Sequence::U8(inner_set.to_owned()),
+                    })
+                })
+                .collect(),
+        }),
+        _ => Err(EncodeError::TypeMismatch { id }),
+    }
+}
+
+/// Encode unsigned integer, as is or as a compact.
+pub(crate) fn encode_unsigned<T>(value: T, compact: bool, output: &mut Vec<u8>)
+where
+    T: Encode,
+    Compact<T>: Encode,
+{
+    if compact {
+        Compact(value).encode_to(output)
+    } else {
+        value.encode_to(output)
+    }
+}
+
+/// Encode primitive (variants of [`TypeDefPrimitive`]).
+///
+/// Compacts are allowed for unsigned integers only.
+fn encode_type_def_primitive<E: ExternalMemory>(
+    found_ty: &TypeDefPrimitive,
+    parsed_data: &ParsedData,
+    id: u32,
+    specialty_set: SpecialtySet,
+    output: &mut Vec<u8>,
+) -> Result<(), EncodeError<E>> {
+    let compact = specialty_set.compact_at.is_some();
+    match (found_ty, parsed_data) {
+        (TypeDefPrimitive::U8, ParsedData::PrimitiveU8 { value, .. }) => {
+            encode_unsigned::<u8>(*value, compact, output)
+        }
+        (TypeDefPrimitive::U16, ParsedData::PrimitiveU16 { value, .. }) => {
+            encode_unsigned::<u16>(*value, compact, output)
+        }
+        (TypeDefPrimitive::U32, ParsedData::PrimitiveU32 { value, .. }) => {
+            encode_unsigned::<u32>(*value, compact, output)
+        }
+        (TypeDefPrimitive::U64, ParsedData::PrimitiveU64 { value, .. }) => {
+            encode_unsigned::<u64>(*value, compact, output)
+        }
+        (TypeDefPrimitive::U128, ParsedData::PrimitiveU128 { value, .. }) => {
+            encode_unsigned::<u128>(*value, compact, output)
+        }
+        _ => {
+            specialty_set.reject_compact()?;
+            match (found_ty, parsed_data) {
+                (TypeDefPrimitive::Bool, ParsedData::PrimitiveBool(a)) => a.encode_to(output),
+                (TypeDefPrimitive::Char, ParsedData::PrimitiveChar(a)) => {
+                    (*a as u32).encode_to(output)
+                }
+                (TypeDefPrimitive::Str, ParsedData::Text { text, .. }) => text.encode_to(output),
+                (TypeDefPrimitive::U256, ParsedData::PrimitiveU256(a)) => {
+                    encode_big_unsigned(a, output).ok_or(EncodeError::TypeMismatch { id })?
+                }
+                (TypeDefPrimitive::I8, ParsedData::PrimitiveI8(a)) => a.encode_to(output),
+                (TypeDefPrimitive::I16, ParsedData::PrimitiveI16(a)) => a.encode_to(output),
+                (TypeDefPrimitive::I32, ParsedData::PrimitiveI32(a)) => a.encode_to(output),
+                (TypeDefPrimitive::I64, ParsedData::PrimitiveI64(a)) => a.encode_to(output),
+                (TypeDefPrimitive::I128, ParsedData::PrimitiveI128(a)) => a.encode_to(output),
+                (TypeDefPrimitive::I256, ParsedData::PrimitiveI256(a)) => {
+                    encode_big_signed(a, output).ok_or(EncodeError::TypeMismatch { id })?
+                }
+                _ => return Err(EncodeError::TypeMismatch { id }),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encode `U256`.
+///
+/// Returns `None` if the value does not fit.
+pub(crate) fn encode_big_unsigned(value: &BigUint, output: &mut Vec<u8>) -> Option<()> {
+    let mut bytes = value.to_bytes_le();
+    if bytes.len() > BIG_INTEGER_ENCODED_LEN {
+        return None;
+    }
+    bytes.resize(BIG_INTEGER_ENCODED_LEN, 0);
+    output.extend_from_slice(&bytes);
+    Some(())
+}
+
+/// Encode `I256`.
+///
+/// Returns `None` if the value does not fit.
+pub(crate) fn encode_big_signed(value: &BigInt, output: &mut Vec<u8>) -> Option<()> {
+    let mut bytes = value.to_signed_bytes_le();
+    if bytes.len() > BIG_INTEGER_ENCODED_LEN {
+        return None;
+    }
+    let filler = match value.sign() {
+        Sign::Minus => 0xff,
+        Sign::NoSign | Sign::Plus => 0,
+    };
+    bytes.resize(BIG_INTEGER_ENCODED_LEN, filler);
+    output.extend_from_slice(&bytes);
+    Some(())
+}
+
+/// Encode bitvec.
+///
+/// `BitStore` and `BitOrder` of the type must match the parsed data.
+fn encode_type_def_bit_sequence<E, M>(
+    bit_ty: &TypeDefBitSequence<PortableForm>,
+    id: u32,
+    parsed_data: &ParsedData,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    output: &mut Vec<u8>,
+) -> Result<(), EncodeError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let bitorder = find_bit_order::<E, M>(bit_ty, id, ext_memory, registry)?;
+    let bitstore_type = registry.resolve_ty(bit_ty.bit_store_type.id, ext_memory)?;
+
+    match (&bitstore_type.type_def, bitorder, parsed_data) {
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U8),
+            FoundBitOrder::Lsb0,
+            ParsedData::BitVecU8Lsb0(a),
+        ) => a.encode_to(output),
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U8),
+            FoundBitOrder::Msb0,
+            ParsedData::BitVecU8Msb0(a),
+        ) => a.encode_to(output),
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U16),
+            FoundBitOrder::Lsb0,
+            ParsedData::BitVecU16Lsb0(a),
+        ) => a.encode_to(output),
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U16),
+            FoundBitOrder::Msb0,
+            ParsedData::BitVecU16Msb0(a),
+        ) => a.encode_to(output),
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U32),
+            FoundBitOrder::Lsb0,
+            ParsedData::BitVecU32Lsb0(a),
+        ) => a.encode_to(output),
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U32),
+            FoundBitOrder::Msb0,
+            ParsedData::BitVecU32Msb0(a),
+        ) => a.encode_to(output),
+        #[cfg(target_pointer_width = "64")]
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U64),
+            FoundBitOrder::Lsb0,
+            ParsedData::BitVecU64Lsb0(a),
+        ) => a.encode_to(output),
+        #[cfg(target_pointer_width = "64")]
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U64),
+            FoundBitOrder::Msb0,
+            ParsedData::BitVecU64Msb0(a),
+        ) => a.encode_to(output),
+        #[cfg(target_pointer_width = "32")]
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U64),
+            FoundBitOrder::Lsb0,
+            ParsedData::BitVecU64Lsb0(a),
+        ) => patch_encode_bitvec_u64(a, false, output),
+        #[cfg(target_pointer_width = "32")]
+        (
+            TypeDef::Primitive(TypeDefPrimitive::U64),
+            FoundBitOrder::Msb0,
+            ParsedData::BitVecU64Msb0(a),
+        ) => patch_encode_bitvec_u64(a, true, output),
+        _ => return Err(EncodeError::TypeMismatch { id }),
+    }
+    Ok(())
+}
+
+/// Encode `BitVec` with `u64` `BitStore` that is stored as `BitVec` with
+/// `u32` `BitStore` in 32bit targets.
+///
+/// Pairs of `u32` elements are assembled into `u64` elements. For `Msb0`, the
+/// first element of the pair is the most significant one.
+#[cfg(target_pointer_width = "32")]
+pub(crate) fn patch_encode_bitvec_u64<O: BitOrder>(
+    bitvec: &BitVec<u32, O>,
+    msb0: bool,
+    output: &mut Vec<u8>,
+) {
+    Compact(bitvec.len() as u32).encode_to(output);
+    for pair in bitvec.as_raw_slice().chunks(2) {
+        let first = pair[0] as u64;
+        let second = pair.get(1).copied().unwrap_or(0) as u64;
+        let element = if msb0 {
+            (first << 32) | second
+        } else {
+            first | (second << 32)
+        };
+        output.extend_from_slice(&element.to_le_bytes());
+    }
+}
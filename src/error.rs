@@ -1,8 +1,9 @@
 //! Errors.
 use external_memory_tools::BufferError;
+use frame_metadata::META_RESERVED;
 use primitive_types::H256;
 
-use crate::std::string::String;
+use crate::std::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
 
 #[cfg(feature = "std")]
 use std::{
@@ -45,6 +46,10 @@ where
         as_decoded: String,
         in_metadata: String,
     },
+    WrongTxVersion {
+        as_decoded: String,
+        in_metadata: String,
+    },
 }
 
 impl<E, M> SignableError<E, M>
@@ -64,6 +69,7 @@ where
             SignableError::SomeDataNotUsedExtensions { from } => format!("Some extensions data (input positions [{from}..]) remained unused after decoding."),
             SignableError::WrongGenesisHash { as_decoded, expected } => format!("Wrong chain. Apparent genesis hash in extensions {} does not match the expected one {}.", hex::encode(as_decoded.0), hex::encode(expected.0)),
             SignableError::WrongSpecVersion { as_decoded, in_metadata} => format!("Wrong metadata spec version. When decoding extensions data with metadata version {in_metadata}, the apparent spec version in extensions is {as_decoded}."),
+            SignableError::WrongTxVersion { as_decoded, in_metadata} => format!("Wrong metadata tx version. When decoding extensions data with metadata tx version {in_metadata}, the apparent tx version in extensions is {as_decoded}."),
         }
     }
 }
@@ -112,7 +118,256 @@ impl<E: ExternalMemory> StorageError<E> {
     }
 }
 
+/// Errors in block parsing.
+#[derive(Debug, Eq, PartialEq)]
+pub enum BlockError<E: ExternalMemory> {
+    BodyNoCompact,
+    ExtrinsicNoCompact { index: usize },
+    Header(HeaderError<E>),
+    Parsing(ParserError<E>),
+    SomeDataNotUsed { from: usize },
+}
+
+impl<E: ExternalMemory> BlockError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            BlockError::BodyNoCompact => String::from("Block body was expected to be a SCALE-encoded vector of extrinsics. Have not found a compact indicating the number of extrinsics."),
+            BlockError::ExtrinsicNoCompact { index } => format!("Extrinsic {index} in block body was expected to be a SCALE-encoded opaque `Vec<u8>`. Have not found a compact indicating extrinsic length."),
+            BlockError::Header(header_error) => format!("{header_error}"),
+            BlockError::Parsing(parser_error) => format!("Error parsing block body. {parser_error}"),
+            BlockError::SomeDataNotUsed { from } => format!("Some block data (input positions [{from}..]) remained unused after decoding."),
+        }
+    }
+}
+
+/// Errors in pallet constants parsing.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ConstantError<E: ExternalMemory> {
+    NoConstant {
+        pallet_name: String,
+        constant_name: String,
+    },
+    NoPallet {
+        pallet_name: String,
+    },
+    Parsing {
+        constant_name: String,
+        parser_error: ParserError<E>,
+    },
+}
+
+impl<E: ExternalMemory> ConstantError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            ConstantError::NoConstant {
+                pallet_name,
+                constant_name,
+            } => format!("Pallet {pallet_name} has no constant {constant_name} in metadata."),
+            ConstantError::NoPallet { pallet_name } => {
+                format!("Pallet {pallet_name} is not found in metadata.")
+            }
+            ConstantError::Parsing {
+                constant_name,
+                parser_error,
+            } => format!("Error parsing constant {constant_name} value. {parser_error}"),
+        }
+    }
+}
+
+/// Errors in encoding parsed data.
+#[derive(Debug, Eq, PartialEq)]
+pub enum EncodeError<E: ExternalMemory> {
+    LengthMismatch {
+        id: u32,
+        expected: usize,
+        found: usize,
+    },
+    NoVariant {
+        id: u32,
+        variant_name: String,
+    },
+    Parsing(ParserError<E>),
+    TypeMismatch {
+        id: u32,
+    },
+}
+
+impl<E: ExternalMemory> EncodeError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            EncodeError::LengthMismatch { id, expected, found } => format!("Type with id {id} expects {expected} element(s) or field(s), parsed data has {found}."),
+            EncodeError::NoVariant { id, variant_name } => {
+                format!("Type with id {id} has no variant {variant_name}.")
+            }
+            EncodeError::Parsing(parser_error) => {
+                format!("Error resolving type for encoding. {parser_error}")
+            }
+            EncodeError::TypeMismatch { id } => {
+                format!("Parsed data does not match type with id {id}.")
+            }
+        }
+    }
+}
+
+/// Errors in `System::Events` storage value parsing.
+#[derive(Debug, Eq, PartialEq)]
+pub enum EventsError<E: ExternalMemory> {
+    NoEventsStorage,
+    NotPlainStorage,
+    Parsing(ParserError<E>),
+    UnexpectedEventRecord,
+    UnexpectedEventsFormat,
+}
+
+impl<E: ExternalMemory> EventsError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            EventsError::NoEventsStorage => {
+                String::from("No `Events` storage entry in metadata `System` pallet.")
+            }
+            EventsError::NotPlainStorage => {
+                String::from("`System` pallet `Events` storage entry is not a plain one.")
+            }
+            EventsError::Parsing(parser_error) => {
+                format!("Error parsing events. {parser_error}")
+            }
+            EventsError::UnexpectedEventRecord => String::from(
+                "Decoded event record does not contain expected phase, event and topics.",
+            ),
+            EventsError::UnexpectedEventsFormat => {
+                String::from("Decoded events are not a sequence of event records.")
+            }
+        }
+    }
+}
+
+/// Errors in field extraction by path.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ExtractError<E: ExternalMemory> {
+    IndexOutOfRange { segment: String, len: u32 },
+    NoSegment { segment: String, ty_id: u32 },
+    Parsing(ParserError<E>),
+    VariantMismatch { segment: String, found: String },
+}
+
+impl<E: ExternalMemory> ExtractError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            ExtractError::IndexOutOfRange { segment, len } => {
+                format!("Path segment {segment} is out of range for a set of {len} element(s).")
+            }
+            ExtractError::NoSegment { segment, ty_id } => {
+                format!("Path segment {segment} does not exist in type with id {ty_id}.")
+            }
+            ExtractError::Parsing(parser_error) => {
+                format!("Error parsing data on the path. {parser_error}")
+            }
+            ExtractError::VariantMismatch { segment, found } => {
+                format!("Path segment {segment} does not match variant {found} found in data.")
+            }
+        }
+    }
+}
+
+/// Errors in block header parsing.
+#[derive(Debug, Eq, PartialEq)]
+pub enum HeaderError<E: ExternalMemory> {
+    Parsing(ParserError<E>),
+    UnexpectedDigestFormat,
+    UnexpectedDigestItem(String),
+    UnexpectedHeaderFormat,
+}
+
+impl<E: ExternalMemory> HeaderError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            HeaderError::Parsing(parser_error) => {
+                format!("Error parsing block header. {parser_error}")
+            }
+            HeaderError::UnexpectedDigestFormat => String::from(
+                "Decoded header digest is not a set of digest items with expected content.",
+            ),
+            HeaderError::UnexpectedDigestItem(variant_name) => {
+                format!("Decoded header digest contains unexpected item {variant_name}.")
+            }
+            HeaderError::UnexpectedHeaderFormat => String::from(
+                "Decoded header does not contain expected parent hash, number, state root, extrinsics root and digest.",
+            ),
+        }
+    }
+}
+
+/// Errors in building encoded values from JSON.
+///
+/// `pointer` is a JSON pointer to the offending value.
+#[derive(Debug, Eq, PartialEq)]
+pub enum JsonError<E: ExternalMemory> {
+    InvalidJson(String),
+    LengthMismatch {
+        pointer: String,
+        expected: usize,
+        found: usize,
+    },
+    MissingField {
+        pointer: String,
+        field_name: String,
+    },
+    Parsing(ParserError<E>),
+    UnexpectedValue {
+        pointer: String,
+        expected: &'static str,
+    },
+    UnknownField {
+        pointer: String,
+        field_name: String,
+    },
+    UnknownVariant {
+        pointer: String,
+        variant_name: String,
+    },
+}
+
+impl<E: ExternalMemory> JsonError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            JsonError::InvalidJson(error_text) => format!("Invalid JSON. {error_text}"),
+            JsonError::LengthMismatch {
+                pointer,
+                expected,
+                found,
+            } => format!("Value at \"{pointer}\" has {found} element(s), expected {expected}."),
+            JsonError::MissingField {
+                pointer,
+                field_name,
+            } => {
+                format!("Value at \"{pointer}\" has no field {field_name}.")
+            }
+            JsonError::Parsing(parser_error) => {
+                format!("Error resolving type for JSON value. {parser_error}")
+            }
+            JsonError::UnexpectedValue { pointer, expected } => {
+                format!("Value at \"{pointer}\" is not a valid {expected}.")
+            }
+            JsonError::UnknownField {
+                pointer,
+                field_name,
+            } => {
+                format!("Value at \"{pointer}\" has unexpected field {field_name}.")
+            }
+            JsonError::UnknownVariant {
+                pointer,
+                variant_name,
+            } => format!("Value at \"{pointer}\" has unknown variant {variant_name}."),
+        }
+    }
+}
+
 /// Errors in data parsing.
+///
+/// Errors occurring while decoding data as a type are wrapped into
+/// [`ParserError::WithTrail`], with [`TypeTrail`] from the decoded type to the
+/// failure point. To match on the error itself, use
+/// [`ParserError::without_trail`].
 #[derive(Debug, Eq, PartialEq)]
 pub enum ParserError<E: ExternalMemory> {
     Buffer(BufferError<E>),
@@ -129,9 +384,41 @@ pub enum ParserError<E: ExternalMemory> {
     V14ShortTypesIncomplete { old_id: u32 },
     V14TypeNotResolved { id: u32 },
     V14TypeNotResolvedShortened { id: u32 },
+    WithTrail(TypeTrail, Box<ParserError<E>>),
 }
 
 impl<E: ExternalMemory> ParserError<E> {
+    /// Add [`TypeCrumb`] at the start of the error [`TypeTrail`].
+    ///
+    /// Crumbs are added as the error propagates from the failure point up to
+    /// the decoding start, so the resulting trail goes from outer types to
+    /// the inner ones.
+    pub(crate) fn add_crumb(self, crumb: TypeCrumb) -> Self {
+        match self {
+            ParserError::WithTrail(mut trail, error) => {
+                trail.0.insert(0, crumb);
+                ParserError::WithTrail(trail, error)
+            }
+            error => ParserError::WithTrail(TypeTrail(vec![crumb]), Box::new(error)),
+        }
+    }
+
+    /// Error itself, with no [`TypeTrail`].
+    pub fn without_trail(&self) -> &ParserError<E> {
+        match self {
+            ParserError::WithTrail(_, error) => error,
+            error => error,
+        }
+    }
+
+    /// [`TypeTrail`] leading to the error, if any.
+    pub fn trail(&self) -> Option<&TypeTrail> {
+        match self {
+            ParserError::WithTrail(trail, _) => Some(trail),
+            _ => None,
+        }
+    }
+
     fn error_text(&self) -> String {
         match &self {
             ParserError::Buffer(buffer_error) => format!("{buffer_error}"),
@@ -148,6 +435,123 @@ impl<E: ExternalMemory> ParserError<E> {
             ParserError::V14ShortTypesIncomplete { old_id } => format!("Unable to resolve type with old id {old_id} in shortened metadata type registry."),
             ParserError::V14TypeNotResolved { id } => format!("Unable to resolve type id {id} in metadata type registry."),
             ParserError::V14TypeNotResolvedShortened { id } => format!("Unable to resolve type with updated id {id} in shortened metadata type registry."),
+            ParserError::WithTrail(trail, error) => format!("{error} Type trail: {trail}."),
+        }
+    }
+}
+
+/// Step in the type tree, on the way from the decoding start to the
+/// decoding failure.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TypeCrumb {
+    /// Sequence or array element, by index.
+    Element(usize),
+
+    /// Named struct or variant field.
+    Field(String),
+
+    /// Unnamed struct or variant field, by index.
+    FieldNumber(usize),
+
+    /// Call or event, within pallet.
+    PalletSpecific {
+        pallet_name: String,
+        variant_name: String,
+    },
+
+    /// Tuple element, by index.
+    TupleElement(usize),
+
+    /// Type in metadata types registry, with type path segments.
+    Ty { id: u32, path: Vec<String> },
+
+    /// Enum variant.
+    Variant(String),
+}
+
+/// Set of [`TypeCrumb`]s, from outer types to the inner ones.
+///
+/// Displayed as, for example, `Call > Utility::batch > calls[3] > Call >
+/// Balances::transfer > value`. Only types with non-empty path are displayed,
+/// by path ident.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeTrail(pub Vec<TypeCrumb>);
+
+impl Display for TypeTrail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut is_first = true;
+        for crumb in self.0.iter() {
+            let segment = match crumb {
+                TypeCrumb::Element(index) => {
+                    write!(f, "[{index}]")?;
+                    continue;
+                }
+                TypeCrumb::FieldNumber(index) | TypeCrumb::TupleElement(index) => {
+                    write!(f, ".{index}")?;
+                    continue;
+                }
+                TypeCrumb::Field(field_name) => field_name.to_owned(),
+                TypeCrumb::PalletSpecific {
+                    pallet_name,
+                    variant_name,
+                } => format!("{pallet_name}::{variant_name}"),
+                TypeCrumb::Ty { id: _, path } => match path.last() {
+                    Some(ident) => ident.to_owned(),
+                    None => continue,
+                },
+                TypeCrumb::Variant(variant_name) => variant_name.to_owned(),
+            };
+            if is_first {
+                is_first = false;
+            } else {
+                write!(f, " > ")?;
+            }
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors in runtime api call parsing.
+#[derive(Debug, Eq, PartialEq)]
+pub enum RuntimeApiError<E: ExternalMemory> {
+    NoApi {
+        api_name: String,
+    },
+    NoMethod {
+        api_name: String,
+        method_name: String,
+    },
+    ParsingInput {
+        input_name: String,
+        parser_error: ParserError<E>,
+    },
+    ParsingOutput(ParserError<E>),
+    SomeDataNotUsedInputs {
+        from: usize,
+    },
+}
+
+impl<E: ExternalMemory> RuntimeApiError<E> {
+    fn error_text(&self) -> String {
+        match &self {
+            RuntimeApiError::NoApi { api_name } => {
+                format!("Runtime api {api_name} is not found in metadata.")
+            }
+            RuntimeApiError::NoMethod {
+                api_name,
+                method_name,
+            } => format!("Runtime api {api_name} has no method {method_name} in metadata."),
+            RuntimeApiError::ParsingInput {
+                input_name,
+                parser_error,
+            } => format!("Error parsing runtime api method input {input_name}. {parser_error}"),
+            RuntimeApiError::ParsingOutput(parser_error) => {
+                format!("Error parsing runtime api method output. {parser_error}")
+            }
+            RuntimeApiError::SomeDataNotUsedInputs { from } => format!(
+                "Some runtime api method input data (input positions [{from}..]) remained unused after decoding."
+            ),
         }
     }
 }
@@ -168,25 +572,33 @@ impl<E: ExternalMemory> ParserError<E> {
 ///
 /// If `Era` is encountered and immortal, block hash (if encountered) must be
 /// checked to match the genesis hash.
+///
+/// Extensions with registered
+/// [`ExtensionHandler`](crate::decoding_sci_ext::ExtensionHandler) could also
+/// fail custom checks.
 #[derive(Debug, Eq, PartialEq)]
 pub enum ExtensionsError {
     BlockHashTwice,
+    CustomCheck { identifier: String, reason: String },
     EraTwice,
     GenesisHashTwice,
     NoGenesisHash,
     NoSpecVersion,
     SpecVersionTwice,
+    TxVersionTwice,
 }
 
 impl ExtensionsError {
     fn error_text(&self) -> String {
         match &self {
             ExtensionsError::BlockHashTwice => String::from("Signable transaction extensions contain more than one block hash entry."),
+            ExtensionsError::CustomCheck { identifier, reason } => format!("Signable transaction extension {identifier} failed the check. {reason}"),
             ExtensionsError::EraTwice => String::from("Signable transaction extensions contain more than one `Era` entry."),
             ExtensionsError::GenesisHashTwice => String::from("Signable transaction extensions contain more than one genesis hash entry. Unable to verify that correct chain is used for parsing."),
             ExtensionsError::NoGenesisHash => String::from("Signable transaction extensions do not include chain genesis hash. Unable to verify that correct chain is used for parsing."),
             ExtensionsError::NoSpecVersion => String::from("Signable transaction extensions do not include metadata spec version. Unable to verify that correct metadata version is used for parsing."),
             ExtensionsError::SpecVersionTwice => String::from("Signable transaction extensions contain more than one metadata spec version. Unable to verify that correct metadata version is used for parsing."),
+            ExtensionsError::TxVersionTwice => String::from("Signable transaction extensions contain more than one tx version. Unable to verify that correct metadata version is used for parsing."),
         }
     }
 }
@@ -197,10 +609,13 @@ pub enum MetaVersionErrorPallets {
     NoSpecNameIdentifier,
     NoSpecVersionIdentifier,
     NoSystemPallet,
+    NoUncheckedExtrinsicType,
     NoVersionInConstants,
     RuntimeVersionNotDecodeable,
     SpecNameIdentifierTwice,
     SpecVersionIdentifierTwice,
+    TxVersionIdentifierTwice,
+    UnexpectedErrorEnumFormat,
     UnexpectedRuntimeVersionFormat,
 }
 
@@ -216,6 +631,9 @@ impl MetaVersionErrorPallets {
             MetaVersionErrorPallets::NoSystemPallet => {
                 String::from("No `System` pallet in metadata.")
             }
+            MetaVersionErrorPallets::NoUncheckedExtrinsicType => String::from(
+                "No unchecked extrinsic type matching the extrinsic metadata found in metadata types registry.",
+            ),
             MetaVersionErrorPallets::NoVersionInConstants => {
                 String::from("No `Version` constant in metadata `System` pallet.")
             }
@@ -228,6 +646,12 @@ impl MetaVersionErrorPallets {
             MetaVersionErrorPallets::SpecVersionIdentifierTwice => String::from(
                 "Spec version associated identifier found twice when decoding `Version` constant.",
             ),
+            MetaVersionErrorPallets::TxVersionIdentifierTwice => String::from(
+                "Transaction version associated identifier found twice when decoding `Version` constant.",
+            ),
+            MetaVersionErrorPallets::UnexpectedErrorEnumFormat => String::from(
+                "Outer error enum in metadata is not an enum with single-field pallet variants.",
+            ),
             MetaVersionErrorPallets::UnexpectedRuntimeVersionFormat => {
                 String::from("Decoded `Version` constant is not a composite.")
             }
@@ -235,6 +659,76 @@ impl MetaVersionErrorPallets {
     }
 }
 
+/// Error in decoding pallet error from `DispatchError::Module`.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ModuleErrorError<E: ExternalMemory, M: AsMetadata<E>> {
+    MetaStructure(M::MetaStructureError),
+    NoPalletErrorTy { pallet_index: u8 },
+    NotAnEnum { id: u32 },
+    Parsing(ParserError<E>),
+}
+
+impl<E, M> ModuleErrorError<E, M>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    fn error_text(&self) -> String {
+        match &self {
+            ModuleErrorError::MetaStructure(meta_structure_error) => {
+                format!("Unexpected structure of the metadata. {meta_structure_error}")
+            }
+            ModuleErrorError::NoPalletErrorTy { pallet_index } => {
+                format!("No error type found in metadata for pallet with index {pallet_index}.")
+            }
+            ModuleErrorError::NotAnEnum { id } => {
+                format!("Pallet error type {id} in metadata type registry is not an enum.")
+            }
+            ModuleErrorError::Parsing(parser_error) => {
+                format!("Error parsing pallet error data. {parser_error}")
+            }
+        }
+    }
+}
+
+/// Error in decoding prefixed metadata.
+#[derive(Debug, Eq, PartialEq)]
+pub enum MetadataPrefixedError {
+    MetadataNotDecodeable { version: u8 },
+    NotHex,
+    SomeDataNotUsed { from: usize },
+    TooShort,
+    UnsupportedVersion(u8),
+    WrongMagic { found: u32 },
+}
+
+impl MetadataPrefixedError {
+    fn error_text(&self) -> String {
+        match &self {
+            MetadataPrefixedError::MetadataNotDecodeable { version } => {
+                format!("Unable to decode metadata as `RuntimeMetadataV{version}`.")
+            }
+            MetadataPrefixedError::NotHex => {
+                String::from("Prefixed metadata is not a valid hexadecimal string.")
+            }
+            MetadataPrefixedError::SomeDataNotUsed { from } => format!(
+                "Some metadata (input positions [{from}..]) remained unused after decoding."
+            ),
+            MetadataPrefixedError::TooShort => String::from(
+                "Prefixed metadata is too short to contain `META` prefix and metadata version.",
+            ),
+            MetadataPrefixedError::UnsupportedVersion(version) => format!(
+                "Metadata version {version} is not supported. Supported versions are 14 and 15."
+            ),
+            MetadataPrefixedError::WrongMagic { found } => format!(
+                "Prefixed metadata starts with {}, expected `META` prefix {}.",
+                hex::encode(found.to_le_bytes()),
+                hex::encode(META_RESERVED.to_le_bytes())
+            ),
+        }
+    }
+}
+
 /// Error in parsing an unchecked extrinsic.
 #[derive(Debug, Eq, PartialEq)]
 pub enum UncheckedExtrinsicError<E: ExternalMemory, M: AsMetadata<E>> {
@@ -247,6 +741,7 @@ pub enum UncheckedExtrinsicError<E: ExternalMemory, M: AsMetadata<E>> {
     Parsing(ParserError<E>),
     VersionMismatch { version_byte: u8, version: u8 },
     UnexpectedCallTy { call_ty_id: u32 },
+    UnsupportedVersion { version_byte: u8 },
 }
 
 impl<E, M> UncheckedExtrinsicError<E, M>
@@ -263,8 +758,43 @@ where
             UncheckedExtrinsicError::NoExtraParam => String::from("Unchecked extrinsic type in provided metadata has no specified extra parameter."),
             UncheckedExtrinsicError::NoSignatureParam => String::from("Unchecked extrinsic type in provided metadata has no specified signature parameter."),
             UncheckedExtrinsicError::Parsing(parser_error) => format!("Error parsing unchecked extrinsic data. {parser_error}"),
-            UncheckedExtrinsicError::VersionMismatch { version_byte, version } => format!("Version byte in unchecked extrinsic {version_byte} does not match with version {version} from provided metadata. Last 7 bits were expected to be identical."),
+            UncheckedExtrinsicError::VersionMismatch { version_byte, version } => format!("Version byte in unchecked extrinsic {version_byte} does not match with version {version} from provided metadata. Last 6 bits were expected to be identical."),
             UncheckedExtrinsicError::UnexpectedCallTy { call_ty_id } => format!("Parameter type for call {call_ty_id} in metadata type registry is not a call type, and does not match known call type descriptors."),
+            UncheckedExtrinsicError::UnsupportedVersion { version_byte } => format!("Version byte in unchecked extrinsic {version_byte} does not correspond to any supported extrinsic format. Supported are signed and unsigned extrinsics of version 4, and bare and general extrinsics of version 5."),
+        }
+    }
+}
+
+/// Error in verifying unchecked extrinsic signature.
+#[derive(Debug, Eq, PartialEq)]
+pub enum SignatureError<E: ExternalMemory, M: AsMetadata<E>> {
+    AdditionalSignedUnknown { identifier: String },
+    BlockHashNeeded,
+    Invalid,
+    MetaStructure(M::MetaStructureError),
+    NoEra,
+    Parsing(ParserError<E>),
+    UnsupportedAddress,
+    UnsupportedSignature,
+    Unsigned,
+}
+
+impl<E, M> SignatureError<E, M>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    fn error_text(&self) -> String {
+        match &self {
+            SignatureError::AdditionalSignedUnknown { identifier } => format!("Signed extension {identifier} has additional signed data that could not be built from provided genesis hash and versions, and is not provided by the caller."),
+            SignatureError::BlockHashNeeded => String::from("Unchecked extrinsic is mortal. Block hash is needed to build the signed payload."),
+            SignatureError::Invalid => String::from("Signature is not valid for the signed payload and the address."),
+            SignatureError::MetaStructure(meta_structure_error) => format!("Unexpected structure of the metadata. {meta_structure_error}"),
+            SignatureError::NoEra => String::from("Signed extensions include mortality check, but no era was found in unchecked extrinsic extra data."),
+            SignatureError::Parsing(parser_error) => format!("Error parsing unchecked extrinsic data. {parser_error}"),
+            SignatureError::UnsupportedAddress => String::from("Signature could be verified only for account id address."),
+            SignatureError::UnsupportedSignature => String::from("Signature could be verified only for sr25519, ed25519 and ecdsa signatures."),
+            SignatureError::Unsigned => String::from("Unchecked extrinsic is unsigned, there is no signature to verify."),
         }
     }
 }
@@ -290,7 +820,11 @@ macro_rules! impl_display_and_error {
     }
 }
 
-impl_display_and_error!(ExtensionsError, MetaVersionErrorPallets);
+impl_display_and_error!(
+    ExtensionsError,
+    MetaVersionErrorPallets,
+    MetadataPrefixedError
+);
 
 /// Implement [`Display`] for errors in both `std` and `no_std` cases.
 /// Implement `Error` for `std` case.
@@ -313,7 +847,18 @@ macro_rules! impl_display_and_error_gen {
     }
 }
 
-impl_display_and_error_gen!(ParserError<E>, StorageError<E>);
+impl_display_and_error_gen!(
+    BlockError<E>,
+    ConstantError<E>,
+    EncodeError<E>,
+    EventsError<E>,
+    ExtractError<E>,
+    HeaderError<E>,
+    JsonError<E>,
+    ParserError<E>,
+    RuntimeApiError<E>,
+    StorageError<E>
+);
 
 impl<E: ExternalMemory> From<BufferError<E>> for ParserError<E> {
     fn from(buffer_error: BufferError<E>) -> Self {
@@ -321,6 +866,24 @@ impl<E: ExternalMemory> From<BufferError<E>> for ParserError<E> {
     }
 }
 
+impl<E: ExternalMemory> From<ParserError<E>> for EncodeError<E> {
+    fn from(parser_error: ParserError<E>) -> Self {
+        EncodeError::Parsing(parser_error)
+    }
+}
+
+impl<E: ExternalMemory> From<ParserError<E>> for ExtractError<E> {
+    fn from(parser_error: ParserError<E>) -> Self {
+        ExtractError::Parsing(parser_error)
+    }
+}
+
+impl<E: ExternalMemory> From<ParserError<E>> for JsonError<E> {
+    fn from(parser_error: ParserError<E>) -> Self {
+        JsonError::Parsing(parser_error)
+    }
+}
+
 /// Implement [`Display`] for errors in both `std` and `no_std` cases.
 /// Implement `Error` for `std` case.
 /// Implement `From<ParserError<E>>` for simplified error conversion.
@@ -361,4 +924,9 @@ macro_rules! impl_display_error_from_2gen {
     }
 }
 
-impl_display_error_from_2gen!(SignableError<E, M>, UncheckedExtrinsicError<E, M>);
+impl_display_error_from_2gen!(
+    ModuleErrorError<E, M>,
+    SignableError<E, M>,
+    SignatureError<E, M>,
+    UncheckedExtrinsicError<E, M>
+);
@@ -0,0 +1,192 @@
+//! Interpret events.
+//!
+//! Events emitted in a block are stored in `System` pallet `Events` storage
+//! entry, as a sequence of `frame_system::EventRecord`. Each event record
+//! contains the phase of block execution in which the event was emitted, the
+//! event itself, and the set of topics (hashes) associated with the event.
+//!
+//! Events could be queried from chain via rpc `state_getStorage` with `Events`
+//! storage key and block hash.
+//!
+//! Pallet errors from `DispatchError::Module` found in events get decoded, see
+//! [`resolve_module_errors`].
+use external_memory_tools::{AddressableBuffer, ExternalMemory};
+use frame_metadata::v14::StorageEntryType;
+use primitive_types::H256;
+
+use crate::std::{collections::BTreeMap, vec::Vec};
+
+use crate::cards::{Event, ExtendedData, FieldData, ParsedData, SequenceRawData, VariantData};
+use crate::decode_all_as_type;
+use crate::error::EventsError;
+use crate::pallet_error::resolve_module_errors;
+use crate::traits::AsMetadata;
+
+/// `System` pallet name.
+pub const SYSTEM_PALLET: &str = "System";
+
+/// `System` pallet storage entry name for events.
+pub const EVENTS_ENTRY: &str = "Events";
+
+/// Phase of block execution in which the event was emitted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    /// Applying an extrinsic with given index.
+    ApplyExtrinsic(u32),
+
+    /// Finalizing the block.
+    Finalization,
+
+    /// Initializing the block.
+    Initialization,
+}
+
+/// Parsed event record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventRecord {
+    pub phase: Phase,
+    pub event: Event,
+    pub topics: Vec<H256>,
+}
+
+impl EventRecord {
+    /// Index of extrinsic that emitted the event, if the event was emitted
+    /// while applying an extrinsic.
+    pub fn extrinsic_index(&self) -> Option<u32> {
+        match self.phase {
+            Phase::ApplyExtrinsic(index) => Some(index),
+            Phase::Finalization | Phase::Initialization => None,
+        }
+    }
+}
+
+/// Decode `System` pallet `Events` storage value.
+///
+/// Event records are returned in order of appearance. All data is expected to
+/// be used.
+pub fn decode_events<B, E, M>(
+    data: &B,
+    ext_memory: &mut E,
+    meta: &M,
+) -> Result<Vec<EventRecord>, EventsError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let events_entry = meta
+        .storage_entry(SYSTEM_PALLET, EVENTS_ENTRY)
+        .ok_or(EventsError::NoEventsStorage)?;
+    let events_ty = match events_entry.ty {
+        StorageEntryType::Plain(ty) => ty,
+        StorageEntryType::Map { .. } => return Err(EventsError::NotPlainStorage),
+    };
+    let mut events_data =
+        decode_all_as_type::<B, E, M>(&events_ty, data, ext_memory, &meta.types())
+            .map_err(EventsError::Parsing)?;
+    resolve_module_errors::<E, M>(&mut events_data, ext_memory, meta);
+    match events_data.data {
+        ParsedData::SequenceRaw(SequenceRawData {
+            element_info: _,
+            data: records,
+        }) => records.into_iter().map(event_record).collect(),
+        _ => Err(EventsError::UnexpectedEventsFormat),
+    }
+}
+
+/// Event records of a single extrinsic, in order of appearance.
+pub fn events_for_extrinsic(records: &[EventRecord], extrinsic_index: u32) -> Vec<&EventRecord> {
+    records
+        .iter()
+        .filter(|record| record.extrinsic_index() == Some(extrinsic_index))
+        .collect()
+}
+
+/// Group event records by extrinsic index.
+///
+/// Event records not emitted during extrinsic application are skipped.
+pub fn group_events_by_extrinsic(records: &[EventRecord]) -> BTreeMap<u32, Vec<&EventRecord>> {
+    let mut out: BTreeMap<u32, Vec<&EventRecord>> = BTreeMap::new();
+    for record in records.iter() {
+        if let Some(extrinsic_index) = record.extrinsic_index() {
+            out.entry(extrinsic_index).or_default().push(record)
+        }
+    }
+    out
+}
+
+/// Transform parsed event record into [`EventRecord`].
+fn event_record<E: ExternalMemory>(parsed_data: ParsedData) -> Result<EventRecord, EventsError<E>> {
+    let mut phase = None;
+    let mut event = None;
+    let mut topics = None;
+    if let ParsedData::Composite(field_data_set) = parsed_data {
+        for field_data in field_data_set.into_iter() {
+            match field_data.field_name.as_deref() {
+                Some("phase") => phase = phase_from_data(field_data.data.data),
+                Some("event") => {
+                    if let ParsedData::Event(found_event) = field_data.data.data {
+                        event = Some(found_event)
+                    }
+                }
+                Some("topics") => topics = topics_from_data(field_data.data.data),
+                _ => (),
+            }
+        }
+    }
+    match (phase, event, topics) {
+        (Some(phase), Some(event), Some(topics)) => Ok(EventRecord {
+            phase,
+            event,
+            topics,
+        }),
+        _ => Err(EventsError::UnexpectedEventRecord),
+    }
+}
+
+/// Get [`Phase`] from parsed data.
+fn phase_from_data(parsed_data: ParsedData) -> Option<Phase> {
+    if let ParsedData::Variant(VariantData {
+        variant_name,
+        variant_docs: _,
+        fields,
+    }) = parsed_data
+    {
+        match (variant_name.as_str(), fields.as_slice()) {
+            (
+                "ApplyExtrinsic",
+                [FieldData {
+                    data:
+                        ExtendedData {
+                            data: ParsedData::PrimitiveU32 { value, .. },
+                            ..
+                        },
+                    ..
+                }],
+            ) => Some(Phase::ApplyExtrinsic(*value)),
+            ("Finalization", []) => Some(Phase::Finalization),
+            ("Initialization", []) => Some(Phase::Initialization),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Get topics from parsed data.
+fn topics_from_data(parsed_data: ParsedData) -> Option<Vec<H256>> {
+    if let ParsedData::SequenceRaw(SequenceRawData {
+        element_info: _,
+        data,
+    }) = parsed_data
+    {
+        data.into_iter()
+            .map(|element| match element {
+                ParsedData::H256(topic) => Some(topic),
+                _ => None,
+            })
+            .collect()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,483 @@
+//! Extract single value from encoded data by field path.
+//!
+//! Field path is a set of segments separated by `.`, for example `data.free`
+//! for `System` pallet `Account` storage value, or `dest.Id` for a call
+//! argument.
+//!
+//! Path segments are matched with the resolved types:
+//!
+//! - for structs, segment is a field name, or field index for structs with
+//!   unnamed fields
+//! - for enums, segment is a variant name, it must match the variant found in
+//!   the data; enum variant with a single field is addressed directly, i.e.
+//!   path ending at a variant results in the variant field value
+//! - for vectors, arrays and tuples, segment is an element index
+//! - for calls and events, first segment is a pallet name, second segment is a
+//!   call or event name
+//! - compacts are transparent
+//!
+//! Data that is not on the path is skipped, see
+//! [`skipping`](crate::skipping). Only the value at the path end gets decoded.
+use external_memory_tools::{AddressableBuffer, ExternalMemory};
+use scale_info::{form::PortableForm, interner::UntrackedSymbol, Field, TypeDef, Variant};
+
+#[cfg(not(feature = "std"))]
+use core::any::TypeId;
+#[cfg(feature = "std")]
+use std::any::TypeId;
+
+use crate::std::{borrow::ToOwned, string::ToString, vec::Vec};
+
+use crate::cards::{ExtendedData, ParsedData};
+use crate::compacts::get_compact;
+use crate::decoding_sci::{
+    decode_variant, decode_with_type, husk_type, pick_variant, ResolvedTy, Ty,
+};
+use crate::error::ExtractError;
+use crate::propagated::{Checker, Propagated};
+use crate::skipping::skip_with_type;
+use crate::special_indicators::{SpecialtyTypeChecked, ENUM_INDEX_ENCODED_LEN};
+use crate::traits::{AsMetadata, ResolveType};
+use crate::visitor::field_data_set;
+
+/// Separator of field path segments.
+pub const PATH_SEPARATOR: char = '.';
+
+/// Extract value at given field path from data encoded as a known type.
+///
+/// Empty path results in the whole data decoded. Data remaining after the
+/// extracted value is not checked.
+pub fn extract_field<B, E, M>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    path: &str,
+    data: &B,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<ExtendedData, ExtractError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let segments: Vec<&str> = if path.is_empty() {
+        Vec::new()
+    } else {
+        path.split(PATH_SEPARATOR).collect()
+    };
+    let mut position: usize = 0;
+    extract_with_type::<B, E, M>(
+        &Ty::Symbol(ty_symbol),
+        &segments,
+        data,
+        ext_memory,
+        &mut position,
+        registry,
+        Propagated::new(),
+    )
+}
+
+/// Extract value at given path segments from data encoded as [`Ty`].
+///
+/// Current parser position gets changed.
+fn extract_with_type<B, E, M>(
+    ty_input: &Ty,
+    segments: &[&str],
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    mut propagated: Propagated,
+) -> Result<ExtendedData, ExtractError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let (segment, rest) = match segments.split_first() {
+        Some(a) => a,
+        None => {
+            return Ok(decode_with_type::<B, E, M>(
+                ty_input, data, ext_memory, position, registry, propagated,
+            )?)
+        }
+    };
+    let (ty, id) = match ty_input {
+        Ty::Resolved(resolved_ty) => (resolved_ty.ty.to_owned(), resolved_ty.id),
+        Ty::Symbol(ty_symbol) => (registry.resolve_ty(ty_symbol.id, ext_memory)?, ty_symbol.id),
+    };
+    match SpecialtyTypeChecked::from_type::<B, E, M>(&ty, data, ext_memory, position, registry) {
+        SpecialtyTypeChecked::None => match &ty.type_def {
+            TypeDef::Composite(x) => extract_from_fields::<B, E, M>(
+                &x.fields,
+                segment,
+                rest,
+                data,
+                ext_memory,
+                position,
+                registry,
+                propagated.checker,
+                id,
+            ),
+            TypeDef::Variant(x) => {
+                propagated.reject_compact()?;
+                extract_from_variant::<B, E, M>(
+                    &x.variants,
+                    segment,
+                    rest,
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    id,
+                )
+            }
+            TypeDef::Sequence(x) => {
+                let number_of_elements = get_compact::<u32, B, E>(data, ext_memory, position)?;
+                propagated.checker.drop_cycle_check();
+                extract_from_elements_set::<B, E, M>(
+                    &x.type_param,
+                    number_of_elements,
+                    segment,
+                    rest,
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    propagated,
+                )
+            }
+            TypeDef::Array(x) => extract_from_elements_set::<B, E, M>(
+                &x.type_param,
+                x.len,
+                segment,
+                rest,
+                data,
+                ext_memory,
+                position,
+                registry,
+                propagated,
+            ),
+            TypeDef::Tuple(x) => {
+                let index = segment
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|index| *index < x.fields.len())
+                    .ok_or_else(|| ExtractError::NoSegment {
+                        segment: segment.to_string(),
+                        ty_id: id,
+                    })?;
+                if x.fields.len() > 1 {
+                    propagated.reject_compact()?;
+                    propagated.forget_hint();
+                }
+                for inner_ty_symbol in x.fields[..index].iter() {
+                    let mut element_checker = propagated.checker.clone();
+                    element_checker.check_id(inner_ty_symbol.id)?;
+                    skip_with_type::<B, E, M>(
+                        &Ty::Symbol(inner_ty_symbol),
+                        data,
+                        ext_memory,
+                        position,
+                        registry,
+                        element_checker,
+                    )?;
+                }
+                let inner_id = x.fields[index].id;
+                let inner_ty = registry.resolve_ty(inner_id, ext_memory)?;
+                let inner_propagated =
+                    Propagated::for_ty(&propagated.checker, &inner_ty, inner_id)?;
+                extract_with_type::<B, E, M>(
+                    &Ty::Resolved(ResolvedTy {
+                        ty: inner_ty,
+                        id: inner_id,
+                    }),
+                    rest,
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    inner_propagated,
+                )
+            }
+            TypeDef::Compact(x) => {
+                propagated.reject_compact()?;
+                propagated.checker.specialty_set.compact_at = Some(id);
+                propagated.checker.check_id(x.type_param.id)?;
+                extract_with_type::<B, E, M>(
+                    &Ty::Symbol(&x.type_param),
+                    segments,
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    propagated,
+                )
+            }
+            _ => Err(ExtractError::NoSegment {
+                segment: segment.to_string(),
+                ty_id: id,
+            }),
+        },
+        SpecialtyTypeChecked::PalletSpecific {
+            pallet_name,
+            pallet_info,
+            variants,
+            item_ty_id,
+            ..
+        } => {
+            propagated.reject_compact()?;
+            if *segment != pallet_name {
+                return Err(ExtractError::VariantMismatch {
+                    segment: segment.to_string(),
+                    found: pallet_name,
+                });
+            }
+            match rest.split_first() {
+                Some((item_segment, item_rest)) => extract_from_variant::<B, E, M>(
+                    &variants,
+                    item_segment,
+                    item_rest,
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    item_ty_id,
+                ),
+                None => {
+                    let variant_data =
+                        decode_variant::<B, E, M>(&variants, data, ext_memory, position, registry)?;
+                    Ok(ExtendedData {
+                        data: ParsedData::Variant(variant_data),
+                        info: vec![pallet_info],
+                    })
+                }
+            }
+        }
+        _ => Err(ExtractError::NoSegment {
+            segment: segment.to_string(),
+            ty_id: id,
+        }),
+    }
+}
+
+/// Find field addressed by path segment.
+///
+/// Segment is matched with field names, or with field index if the fields are
+/// unnamed.
+fn find_field(fields: &[Field<PortableForm>], segment: &str) -> Option<usize> {
+    match fields
+        .iter()
+        .position(|field| field.name.as_deref() == Some(segment))
+    {
+        Some(index) => Some(index),
+        None => {
+            if fields.iter().all(|field| field.name.is_none()) {
+                segment
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|index| *index < fields.len())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Extract value from a set of [`Field`]s, skipping the fields before the
+/// addressed one.
+///
+/// Current parser position gets changed.
+#[allow(clippy::too_many_arguments)]
+fn extract_from_fields<B, E, M>(
+    fields: &[Field<PortableForm>],
+    segment: &str,
+    rest: &[&str],
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    mut checker: Checker,
+    ty_id: u32,
+) -> Result<ExtendedData, ExtractError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let index = find_field(fields, segment).ok_or_else(|| ExtractError::NoSegment {
+        segment: segment.to_string(),
+        ty_id,
+    })?;
+    if fields.len() > 1 {
+        checker.reject_compact()?;
+        checker.forget_hint();
+    }
+    for field in fields[..index].iter() {
+        skip_with_type::<B, E, M>(
+            &Ty::Symbol(&field.ty),
+            data,
+            ext_memory,
+            position,
+            registry,
+            Checker::update_for_field(&checker, field)?,
+        )?;
+    }
+    let field = &fields[index];
+    extract_with_type::<B, E, M>(
+        &Ty::Symbol(&field.ty),
+        rest,
+        data,
+        ext_memory,
+        position,
+        registry,
+        Propagated::for_field(&checker, field)?,
+    )
+}
+
+/// Extract value from a variant.
+///
+/// Path segment must match the variant found in data. If the path ends at the
+/// variant with a single field, the field value is extracted. If the path ends
+/// at the variant with other number of fields, all fields are decoded.
+///
+/// Current parser position gets changed.
+#[allow(clippy::too_many_arguments)]
+fn extract_from_variant<B, E, M>(
+    variants: &[Variant<PortableForm>],
+    segment: &str,
+    rest: &[&str],
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    ty_id: u32,
+) -> Result<ExtendedData, ExtractError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    if !variants.iter().any(|variant| variant.name == segment) {
+        return Err(ExtractError::NoSegment {
+            segment: segment.to_string(),
+            ty_id,
+        });
+    }
+    let found_variant = pick_variant::<B, E>(variants, data, ext_memory, *position)?;
+    if found_variant.name != segment {
+        return Err(ExtractError::VariantMismatch {
+            segment: segment.to_string(),
+            found: found_variant.name.to_owned(),
+        });
+    }
+    *position += ENUM_INDEX_ENCODED_LEN;
+    match rest.split_first() {
+        Some((field_segment, field_rest)) => extract_from_fields::<B, E, M>(
+            &found_variant.fields,
+            field_segment,
+            field_rest,
+            data,
+            ext_memory,
+            position,
+            registry,
+            Checker::new(),
+            ty_id,
+        ),
+        None => {
+            if found_variant.fields.len() == 1 {
+                let field = &found_variant.fields[0];
+                extract_with_type::<B, E, M>(
+                    &Ty::Symbol(&field.ty),
+                    rest,
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    Propagated::for_field(&Checker::new(), field)?,
+                )
+            } else {
+                let mut checker = Checker::new();
+                checker.forget_hint();
+                let mut values: Vec<ExtendedData> = Vec::new();
+                for field in found_variant.fields.iter() {
+                    values.push(decode_with_type::<B, E, M>(
+                        &Ty::Symbol(&field.ty),
+                        data,
+                        ext_memory,
+                        position,
+                        registry,
+                        Propagated::for_field(&checker, field)?,
+                    )?)
+                }
+                Ok(ExtendedData {
+                    data: ParsedData::Composite(field_data_set(&found_variant.fields, values)),
+                    info: Vec::new(),
+                })
+            }
+        }
+    }
+}
+
+/// Extract value from a known number of identical elements, skipping the
+/// elements before the addressed one.
+///
+/// Current parser position gets changed.
+#[allow(clippy::too_many_arguments)]
+fn extract_from_elements_set<B, E, M>(
+    element: &UntrackedSymbol<TypeId>,
+    number_of_elements: u32,
+    segment: &str,
+    rest: &[&str],
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    propagated: Propagated,
+) -> Result<ExtendedData, ExtractError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    propagated.reject_compact()?;
+    let index = segment
+        .parse::<u32>()
+        .map_err(|_| ExtractError::NoSegment {
+            segment: segment.to_string(),
+            ty_id: element.id,
+        })?;
+    if index >= number_of_elements {
+        return Err(ExtractError::IndexOutOfRange {
+            segment: segment.to_string(),
+            len: number_of_elements,
+        });
+    }
+
+    let husked = husk_type::<E, M>(element, registry, ext_memory, propagated.checker, false)?;
+
+    for _i in 0..index {
+        skip_with_type::<B, E, M>(
+            &Ty::Resolved(ResolvedTy {
+                ty: husked.ty.to_owned(),
+                id: husked.id,
+            }),
+            data,
+            ext_memory,
+            position,
+            registry,
+            husked.checker.clone(),
+        )?;
+    }
+    extract_with_type::<B, E, M>(
+        &Ty::Resolved(ResolvedTy {
+            ty: husked.ty,
+            id: husked.id,
+        }),
+        rest,
+        data,
+        ext_memory,
+        position,
+        registry,
+        Propagated::with_checker(husked.checker),
+    )
+}
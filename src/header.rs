@@ -0,0 +1,683 @@
+//! Decode block headers.
+//!
+//! Block header contains parent block hash, block number, state root,
+//! extrinsics root, and the digest, see
+//! [`Header`](https://docs.rs/sp-runtime/latest/sp_runtime/generic/struct.Header.html).
+//!
+//! Header type is resolved in metadata types registry, if available, see
+//! [`AsMetadata::header_ty`]. Otherwise the well-known header layout is used:
+//! block number is a compact, and the digest is a set of
+//! [`DigestItem`](https://docs.rs/sp-runtime/latest/sp_runtime/generic/enum.DigestItem.html)s
+//! with known variant indices.
+//!
+//! Digest items `PreRuntime`, `Consensus` and `Seal` contain consensus engine
+//! identifier and opaque data. Pre-runtime data for `BABE` and `aura` engines
+//! contains the slot and is decoded further.
+use external_memory_tools::{AddressableBuffer, ExternalMemory};
+use parity_scale_codec::{Decode, DecodeAll};
+use primitive_types::{H256, H512};
+
+use crate::std::{
+    borrow::ToOwned,
+    fmt::{Display, Formatter, Result as FmtResult},
+    string::String,
+    vec::Vec,
+};
+
+use crate::cards::{
+    ExtendedCard, ParsedData, ParserCard, Sequence, SequenceData, SequenceRawData, VariantData,
+};
+use crate::compacts::get_compact;
+use crate::decode_as_type_at_position;
+use crate::error::{HeaderError, ParserError};
+use crate::traits::AsMetadata;
+
+/// Consensus engine identifier for `BABE`.
+pub const BABE_ENGINE_ID: [u8; ENGINE_ID_LEN] = *b"BABE";
+
+/// Consensus engine identifier for `aura`.
+pub const AURA_ENGINE_ID: [u8; ENGINE_ID_LEN] = *b"aura";
+
+/// Consensus engine identifier for `GRANDPA`.
+pub const GRANDPA_ENGINE_ID: [u8; ENGINE_ID_LEN] = *b"FRNK";
+
+/// Length of consensus engine identifier.
+pub const ENGINE_ID_LEN: usize = 4;
+
+/// Length of hashes in well-known header layout.
+const HASH_LEN: usize = 32;
+
+/// `DigestItem` variant index for `Other`, in well-known header layout.
+const DIGEST_OTHER: u8 = 0;
+
+/// `DigestItem` variant index for `Consensus`, in well-known header layout.
+const DIGEST_CONSENSUS: u8 = 4;
+
+/// `DigestItem` variant index for `Seal`, in well-known header layout.
+const DIGEST_SEAL: u8 = 5;
+
+/// `DigestItem` variant index for `PreRuntime`, in well-known header layout.
+const DIGEST_PRE_RUNTIME: u8 = 6;
+
+/// `DigestItem` variant index for `RuntimeEnvironmentUpdated`, in well-known
+/// header layout.
+const DIGEST_RUNTIME_ENVIRONMENT_UPDATED: u8 = 8;
+
+/// Decoded block header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Header {
+    pub parent_hash: H256,
+    pub number: u64,
+    pub state_root: H256,
+    pub extrinsics_root: H256,
+    pub digest: Vec<DigestItem>,
+}
+
+/// Decoded digest item.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DigestItem {
+    PreRuntime {
+        engine_id: EngineId,
+        data: PreRuntimeData,
+    },
+    Consensus {
+        engine_id: EngineId,
+        data: Vec<u8>,
+    },
+    Seal {
+        engine_id: EngineId,
+        data: Vec<u8>,
+    },
+    Other(Vec<u8>),
+    RuntimeEnvironmentUpdated,
+}
+
+/// Consensus engine identifier.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EngineId(pub [u8; ENGINE_ID_LEN]);
+
+impl Display for EngineId {
+    /// Engine identifiers are displayed as text if possible, and as hex
+    /// otherwise.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.0.iter().all(|byte| byte.is_ascii_graphic()) {
+            write!(f, "{}", String::from_utf8_lossy(&self.0))
+        } else {
+            write!(f, "{}", hex::encode(self.0))
+        }
+    }
+}
+
+/// Pre-runtime digest data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PreRuntimeData {
+    /// `aura` pre-runtime data, the slot.
+    Aura { slot: u64 },
+
+    /// `BABE` pre-runtime data.
+    Babe(BabePreDigest),
+
+    /// Data for other engines, or data that could not be decoded.
+    Raw(Vec<u8>),
+}
+
+/// `BABE` pre-runtime digest.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BabePreDigest {
+    Primary {
+        authority_index: u32,
+        slot: u64,
+        vrf_output: H256,
+        vrf_proof: H512,
+    },
+    SecondaryPlain {
+        authority_index: u32,
+        slot: u64,
+    },
+    SecondaryVrf {
+        authority_index: u32,
+        slot: u64,
+        vrf_output: H256,
+        vrf_proof: H512,
+    },
+}
+
+impl PreRuntimeData {
+    /// Decode pre-runtime data for known consensus engines.
+    ///
+    /// If the engine is not known or the data could not be decoded, the data
+    /// is kept raw.
+    pub fn new(engine_id: &EngineId, data: Vec<u8>) -> Self {
+        let decoded = match engine_id.0 {
+            AURA_ENGINE_ID => u64::decode_all(&mut data.as_slice())
+                .ok()
+                .map(|slot| PreRuntimeData::Aura { slot }),
+            BABE_ENGINE_ID => babe_pre_digest(&data).map(PreRuntimeData::Babe),
+            _ => None,
+        };
+        decoded.unwrap_or(PreRuntimeData::Raw(data))
+    }
+}
+
+/// Decode `BABE` pre-runtime digest.
+fn babe_pre_digest(data: &[u8]) -> Option<BabePreDigest> {
+    let (variant_index, mut input) = data.split_first()?;
+    let authority_index = u32::decode(&mut input).ok()?;
+    let slot = u64::decode(&mut input).ok()?;
+    let pre_digest = match variant_index {
+        1 | 3 => {
+            let vrf_output = H256(<[u8; 32]>::decode(&mut input).ok()?);
+            let vrf_proof = H512(<[u8; 64]>::decode(&mut input).ok()?);
+            if *variant_index == 1 {
+                BabePreDigest::Primary {
+                    authority_index,
+                    slot,
+                    vrf_output,
+                    vrf_proof,
+                }
+            } else {
+                BabePreDigest::SecondaryVrf {
+                    authority_index,
+                    slot,
+                    vrf_output,
+                    vrf_proof,
+                }
+            }
+        }
+        2 => BabePreDigest::SecondaryPlain {
+            authority_index,
+            slot,
+        },
+        _ => return None,
+    };
+    if input.is_empty() {
+        Some(pre_digest)
+    } else {
+        None
+    }
+}
+
+/// Decode block header.
+///
+/// Header type is taken from the metadata if available, otherwise well-known
+/// header layout is used. All data is expected to be used.
+pub fn decode_header<B, E, M>(
+    data: &B,
+    ext_memory: &mut E,
+    meta: &M,
+) -> Result<Header, HeaderError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let mut position: usize = 0;
+    let header = decode_header_at_position::<B, E, M>(data, ext_memory, meta, &mut position)?;
+    if position != data.total_len() {
+        Err(HeaderError::Parsing(ParserError::SomeDataNotUsedBlob {
+            from: position,
+        }))
+    } else {
+        Ok(header)
+    }
+}
+
+/// Decode block header starting at given position.
+///
+/// Header type is taken from the metadata if available, otherwise well-known
+/// header layout is used. As decoding proceeds, `position` gets changed.
+pub fn decode_header_at_position<B, E, M>(
+    data: &B,
+    ext_memory: &mut E,
+    meta: &M,
+    position: &mut usize,
+) -> Result<Header, HeaderError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    match meta.header_ty() {
+        Some(header_ty) => {
+            let header_data = decode_as_type_at_position::<B, E, M>(
+                &header_ty,
+                data,
+                ext_memory,
+                &meta.types(),
+                position,
+            )
+            .map_err(HeaderError::Parsing)?;
+            header_from_data(header_data.data)
+        }
+        None => header_well_known::<B, E>(data, ext_memory, position).map_err(HeaderError::Parsing),
+    }
+}
+
+/// Decode block header using well-known header layout.
+///
+/// All data is expected to be used.
+pub fn decode_header_well_known<B, E>(
+    data: &B,
+    ext_memory: &mut E,
+) -> Result<Header, HeaderError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    let mut position: usize = 0;
+    let header =
+        header_well_known::<B, E>(data, ext_memory, &mut position).map_err(HeaderError::Parsing)?;
+    if position != data.total_len() {
+        Err(HeaderError::Parsing(ParserError::SomeDataNotUsedBlob {
+            from: position,
+        }))
+    } else {
+        Ok(header)
+    }
+}
+
+/// Decode block header using well-known header layout, starting at given
+/// position, with [`ParserError`] as error type.
+///
+/// As decoding proceeds, `position` gets changed.
+fn header_well_known<B, E>(
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+) -> Result<Header, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    let parent_hash = H256(read_array::<B, E, HASH_LEN>(data, ext_memory, position)?);
+    let number = get_compact::<u64, B, E>(data, ext_memory, position)?;
+    let state_root = H256(read_array::<B, E, HASH_LEN>(data, ext_memory, position)?);
+    let extrinsics_root = H256(read_array::<B, E, HASH_LEN>(data, ext_memory, position)?);
+    let number_of_items = get_compact::<u32, B, E>(data, ext_memory, position)?;
+    let mut digest: Vec<DigestItem> = Vec::new();
+    for _i in 0..number_of_items {
+        let item_start = *position;
+        let variant_index = data.read_byte(ext_memory, *position)?;
+        *position += 1;
+        let digest_item = match variant_index {
+            DIGEST_OTHER => DigestItem::Other(read_vec(data, ext_memory, position)?),
+            DIGEST_CONSENSUS => DigestItem::Consensus {
+                engine_id: EngineId(read_array::<B, E, ENGINE_ID_LEN>(
+                    data, ext_memory, position,
+                )?),
+                data: read_vec(data, ext_memory, position)?,
+            },
+            DIGEST_SEAL => DigestItem::Seal {
+                engine_id: EngineId(read_array::<B, E, ENGINE_ID_LEN>(
+                    data, ext_memory, position,
+                )?),
+                data: read_vec(data, ext_memory, position)?,
+            },
+            DIGEST_PRE_RUNTIME => {
+                let engine_id = EngineId(read_array::<B, E, ENGINE_ID_LEN>(
+                    data, ext_memory, position,
+                )?);
+                let pre_runtime_data = read_vec(data, ext_memory, position)?;
+                DigestItem::PreRuntime {
+                    engine_id,
+                    data: PreRuntimeData::new(&engine_id, pre_runtime_data),
+                }
+            }
+            DIGEST_RUNTIME_ENVIRONMENT_UPDATED => DigestItem::RuntimeEnvironmentUpdated,
+            _ => {
+                return Err(ParserError::UnexpectedEnumVariant {
+                    position: item_start,
+                })
+            }
+        };
+        digest.push(digest_item)
+    }
+    Ok(Header {
+        parent_hash,
+        number,
+        state_root,
+        extrinsics_root,
+        digest,
+    })
+}
+
+/// Read fixed length array and move current parser position accordingly.
+fn read_array<B, E, const N: usize>(
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+) -> Result<[u8; N], ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    let slice = data.read_slice(ext_memory, *position, N)?;
+    let out: [u8; N] = slice
+        .as_ref()
+        .try_into()
+        .expect("constant length, always fits");
+    *position += N;
+    Ok(out)
+}
+
+/// Read compact-prefixed `Vec<u8>` and move current parser position
+/// accordingly.
+fn read_vec<B, E>(
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+) -> Result<Vec<u8>, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    let length = get_compact::<u32, B, E>(data, ext_memory, position)? as usize;
+    let slice = data.read_slice(ext_memory, *position, length)?;
+    *position += length;
+    Ok(slice.as_ref().to_vec())
+}
+
+/// Transform parsed header into [`Header`].
+fn header_from_data<E: ExternalMemory>(parsed_data: ParsedData) -> Result<Header, HeaderError<E>> {
+    let mut parent_hash = None;
+    let mut number = None;
+    let mut state_root = None;
+    let mut extrinsics_root = None;
+    let mut digest = None;
+    if let ParsedData::Composite(field_data_set) = parsed_data {
+        for field_data in field_data_set.into_iter() {
+            match (field_data.field_name.as_deref(), field_data.data.data) {
+                (Some("parent_hash"), ParsedData::H256(hash)) => parent_hash = Some(hash),
+                (Some("number"), found_number) => number = number_from_data(found_number),
+                (Some("state_root"), ParsedData::H256(hash)) => state_root = Some(hash),
+                (Some("extrinsics_root"), ParsedData::H256(hash)) => extrinsics_root = Some(hash),
+                (Some("digest"), found_digest) => digest = Some(digest_from_data(found_digest)?),
+                _ => (),
+            }
+        }
+    }
+    match (parent_hash, number, state_root, extrinsics_root, digest) {
+        (
+            Some(parent_hash),
+            Some(number),
+            Some(state_root),
+            Some(extrinsics_root),
+            Some(digest),
+        ) => Ok(Header {
+            parent_hash,
+            number,
+            state_root,
+            extrinsics_root,
+            digest,
+        }),
+        _ => Err(HeaderError::UnexpectedHeaderFormat),
+    }
+}
+
+/// Get block number from parsed data.
+fn number_from_data(parsed_data: ParsedData) -> Option<u64> {
+    match parsed_data {
+        ParsedData::PrimitiveU8 { value, .. } => Some(value.into()),
+        ParsedData::PrimitiveU16 { value, .. } => Some(value.into()),
+        ParsedData::PrimitiveU32 { value, .. } => Some(value.into()),
+        ParsedData::PrimitiveU64 { value, .. } => Some(value),
+        _ => None,
+    }
+}
+
+/// Get digest items from parsed data.
+fn digest_from_data<E: ExternalMemory>(
+    parsed_data: ParsedData,
+) -> Result<Vec<DigestItem>, HeaderError<E>> {
+    if let ParsedData::Composite(mut field_data_set) = parsed_data {
+        if field_data_set.len() == 1 {
+            if let ParsedData::SequenceRaw(SequenceRawData {
+                element_info: _,
+                data,
+            }) = field_data_set.remove(0).data.data
+            {
+                return data.into_iter().map(digest_item_from_data).collect();
+            }
+        }
+    }
+    Err(HeaderError::UnexpectedDigestFormat)
+}
+
+/// Get digest item from parsed data.
+fn digest_item_from_data<E: ExternalMemory>(
+    parsed_data: ParsedData,
+) -> Result<DigestItem, HeaderError<E>> {
+    if let ParsedData::Variant(VariantData {
+        variant_name,
+        variant_docs: _,
+        fields,
+    }) = parsed_data
+    {
+        let mut field_bytes = Vec::new();
+        for field_data in fields.into_iter() {
+            field_bytes.push(bytes_from_data(field_data.data.data));
+        }
+        match (variant_name.as_str(), field_bytes.as_slice()) {
+            ("PreRuntime", [Some(engine_id), Some(data)]) => {
+                let engine_id = engine_id_from_bytes(engine_id)?;
+                Ok(DigestItem::PreRuntime {
+                    engine_id,
+                    data: PreRuntimeData::new(&engine_id, data.to_owned()),
+                })
+            }
+            ("Consensus", [Some(engine_id), Some(data)]) => Ok(DigestItem::Consensus {
+                engine_id: engine_id_from_bytes(engine_id)?,
+                data: data.to_owned(),
+            }),
+            ("Seal", [Some(engine_id), Some(data)]) => Ok(DigestItem::Seal {
+                engine_id: engine_id_from_bytes(engine_id)?,
+                data: data.to_owned(),
+            }),
+            ("Other", [Some(data)]) => Ok(DigestItem::Other(data.to_owned())),
+            ("RuntimeEnvironmentUpdated", []) => Ok(DigestItem::RuntimeEnvironmentUpdated),
+            _ => Err(HeaderError::UnexpectedDigestItem(variant_name)),
+        }
+    } else {
+        Err(HeaderError::UnexpectedDigestFormat)
+    }
+}
+
+/// Get bytes from parsed `Vec<u8>` or `[u8; N]` data.
+fn bytes_from_data(parsed_data: ParsedData) -> Option<Vec<u8>> {
+    match parsed_data {
+        ParsedData::Sequence(SequenceData {
+            element_info: _,
+            data: Sequence::U8(value),
+        }) => Some(value),
+        ParsedData::SequenceRaw(SequenceRawData {
+            element_info: _,
+            data,
+        }) if data.is_empty() => Some(Vec::new()),
+        _ => None,
+    }
+}
+
+/// Get [`EngineId`] from bytes.
+fn engine_id_from_bytes<E: ExternalMemory>(bytes: &[u8]) -> Result<EngineId, HeaderError<E>> {
+    bytes
+        .try_into()
+        .map(EngineId)
+        .map_err(|_| HeaderError::UnexpectedDigestFormat)
+}
+
+impl Header {
+    /// Transform `Header` into a set of flat formatted [`ExtendedCard`]s.
+    pub fn card(&self, indent: u32) -> Vec<ExtendedCard> {
+        let mut out = vec![
+            ExtendedCard {
+                parser_card: ParserCard::ParentHash(self.parent_hash),
+                indent,
+                info_flat: Vec::new(),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::BlockNumber(self.number),
+                indent,
+                info_flat: Vec::new(),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::StateRoot(self.state_root),
+                indent,
+                info_flat: Vec::new(),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::ExtrinsicsRoot(self.extrinsics_root),
+                indent,
+                info_flat: Vec::new(),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::DigestAnnounced(self.digest.len()),
+                indent,
+                info_flat: Vec::new(),
+            },
+        ];
+        for digest_item in self.digest.iter() {
+            out.extend_from_slice(&digest_item.card(indent + 1))
+        }
+        out
+    }
+}
+
+impl DigestItem {
+    /// Transform `DigestItem` into a set of flat formatted [`ExtendedCard`]s.
+    pub fn card(&self, indent: u32) -> Vec<ExtendedCard> {
+        match &self {
+            DigestItem::PreRuntime { engine_id, data } => {
+                let mut out = vec![ExtendedCard {
+                    parser_card: ParserCard::DigestPreRuntime(*engine_id),
+                    indent,
+                    info_flat: Vec::new(),
+                }];
+                out.extend_from_slice(&data.card(indent + 1));
+                out
+            }
+            DigestItem::Consensus { engine_id, data } => vec![
+                ExtendedCard {
+                    parser_card: ParserCard::DigestConsensus(*engine_id),
+                    indent,
+                    info_flat: Vec::new(),
+                },
+                bytes_card(data, indent + 1),
+            ],
+            DigestItem::Seal { engine_id, data } => vec![
+                ExtendedCard {
+                    parser_card: ParserCard::DigestSeal(*engine_id),
+                    indent,
+                    info_flat: Vec::new(),
+                },
+                bytes_card(data, indent + 1),
+            ],
+            DigestItem::Other(data) => vec![
+                ExtendedCard {
+                    parser_card: ParserCard::DigestOther,
+                    indent,
+                    info_flat: Vec::new(),
+                },
+                bytes_card(data, indent + 1),
+            ],
+            DigestItem::RuntimeEnvironmentUpdated => vec![ExtendedCard {
+                parser_card: ParserCard::DigestRuntimeEnvironmentUpdated,
+                indent,
+                info_flat: Vec::new(),
+            }],
+        }
+    }
+}
+
+impl PreRuntimeData {
+    /// Transform `PreRuntimeData` into a set of flat formatted
+    /// [`ExtendedCard`]s.
+    pub fn card(&self, indent: u32) -> Vec<ExtendedCard> {
+        match &self {
+            PreRuntimeData::Aura { slot } => vec![ExtendedCard {
+                parser_card: ParserCard::Slot(*slot),
+                indent,
+                info_flat: Vec::new(),
+            }],
+            PreRuntimeData::Babe(babe_pre_digest) => babe_pre_digest.card(indent),
+            PreRuntimeData::Raw(data) => vec![bytes_card(data, indent)],
+        }
+    }
+}
+
+impl BabePreDigest {
+    /// Transform `BabePreDigest` into a set of flat formatted
+    /// [`ExtendedCard`]s.
+    pub fn card(&self, indent: u32) -> Vec<ExtendedCard> {
+        let (kind, authority_index, slot, vrf) = match &self {
+            BabePreDigest::Primary {
+                authority_index,
+                slot,
+                vrf_output,
+                vrf_proof,
+            } => (
+                "Primary",
+                authority_index,
+                slot,
+                Some((vrf_output, vrf_proof)),
+            ),
+            BabePreDigest::SecondaryPlain {
+                authority_index,
+                slot,
+            } => ("SecondaryPlain", authority_index, slot, None),
+            BabePreDigest::SecondaryVrf {
+                authority_index,
+                slot,
+                vrf_output,
+                vrf_proof,
+            } => (
+                "SecondaryVRF",
+                authority_index,
+                slot,
+                Some((vrf_output, vrf_proof)),
+            ),
+        };
+        let mut out = vec![
+            ExtendedCard {
+                parser_card: ParserCard::BabePreDigest(kind.to_owned()),
+                indent,
+                info_flat: Vec::new(),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::AuthorityIndex(*authority_index),
+                indent: indent + 1,
+                info_flat: Vec::new(),
+            },
+            ExtendedCard {
+                parser_card: ParserCard::Slot(*slot),
+                indent: indent + 1,
+                info_flat: Vec::new(),
+            },
+        ];
+        if let Some((vrf_output, vrf_proof)) = vrf {
+            out.push(ExtendedCard {
+                parser_card: ParserCard::VrfOutput(*vrf_output),
+                indent: indent + 1,
+                info_flat: Vec::new(),
+            });
+            out.push(ExtendedCard {
+                parser_card: ParserCard::VrfProof(*vrf_proof),
+                indent: indent + 1,
+                info_flat: Vec::new(),
+            });
+        }
+        out
+    }
+}
+
+/// Card for opaque digest data.
+fn bytes_card(data: &[u8], indent: u32) -> ExtendedCard {
+    ExtendedCard {
+        parser_card: ParserCard::SequenceU8 {
+            hex: hex::encode(data),
+            text: None,
+            element_info_flat: Vec::new(),
+        },
+        indent,
+        info_flat: Vec::new(),
+    }
+}
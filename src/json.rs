@@ -0,0 +1,995 @@
+//! Build SCALE-encoded values from JSON.
+//!
+//! JSON value is encoded as a type from the metadata types registry. Types
+//! are resolved same way as in
+//! [`decode_with_type`](crate::decoding_sci::decode_with_type), and JSON is
+//! expected to follow the type structure:
+//!
+//! - structs with named fields are JSON objects with field names as keys,
+//!   structs with multiple unnamed fields and tuples are JSON arrays, structs
+//!   with single unnamed field are represented by the field value itself
+//! - enum variants without fields are variant name strings, e.g. `"None"`,
+//!   enum variants with fields are single-key objects with variant name as a
+//!   key and fields content as a value, e.g. `{"Id": "5Grw..."}`
+//! - vectors and arrays are JSON arrays, vectors and arrays of `u8` could also
+//!   be hex strings
+//! - unsigned and signed integers are JSON numbers or decimal strings, large
+//!   integers must be decimal strings
+//! - compacts are transparent
+//! - bitvecs are arrays of booleans, or strings of `0` and `1`
+//!
+//! Special types, as recognized by [`SpecialtyTypeHinted`], accept natural
+//! text forms:
+//!
+//! - `AccountId32` and public keys are SS58 addresses or hex strings
+//! - hashes and signatures are hex strings
+//! - `Era` is `"Immortal"` or an object with `period` and `phase`
+//! - `PerThing` items are either raw parts as integers, or percent strings,
+//!   e.g. `"12.5%"`
+//!
+//! Calls and events need no special treatment, as the pallet enum is a regular
+//! enum: `{"Balances": {"transfer": {"dest": {"Id": "5Grw..."}, "value":
+//! "1000000000000"}}}`.
+//!
+//! Errors contain JSON pointer to the offending value.
+use base58::FromBase58;
+use bitvec::prelude::{BitOrder, BitStore, BitVec, Lsb0, Msb0};
+use external_memory_tools::ExternalMemory;
+use num_bigint::{BigInt, BigUint};
+use parity_scale_codec::{Compact, Encode};
+use scale_info::{
+    form::PortableForm, interner::UntrackedSymbol, Field, TypeDef, TypeDefBitSequence,
+    TypeDefPrimitive, Variant,
+};
+use serde_json::{Map, Value};
+use sp_arithmetic::{PerThing, PerU16, Perbill, Percent, Permill, Perquintill};
+use sp_core_hashing::blake2_512;
+
+#[cfg(not(feature = "std"))]
+use crate::additional_types::Era;
+#[cfg(feature = "std")]
+use sp_runtime::generic::Era;
+
+#[cfg(not(feature = "std"))]
+use core::any::TypeId;
+#[cfg(feature = "std")]
+use std::any::TypeId;
+
+use crate::std::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::decoding_sci::{find_bit_order, husk_type, FoundBitOrder, ResolvedTy, Ty};
+#[cfg(target_pointer_width = "32")]
+use crate::encoding::patch_encode_bitvec_u64;
+use crate::encoding::{encode_big_signed, encode_big_unsigned, encode_unsigned};
+use crate::error::{JsonError, ParserError};
+use crate::propagated::{Checker, SpecialtySet};
+use crate::special_indicators::SpecialtyTypeHinted;
+use crate::special_types::{
+    ACCOUNT_ID20_LEN, ACCOUNT_ID_32_LEN, PUBLIC_LEN_ECDSA, PUBLIC_LEN_ED25519, PUBLIC_LEN_SR25519,
+    SIGNATURE_LEN_ECDSA, SIGNATURE_LEN_ED25519, SIGNATURE_LEN_SR25519,
+};
+use crate::traits::{AsMetadata, ResolveType};
+
+/// Length of `H160`.
+const H160_LEN: usize = 20;
+
+/// Length of `H256`.
+const H256_LEN: usize = 32;
+
+/// Length of `H512`.
+const H512_LEN: usize = 64;
+
+/// Optional prefix for hex strings.
+const HEX_PREFIX: &str = "0x";
+
+/// Length of SS58 checksum, for public keys.
+const SS58_CHECKSUM_LEN: usize = 2;
+
+/// Prefix hashed together with SS58 address body to get the checksum.
+const SS58_PREFIX: &[u8] = b"SS58PRE";
+
+/// `Era` variant name for immortal transactions.
+const ERA_IMMORTAL: &str = "Immortal";
+
+/// `Era` object key for period.
+const ERA_PERIOD: &str = "period";
+
+/// `Era` object key for phase.
+const ERA_PHASE: &str = "phase";
+
+/// Percent sign for `PerThing` items in percent form.
+const PERCENT_SIGN: char = '%';
+
+/// JSON value for enum variants without fields given as variant name.
+static JSON_NULL: Value = Value::Null;
+
+/// Encode JSON text as a known type.
+pub fn encode_json_as_type<E, M>(
+    json: &str,
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<Vec<u8>, JsonError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let value: Value =
+        serde_json::from_str(json).map_err(|e| JsonError::InvalidJson(e.to_string()))?;
+    encode_json_value_as_type::<E, M>(&value, ty_symbol, ext_memory, registry)
+}
+
+/// Encode JSON [`Value`] as a known type.
+pub fn encode_json_value_as_type<E, M>(
+    value: &Value,
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<Vec<u8>, JsonError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let mut output: Vec<u8> = Vec::new();
+    json_with_type::<E, M>(
+        &Ty::Symbol(ty_symbol),
+        value,
+        "",
+        ext_memory,
+        registry,
+        Checker::new(),
+        &mut output,
+    )?;
+    Ok(output)
+}
+
+/// JSON pointer for an element within the value at `pointer`.
+fn pointer_push(pointer: &str, token: &str) -> String {
+    format!("{pointer}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Error for value at `pointer` not matching the `expected` form.
+fn unexpected<E: ExternalMemory>(pointer: &str, expected: &'static str) -> JsonError<E> {
+    JsonError::UnexpectedValue {
+        pointer: pointer.to_owned(),
+        expected,
+    }
+}
+
+/// General function to encode JSON [`Value`] as [`Ty`], appending the encoded
+/// bytes to `output`.
+#[allow(clippy::too_many_arguments)]
+fn json_with_type<E, M>(
+    ty_input: &Ty,
+    value: &Value,
+    pointer: &str,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    mut checker: Checker,
+    output: &mut Vec<u8>,
+) -> Result<(), JsonError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let (ty, id) = match ty_input {
+        Ty::Resolved(resolved_ty) => (resolved_ty.ty.to_owned(), resolved_ty.id),
+        Ty::Symbol(ty_symbol) => (registry.resolve_ty(ty_symbol.id, ext_memory)?, ty_symbol.id),
+    };
+    let compact = checker.specialty_set.compact_at.is_some();
+    match SpecialtyTypeHinted::from_type(&ty) {
+        SpecialtyTypeHinted::AccountId20 => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                ACCOUNT_ID20_LEN,
+                false,
+                "AccountId20",
+                output,
+            )?
+        }
+        SpecialtyTypeHinted::AccountId32 => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                ACCOUNT_ID_32_LEN,
+                true,
+                "AccountId32",
+                output,
+            )?
+        }
+        SpecialtyTypeHinted::Era => {
+            checker.reject_compact()?;
+            json_era(value, pointer)?.encode_to(output)
+        }
+        SpecialtyTypeHinted::H160 => {
+            checker.reject_compact()?;
+            json_bytes(value, pointer, H160_LEN, false, "H160", output)?
+        }
+        SpecialtyTypeHinted::H256 => {
+            checker.reject_compact()?;
+            json_bytes(value, pointer, H256_LEN, false, "H256", output)?
+        }
+        SpecialtyTypeHinted::H512 => {
+            checker.reject_compact()?;
+            json_bytes(value, pointer, H512_LEN, false, "H512", output)?
+        }
+        SpecialtyTypeHinted::Perbill => encode_unsigned::<u32>(
+            Perbill::from_json(value, pointer)?.deconstruct(),
+            compact,
+            output,
+        ),
+        SpecialtyTypeHinted::Percent => encode_unsigned::<u8>(
+            Percent::from_json(value, pointer)?.deconstruct(),
+            compact,
+            output,
+        ),
+        SpecialtyTypeHinted::Permill => encode_unsigned::<u32>(
+            Permill::from_json(value, pointer)?.deconstruct(),
+            compact,
+            output,
+        ),
+        SpecialtyTypeHinted::Perquintill => encode_unsigned::<u64>(
+            Perquintill::from_json(value, pointer)?.deconstruct(),
+            compact,
+            output,
+        ),
+        SpecialtyTypeHinted::PerU16 => encode_unsigned::<u16>(
+            PerU16::from_json(value, pointer)?.deconstruct(),
+            compact,
+            output,
+        ),
+        SpecialtyTypeHinted::PublicEd25519 => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                PUBLIC_LEN_ED25519,
+                true,
+                "ed25519 public key",
+                output,
+            )?
+        }
+        SpecialtyTypeHinted::PublicSr25519 => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                PUBLIC_LEN_SR25519,
+                true,
+                "sr25519 public key",
+                output,
+            )?
+        }
+        SpecialtyTypeHinted::PublicEcdsa => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                PUBLIC_LEN_ECDSA,
+                true,
+                "ecdsa public key",
+                output,
+            )?
+        }
+        SpecialtyTypeHinted::SignatureEd25519 => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                SIGNATURE_LEN_ED25519,
+                false,
+                "ed25519 signature",
+                output,
+            )?
+        }
+        SpecialtyTypeHinted::SignatureSr25519 => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                SIGNATURE_LEN_SR25519,
+                false,
+                "sr25519 signature",
+                output,
+            )?
+        }
+        SpecialtyTypeHinted::SignatureEcdsa => {
+            checker.reject_compact()?;
+            json_bytes(
+                value,
+                pointer,
+                SIGNATURE_LEN_ECDSA,
+                false,
+                "ecdsa signature",
+                output,
+            )?
+        }
+        // Calls and events are regular enums here.
+        SpecialtyTypeHinted::None
+        | SpecialtyTypeHinted::PalletSpecific(_)
+        | SpecialtyTypeHinted::UncheckedExtrinsic => match &ty.type_def {
+            TypeDef::Composite(x) => json_fields::<E, M>(
+                &x.fields, value, pointer, ext_memory, registry, checker, output,
+            )?,
+            TypeDef::Variant(x) => {
+                checker.reject_compact()?;
+                let (found_variant, fields_value, fields_pointer) =
+                    json_variant::<E>(&x.variants, value, pointer)?;
+                output.push(found_variant.index);
+                json_fields::<E, M>(
+                    &found_variant.fields,
+                    fields_value,
+                    &fields_pointer,
+                    ext_memory,
+                    registry,
+                    Checker::new(),
+                    output,
+                )?
+            }
+            TypeDef::Sequence(x) => {
+                checker.drop_cycle_check();
+                json_elements_set::<E, M>(
+                    &x.type_param,
+                    None,
+                    value,
+                    pointer,
+                    ext_memory,
+                    registry,
+                    checker,
+                    output,
+                )?
+            }
+            TypeDef::Array(x) => json_elements_set::<E, M>(
+                &x.type_param,
+                Some(x.len as usize),
+                value,
+                pointer,
+                ext_memory,
+                registry,
+                checker,
+                output,
+            )?,
+            TypeDef::Tuple(x) => {
+                let elements: &[Value] = match value {
+                    Value::Array(elements) => elements,
+                    Value::Null if x.fields.is_empty() => &[],
+                    _ => return Err(unexpected(pointer, "tuple")),
+                };
+                if elements.len() != x.fields.len() {
+                    return Err(JsonError::LengthMismatch {
+                        pointer: pointer.to_owned(),
+                        expected: x.fields.len(),
+                        found: elements.len(),
+                    });
+                }
+                if x.fields.len() > 1 {
+                    checker.reject_compact()?;
+                    checker.forget_hint();
+                }
+                for (i, (inner_ty_symbol, element)) in
+                    x.fields.iter().zip(elements.iter()).enumerate()
+                {
+                    let inner_id = inner_ty_symbol.id;
+                    let inner_ty = registry.resolve_ty(inner_id, ext_memory)?;
+                    let inner_checker = checker.update_for_ty(&inner_ty, inner_id)?;
+                    json_with_type::<E, M>(
+                        &Ty::Resolved(ResolvedTy {
+                            ty: inner_ty,
+                            id: inner_id,
+                        }),
+                        element,
+                        &pointer_push(pointer, &i.to_string()),
+                        ext_memory,
+                        registry,
+                        inner_checker,
+                        output,
+                    )?;
+                }
+            }
+            TypeDef::Primitive(x) => {
+                json_primitive::<E>(x, value, pointer, checker.specialty_set, output)?
+            }
+            TypeDef::Compact(x) => {
+                checker.reject_compact()?;
+                checker.specialty_set.compact_at = Some(id);
+                checker.check_id(x.type_param.id)?;
+                json_with_type::<E, M>(
+                    &Ty::Symbol(&x.type_param),
+                    value,
+                    pointer,
+                    ext_memory,
+                    registry,
+                    checker,
+                    output,
+                )?
+            }
+            TypeDef::BitSequence(x) => {
+                checker.reject_compact()?;
+                json_bit_sequence::<E, M>(x, id, value, pointer, ext_memory, registry, output)?
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Encode JSON [`Value`] as a set of [`Field`]s. Used for structs, enums,
+/// calls and events.
+///
+/// Named fields are expected in a JSON object. Multiple unnamed fields are
+/// expected in a JSON array, single unnamed field is the value itself.
+fn json_fields<E, M>(
+    fields: &[Field<PortableForm>],
+    value: &Value,
+    pointer: &str,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    mut checker: Checker,
+    output: &mut Vec<u8>,
+) -> Result<(), JsonError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    if fields.len() > 1 {
+        // Only single-field structs can be processed as a compact.
+        checker.reject_compact()?;
+        checker.forget_hint();
+    }
+    if fields.is_empty() {
+        return match value {
+            Value::Null => Ok(()),
+            Value::Array(elements) if elements.is_empty() => Ok(()),
+            Value::Object(map) if map.is_empty() => Ok(()),
+            _ => Err(unexpected(pointer, "empty struct")),
+        };
+    }
+    if fields.iter().all(|field| field.name.is_some()) {
+        let map = match value {
+            Value::Object(map) => map,
+            _ => return Err(unexpected(pointer, "struct with named fields")),
+        };
+        check_unknown_fields(fields, map, pointer)?;
+        for field in fields.iter() {
+            let field_name = field.name.as_deref().unwrap_or_default();
+            let field_value = map.get(field_name).ok_or_else(|| JsonError::MissingField {
+                pointer: pointer.to_owned(),
+                field_name: field_name.to_owned(),
+            })?;
+            json_with_type::<E, M>(
+                &Ty::Symbol(&field.ty),
+                field_value,
+                &pointer_push(pointer, field_name),
+                ext_memory,
+                registry,
+                checker.update_for_field(field)?,
+                output,
+            )?;
+        }
+    } else if fields.len() == 1 {
+        json_with_type::<E, M>(
+            &Ty::Symbol(&fields[0].ty),
+            value,
+            pointer,
+            ext_memory,
+            registry,
+            checker.update_for_field(&fields[0])?,
+            output,
+        )?;
+    } else {
+        let elements = match value {
+            Value::Array(elements) => elements,
+            _ => return Err(unexpected(pointer, "struct with unnamed fields")),
+        };
+        if elements.len() != fields.len() {
+            return Err(JsonError::LengthMismatch {
+                pointer: pointer.to_owned(),
+                expected: fields.len(),
+                found: elements.len(),
+            });
+        }
+        for (i, (field, element)) in fields.iter().zip(elements.iter()).enumerate() {
+            json_with_type::<E, M>(
+                &Ty::Symbol(&field.ty),
+                element,
+                &pointer_push(pointer, &i.to_string()),
+                ext_memory,
+                registry,
+                checker.update_for_field(field)?,
+                output,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Check that JSON object has no keys other than the field names.
+fn check_unknown_fields<E: ExternalMemory>(
+    fields: &[Field<PortableForm>],
+    map: &Map<String, Value>,
+    pointer: &str,
+) -> Result<(), JsonError<E>> {
+    for key in map.keys() {
+        if !fields
+            .iter()
+            .any(|field| field.name.as_deref() == Some(key.as_str()))
+        {
+            return Err(JsonError::UnknownField {
+                pointer: pointer.to_owned(),
+                field_name: key.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Find enum [`Variant`] for JSON [`Value`].
+///
+/// Variant without fields could be a variant name string. Otherwise a
+/// single-key JSON object is expected, with variant name as a key.
+///
+/// Returns found variant, JSON value for variant fields and its pointer.
+fn json_variant<'a, 'b, E: ExternalMemory>(
+    variants: &'a [Variant<PortableForm>],
+    value: &'b Value,
+    pointer: &str,
+) -> Result<(&'a Variant<PortableForm>, &'b Value, String), JsonError<E>> {
+    let (variant_name, fields_value, fields_pointer) = match value {
+        Value::String(variant_name) => (variant_name, &JSON_NULL, pointer.to_owned()),
+        Value::Object(map) if map.len() == 1 => {
+            let (variant_name, fields_value) = map.iter().next().expect("checked length");
+            (
+                variant_name,
+                fields_value,
+                pointer_push(pointer, variant_name),
+            )
+        }
+        _ => return Err(unexpected(pointer, "enum variant")),
+    };
+    let found_variant = variants
+        .iter()
+        .find(|variant| &variant.name == variant_name)
+        .ok_or_else(|| JsonError::UnknownVariant {
+            pointer: pointer.to_owned(),
+            variant_name: variant_name.to_owned(),
+        })?;
+    Ok((found_variant, fields_value, fields_pointer))
+}
+
+/// Encode JSON [`Value`] as a set of identical elements. Used for vectors
+/// (`len` is `None`, length prefix is added) and arrays (`len` is known).
+///
+/// Elements are expected in a JSON array. Elements of `u8` type could also be
+/// in a hex string.
+#[allow(clippy::too_many_arguments)]
+fn json_elements_set<E, M>(
+    element: &UntrackedSymbol<TypeId>,
+    len: Option<usize>,
+    value: &Value,
+    pointer: &str,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    checker: Checker,
+    output: &mut Vec<u8>,
+) -> Result<(), JsonError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    checker.reject_compact()?;
+
+    let husked = husk_type::<E, M>(element, registry, ext_memory, checker, false)?;
+
+    let check_len = |found: usize| -> Result<(), JsonError<E>> {
+        match len {
+            Some(expected) if expected != found => Err(JsonError::LengthMismatch {
+                pointer: pointer.to_owned(),
+                expected,
+                found,
+            }),
+            _ => Ok(()),
+        }
+    };
+
+    match value {
+        Value::String(text) => {
+            let is_u8 = matches!(husked.ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U8))
+                && husked.checker.specialty_set.compact_at.is_none();
+            if !is_u8 {
+                return Err(unexpected(pointer, "sequence"));
+            }
+            let bytes = decode_hex(text).ok_or_else(|| unexpected(pointer, "hex string"))?;
+            check_len(bytes.len())?;
+            if len.is_none() {
+                Compact(bytes.len() as u32).encode_to(output);
+            }
+            output.extend_from_slice(&bytes);
+        }
+        Value::Array(elements) => {
+            check_len(elements.len())?;
+            if len.is_none() {
+                Compact(elements.len() as u32).encode_to(output);
+            }
+            for (i, element_value) in elements.iter().enumerate() {
+                json_with_type::<E, M>(
+                    &Ty::Resolved(ResolvedTy {
+                        ty: husked.ty.to_owned(),
+                        id: husked.id,
+                    }),
+                    element_value,
+                    &pointer_push(pointer, &i.to_string()),
+                    ext_memory,
+                    registry,
+                    husked.checker.clone(),
+                    output,
+                )?;
+            }
+        }
+        _ => return Err(unexpected(pointer, "sequence")),
+    }
+    Ok(())
+}
+
+/// Decode hex string, with or without `0x` prefix.
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    hex::decode(text.strip_prefix(HEX_PREFIX).unwrap_or(text)).ok()
+}
+
+/// Decode SS58 address into public key bytes. Address prefix is not checked.
+fn decode_ss58(text: &str) -> Option<Vec<u8>> {
+    let data = text.from_base58().ok()?;
+    let prefix_len = match data.first()? {
+        0..=63 => 1,
+        64..=127 => 2,
+        _ => return None,
+    };
+    let body_len = data.len().checked_sub(SS58_CHECKSUM_LEN)?;
+    if body_len <= prefix_len {
+        return None;
+    }
+    let hash = blake2_512(&[SS58_PREFIX, &data[..body_len]].concat());
+    if data[body_len..] != hash[..SS58_CHECKSUM_LEN] {
+        return None;
+    }
+    Some(data[prefix_len..body_len].to_vec())
+}
+
+/// Encode JSON string with fixed number of bytes, as hex or, if
+/// `allow_ss58` is set, as SS58 address.
+fn json_bytes<E: ExternalMemory>(
+    value: &Value,
+    pointer: &str,
+    len: usize,
+    allow_ss58: bool,
+    expected: &'static str,
+    output: &mut Vec<u8>,
+) -> Result<(), JsonError<E>> {
+    let text = value
+        .as_str()
+        .ok_or_else(|| unexpected(pointer, expected))?;
+    let bytes = match text.strip_prefix(HEX_PREFIX) {
+        Some(hex_part) => hex::decode(hex_part).ok(),
+        None if allow_ss58 => decode_ss58(text),
+        None => hex::decode(text).ok(),
+    }
+    .ok_or_else(|| unexpected(pointer, expected))?;
+    if bytes.len() != len {
+        return Err(unexpected(pointer, expected));
+    }
+    output.extend_from_slice(&bytes);
+    Ok(())
+}
+
+/// Get [`Era`] from JSON [`Value`].
+fn json_era<E: ExternalMemory>(value: &Value, pointer: &str) -> Result<Era, JsonError<E>> {
+    match value {
+        Value::String(text) if text == ERA_IMMORTAL => Ok(Era::Immortal),
+        Value::Object(map) => {
+            let period = map
+                .get(ERA_PERIOD)
+                .and_then(json_unsigned)
+                .and_then(|period| u64::try_from(period).ok());
+            let phase = map
+                .get(ERA_PHASE)
+                .and_then(json_unsigned)
+                .and_then(|phase| u64::try_from(phase).ok());
+            match (period, phase) {
+                (Some(period), Some(phase))
+                    if map.len() == 2
+                        && period.is_power_of_two()
+                        && period >= 4
+                        && phase < period =>
+                {
+                    Ok(Era::Mortal(period, phase))
+                }
+                _ => Err(unexpected(pointer, "Era")),
+            }
+        }
+        _ => Err(unexpected(pointer, "Era")),
+    }
+}
+
+/// Get unsigned integer from JSON number or decimal string.
+fn json_unsigned(value: &Value) -> Option<u128> {
+    match value {
+        Value::Number(number) => number.as_u64().map(u128::from),
+        Value::String(text) => text.parse::<u128>().ok(),
+        _ => None,
+    }
+}
+
+/// Get signed integer from JSON number or decimal string.
+fn json_signed(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(number) => number.as_i64().map(i128::from),
+        Value::String(text) => text.parse::<i128>().ok(),
+        _ => None,
+    }
+}
+
+/// Get big integer from JSON number or decimal string.
+fn json_big_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(number) if number.is_u64() || number.is_i64() => Some(number.to_string()),
+        Value::String(text) => Some(text.to_owned()),
+        _ => None,
+    }
+}
+
+/// Get raw parts of a `PerThing` item from JSON [`Value`].
+trait FromJson: Sized {
+    fn from_json<E: ExternalMemory>(value: &Value, pointer: &str) -> Result<Self, JsonError<E>>;
+}
+
+/// Implement [`FromJson`] for `PerThing` items, producing inner type value.
+///
+/// Value could be raw parts as integer, or percent string.
+macro_rules! impl_from_json_per_thing {
+    ($($per_thing: ty, $inner: ty), *) => {
+        $(
+            impl FromJson for $per_thing {
+                fn from_json<E: ExternalMemory>(value: &Value, pointer: &str) -> Result<Self, JsonError<E>> {
+                    let parts = match value {
+                        Value::String(text) if text.ends_with(PERCENT_SIGN) => {
+                            percent_to_parts(text.trim_end_matches(PERCENT_SIGN), <$per_thing>::ACCURACY as u128)
+                        }
+                        _ => json_unsigned(value),
+                    };
+                    match parts {
+                        Some(parts) if parts <= <$per_thing>::ACCURACY as u128 => {
+                            Ok(<$per_thing>::from_parts(parts as $inner))
+                        }
+                        _ => Err(unexpected(pointer, stringify!($per_thing))),
+                    }
+                }
+            }
+        )*
+    }
+}
+
+impl_from_json_per_thing!(
+    PerU16,
+    u16,
+    Percent,
+    u8,
+    Permill,
+    u32,
+    Perbill,
+    u32,
+    Perquintill,
+    u64
+);
+
+/// Transform decimal percent value into `PerThing` raw parts.
+///
+/// Value is rounded down.
+fn percent_to_parts(text: &str, accuracy: u128) -> Option<u128> {
+    const PERCENT_MAX: u128 = 100;
+    let (integer_part, fraction_part) = match text.split_once('.') {
+        Some((integer_part, fraction_part)) => (integer_part, fraction_part),
+        None => (text, ""),
+    };
+    if integer_part.is_empty()
+        || !(integer_part.chars().chain(fraction_part.chars())).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let numerator = format!("{integer_part}{fraction_part}")
+        .parse::<u128>()
+        .ok()?;
+    let denominator = 10u128
+        .checked_pow(fraction_part.len() as u32)?
+        .checked_mul(PERCENT_MAX)?;
+    numerator.checked_mul(accuracy).map(|a| a / denominator)
+}
+
+/// Encode JSON [`Value`] as a primitive (variants of [`TypeDefPrimitive`]).
+///
+/// Compacts are allowed for unsigned integers only.
+fn json_primitive<E: ExternalMemory>(
+    found_ty: &TypeDefPrimitive,
+    value: &Value,
+    pointer: &str,
+    specialty_set: SpecialtySet,
+    output: &mut Vec<u8>,
+) -> Result<(), JsonError<E>> {
+    let compact = specialty_set.compact_at.is_some();
+    match found_ty {
+        TypeDefPrimitive::U8 => encode_unsigned::<u8>(
+            json_unsigned(value)
+                .and_then(|a| u8::try_from(a).ok())
+                .ok_or_else(|| unexpected(pointer, "u8"))?,
+            compact,
+            output,
+        ),
+        TypeDefPrimitive::U16 => encode_unsigned::<u16>(
+            json_unsigned(value)
+                .and_then(|a| u16::try_from(a).ok())
+                .ok_or_else(|| unexpected(pointer, "u16"))?,
+            compact,
+            output,
+        ),
+        TypeDefPrimitive::U32 => encode_unsigned::<u32>(
+            json_unsigned(value)
+                .and_then(|a| u32::try_from(a).ok())
+                .ok_or_else(|| unexpected(pointer, "u32"))?,
+            compact,
+            output,
+        ),
+        TypeDefPrimitive::U64 => encode_unsigned::<u64>(
+            json_unsigned(value)
+                .and_then(|a| u64::try_from(a).ok())
+                .ok_or_else(|| unexpected(pointer, "u64"))?,
+            compact,
+            output,
+        ),
+        TypeDefPrimitive::U128 => encode_unsigned::<u128>(
+            json_unsigned(value).ok_or_else(|| unexpected(pointer, "u128"))?,
+            compact,
+            output,
+        ),
+        _ => {
+            specialty_set.reject_compact()?;
+            match found_ty {
+                TypeDefPrimitive::Bool => value
+                    .as_bool()
+                    .ok_or_else(|| unexpected(pointer, "bool"))?
+                    .encode_to(output),
+                TypeDefPrimitive::Char => {
+                    let mut chars = value
+                        .as_str()
+                        .ok_or_else(|| unexpected(pointer, "char"))?
+                        .chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => (c as u32).encode_to(output),
+                        _ => return Err(unexpected(pointer, "char")),
+                    }
+                }
+                TypeDefPrimitive::Str => value
+                    .as_str()
+                    .ok_or_else(|| unexpected(pointer, "str"))?
+                    .encode_to(output),
+                TypeDefPrimitive::U256 => json_big_text(value)
+                    .and_then(|text| BigUint::parse_bytes(text.as_bytes(), 10))
+                    .and_then(|a| encode_big_unsigned(&a, output))
+                    .ok_or_else(|| unexpected(pointer, "u256"))?,
+                TypeDefPrimitive::I8 => json_signed(value)
+                    .and_then(|a| i8::try_from(a).ok())
+                    .ok_or_else(|| unexpected(pointer, "i8"))?
+                    .encode_to(output),
+                TypeDefPrimitive::I16 => json_signed(value)
+                    .and_then(|a| i16::try_from(a).ok())
+                    .ok_or_else(|| unexpected(pointer, "i16"))?
+                    .encode_to(output),
+                TypeDefPrimitive::I32 => json_signed(value)
+                    .and_then(|a| i32::try_from(a).ok())
+                    .ok_or_else(|| unexpected(pointer, "i32"))?
+                    .encode_to(output),
+                TypeDefPrimitive::I64 => json_signed(value)
+                    .and_then(|a| i64::try_from(a).ok())
+                    .ok_or_else(|| unexpected(pointer, "i64"))?
+                    .encode_to(output),
+                TypeDefPrimitive::I128 => json_signed(value)
+                    .ok_or_else(|| unexpected(pointer, "i128"))?
+                    .encode_to(output),
+                TypeDefPrimitive::I256 => json_big_text(value)
+                    .and_then(|text| BigInt::parse_bytes(text.as_bytes(), 10))
+                    .and_then(|a| encode_big_signed(&a, output))
+                    .ok_or_else(|| unexpected(pointer, "i256"))?,
+                TypeDefPrimitive::U8
+                | TypeDefPrimitive::U16
+                | TypeDefPrimitive::U32
+                | TypeDefPrimitive::U64
+                | TypeDefPrimitive::U128 => unreachable!("unsigned integers processed above"),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Get bits from JSON array of booleans or string of `0` and `1`.
+fn json_bits(value: &Value) -> Option<Vec<bool>> {
+    match value {
+        Value::Array(elements) => elements.iter().map(|element| element.as_bool()).collect(),
+        Value::String(text) => text
+            .chars()
+            .map(|c| match c {
+                '0' => Some(false),
+                '1' => Some(true),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Assemble `BitVec` from bits.
+fn collect_bitvec<T: BitStore, O: BitOrder>(bits: &[bool]) -> BitVec<T, O> {
+    bits.iter().copied().collect()
+}
+
+/// Encode JSON [`Value`] as a bitvec.
+#[allow(clippy::too_many_arguments)]
+fn json_bit_sequence<E, M>(
+    bit_ty: &TypeDefBitSequence<PortableForm>,
+    id: u32,
+    value: &Value,
+    pointer: &str,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    output: &mut Vec<u8>,
+) -> Result<(), JsonError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let bits = json_bits(value).ok_or_else(|| unexpected(pointer, "bitvec"))?;
+
+    let bitorder = find_bit_order::<E, M>(bit_ty, id, ext_memory, registry)?;
+    let bitstore_type = registry.resolve_ty(bit_ty.bit_store_type.id, ext_memory)?;
+
+    match (&bitstore_type.type_def, bitorder) {
+        (TypeDef::Primitive(TypeDefPrimitive::U8), FoundBitOrder::Lsb0) => {
+            collect_bitvec::<u8, Lsb0>(&bits).encode_to(output)
+        }
+        (TypeDef::Primitive(TypeDefPrimitive::U8), FoundBitOrder::Msb0) => {
+            collect_bitvec::<u8, Msb0>(&bits).encode_to(output)
+        }
+        (TypeDef::Primitive(TypeDefPrimitive::U16), FoundBitOrder::Lsb0) => {
+            collect_bitvec::<u16, Lsb0>(&bits).encode_to(output)
+        }
+        (TypeDef::Primitive(TypeDefPrimitive::U16), FoundBitOrder::Msb0) => {
+            collect_bitvec::<u16, Msb0>(&bits).encode_to(output)
+        }
+        (TypeDef::Primitive(TypeDefPrimitive::U32), FoundBitOrder::Lsb0) => {
+            collect_bitvec::<u32, Lsb0>(&bits).encode_to(output)
+        }
+        (TypeDef::Primitive(TypeDefPrimitive::U32), FoundBitOrder::Msb0) => {
+            collect_bitvec::<u32, Msb0>(&bits).encode_to(output)
+        }
+        #[cfg(target_pointer_width = "64")]
+        (TypeDef::Primitive(TypeDefPrimitive::U64), FoundBitOrder::Lsb0) => {
+            collect_bitvec::<u64, Lsb0>(&bits).encode_to(output)
+        }
+        #[cfg(target_pointer_width = "64")]
+        (TypeDef::Primitive(TypeDefPrimitive::U64), FoundBitOrder::Msb0) => {
+            collect_bitvec::<u64, Msb0>(&bits).encode_to(output)
+        }
+        #[cfg(target_pointer_width = "32")]
+        (TypeDef::Primitive(TypeDefPrimitive::U64), FoundBitOrder::Lsb0) => {
+            patch_encode_bitvec_u64(&collect_bitvec::<u32, Lsb0>(&bits), false, output)
+        }
+        #[cfg(target_pointer_width = "32")]
+        (TypeDef::Primitive(TypeDefPrimitive::U64), FoundBitOrder::Msb0) => {
+            patch_encode_bitvec_u64(&collect_bitvec::<u32, Msb0>(&bits), true, output)
+        }
+        _ => return Err(JsonError::Parsing(ParserError::NotBitStoreType { id })),
+    }
+    Ok(())
+}
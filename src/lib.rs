@@ -3,10 +3,15 @@
 //! metadata. Decoded data could be pattern matched or represented in readable
 //! form.
 //!
-//! Currently only the most recent `RuntimeMetadata` version `V14` is supported
-//! for the chain metadata, as only the `V14` has conveniently in-built types
-//! database in it, thus allowing to track types using metadata itself without
-//! any additional information.
+//! Currently `RuntimeMetadata` versions `V14` and `V15` are supported for the
+//! chain metadata, as only these versions have conveniently in-built types
+//! database in them, thus allowing to track types using metadata itself without
+//! any additional information. Both versions implement [`AsMetadata`] trait.
+//!
+//! Metadata, as fetched from chain, is `RuntimeMetadataPrefixed`, i.e. the
+//! metadata itself preceded by `META` magic prefix and metadata version.
+//! [`MetadataVersioned`] decodes prefixed metadata of any supported version,
+//! and implements [`AsMetadata`] trait as well.
 //!
 //! # Assumptions
 //!
@@ -40,13 +45,44 @@
 //! [`PalletCallMetadata`](frame_metadata::v14::PalletCallMetadata). Further
 //! decoding uses the type information found.
 //!
+//! Calls wrapping other calls, such as batches, proxy, multisig, sudo and
+//! scheduler calls, could be walked into a tree of nested calls, each with its
+//! location path and wrapper semantics, see [`call_tree`](crate::call_tree).
+//!
 //! Remaining data is SCALE-encoded set of signable extensions, as declared in
 //! [`ExtrinsicMetadata`](frame_metadata::v14::ExtrinsicMetadata). Chain genesis
 //! hash must be found among the decoded extensions and must match the genesis
 //! hash known for the chain. Spec version must be found among the decoded
 //! extensions and must match the spec version derived from the provided
-//! metadata. This is done to make sure that the correct metadata was used for
-//! parsing.
+//! metadata. Tx version, if found among the decoded extensions, must match the
+//! tx version derived from the provided metadata. This is done to make sure
+//! that the correct metadata was used for parsing.
+//!
+//! Decoded extensions are grouped by signed extension identifier into
+//! [`ExtensionData`](crate::cards::ExtensionData), each with the `ty` (extra)
+//! part and the `additional_signed` part, and are carded in same grouping.
+//!
+//! Mortal transactions are valid only within a limited block range, which
+//! could be calculated from the decoded `Era` and a reference block, see
+//! [`mortality`](crate::mortality). With a reference block supplied, `Era`
+//! cards show the block range.
+//!
+//! Call hash and the exact signing payload (call data followed by extensions
+//! data, hashed if longer than 256 bytes) are available through
+//! [`MarkedData`].
+//!
+//! ## Blocks
+//!
+//! Block body is a SCALE-encoded set of unchecked extrinsics, each extrinsic
+//! prefixed with its length. Extrinsics are separated and decoded one by one,
+//! so that a failure in one extrinsic does not abort the whole block decoding.
+//! Block header type is found in the metadata types registry.
+//!
+//! Block header could also be decoded separately into parent hash, number,
+//! state root, extrinsics root and digest. Well-known header layout is used if
+//! the metadata has no header type. Digest items are rendered with consensus
+//! engine identifiers, and `BABE` and `aura` pre-runtime digests are decoded
+//! to show the slot.
 //!
 //! ## Storage items
 //!
@@ -54,6 +90,30 @@
 //! SCALE-encoded data has a type declared in corresponding chain metadata
 //! [`StorageEntryType`](frame_metadata::v14::StorageEntryType).
 //!
+//! ## Pallet errors
+//!
+//! Pallet errors in `DispatchError::Module` are SCALE-encoded with pallet
+//! index and fixed length encoded pallet error. Pallet error type is selected
+//! from the metadata using the pallet index, so that the pallet error is
+//! decoded into a named error variant.
+//!
+//! ## Pallet constants
+//!
+//! Pallet constants are stored in the metadata as SCALE-encoded values with
+//! declared types, and could be decoded by pallet name and constant name.
+//!
+//! ## Events
+//!
+//! Events of a block are stored in `System` pallet `Events` storage entry.
+//! Decoded event records contain the phase of block execution, the event
+//! itself, and the event topics, and could be grouped by extrinsic index.
+//!
+//! ## Runtime api calls
+//!
+//! Runtime api methods are described in `V15` metadata. Method inputs (such as
+//! data sent through `state_call` rpc) and method output are decoded using the
+//! types declared in [`RuntimeApiMethodMetadata`](frame_metadata::v15::RuntimeApiMethodMetadata).
+//!
 //! # Parsed data and cards
 //!
 //! Parsing data with a given type results in [`ExtendedData`]. Parsing data as
@@ -67,6 +127,19 @@
 //! displayed to user. Each `Call` and `ExtendedData` gets carded into
 //! `Vec<ExtendedCard>`.
 //!
+//! Decoding is driven by the types from the metadata, and decoded data is
+//! passed to a [`Visitor`](crate::visitor::Visitor). `ExtendedData` is
+//! assembled by one of the visitors. Custom visitors could be used to
+//! extract only the needed values without assembling whole `ExtendedData`, or
+//! to produce custom output formats.
+//!
+//! Data could also be skipped as a known type, without decoding, to find where
+//! the encoded value ends, see [`skipping`](crate::skipping), or a single
+//! field could be extracted by its path, see [`extract`](crate::extract).
+//!
+//! Parsed data could be encoded back into bytes with the type from the
+//! metadata, see [`encoding`](crate::encoding).
+//!
 //! # Special types
 //!
 //! Types, as stored in the metadata types registry, have associated
@@ -76,13 +149,21 @@
 //! Some `Path` identifiers are used without further checking, such as
 //! well-known array-based types (`AccountId32`, hashes, public keys, signatures
 //! etc) or other types with known or easily determined encoded size, such as
-//! `Era`, `PerThing` items etc.
+//! `Era`, `PerThing` items etc. Ethereum-style `AccountId20` is displayed
+//! with [EIP-55](https://eips.ethereum.org/EIPS/eip-55) checksum.
 //!
 //! Other `Path` identifiers are checked first, and used only if the further
 //! discovered type information matches the expected one, this is the case for
 //! `Call` and `Event`. If it does not match, the data is parsed as is, i.e.
 //! without fitting into specific item format.
 //!
+//! Chain-specific special types could be added by the user, see
+//! [`CustomType`](crate::special_indicators::CustomType). Registered custom
+//! types are matched by the whole `Path` before the built-in ones, and are
+//! decoded and carded by the user-provided strategy. Custom types are attached
+//! to the metadata with [`MetadataWithCustomTypes`], and are used by all parser
+//! entry points.
+//!
 //! Enums and structs contain sets of [`Field`](scale_info::Field)s. Field
 //! `name` and `type_name` may also hint at type specialty information, although
 //! less reliably than the `Path`. Such hints do not cause errors in parser flow
@@ -112,12 +193,13 @@
 //! base58 representation of `AccountId32` and public keys, identical to the one
 //! in `sp_core`.
 //!
+//! Feature `json` enables building SCALE-encoded values from JSON, see
+//! `json` module.
+//!
 //! # Examples
 //!```
 //! # #[cfg(feature = "std")]
 //! # {
-//! use frame_metadata::v14::RuntimeMetadataV14;
-//! use parity_scale_codec::Decode;
 //! use primitive_types::H256;
 //! use scale_info::{IntoPortable, Path, Registry};
 //! use sp_core::crypto::AccountId32;
@@ -127,8 +209,9 @@
 //!     parse_transaction,
 //!     AddressableBuffer,
 //!     AsMetadata,
+//!     MetadataVersioned,
 //!     cards::{
-//!         Call, ExtendedData, FieldData, Info,
+//!         Call, ExtendedData, ExtensionData, FieldData, Info,
 //!         PalletSpecificData, ParsedData, VariantData,
 //!     },
 //!     special_indicators::SpecialtyUnsignedInteger,
@@ -140,12 +223,9 @@
 //! // Hexadecimal metadata, such as one fetched through rpc query
 //! let metadata_westend9111_hex = std::fs::read_to_string("for_tests/westend9111").unwrap();
 //!
-//! // SCALE-encoded `V14` metadata, first 5 elements cut off here are `META` prefix and
-//! // `V14` enum index
-//! let metadata_westend9111_vec = hex::decode(&metadata_westend9111_hex.trim()).unwrap()[5..].to_vec();
-//!
-//! // `RuntimeMetadataV14` decoded and ready to use.
-//! let metadata_westend9111 = RuntimeMetadataV14::decode(&mut &metadata_westend9111_vec[..]).unwrap();
+//! // Metadata decoded and ready to use. `META` prefix is checked and metadata
+//! // version is selected automatically.
+//! let metadata_westend9111 = MetadataVersioned::from_prefixed_hex(&metadata_westend9111_hex).unwrap();
 //!
 //! // Chain genesis hash, typically well-known. Could be fetched through a separate rpc query.
 //! let westend_genesis_hash = H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap();
@@ -227,219 +307,241 @@
 //! ];
 //! assert_eq!(call_data.0.fields, expected_field_data);
 //!
-//! // Parsed extensions. Note that many extensions are empty.
+//! // Parsed extensions, grouped by signed extension. Note that many
+//! // extensions are empty.
 //! let expected_extensions_data = vec![
-//!     ExtendedData {
-//!         data: ParsedData::Composite(Vec::new()),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                     "frame_system",
-//!                     "extensions",
-//!                     "check_spec_version",
-//!                     "CheckSpecVersion",
-//!                 ])
-//!                     .unwrap()
-//!                     .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
-//!     },
-//!     ExtendedData {
-//!         data: ParsedData::Composite(Vec::new()),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                     "frame_system",
-//!                     "extensions",
-//!                     "check_tx_version",
-//!                     "CheckTxVersion",
-//!                 ])
-//!                     .unwrap()
-//!                     .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
-//!     },
-//!     ExtendedData {
-//!         data: ParsedData::Composite(Vec::new()),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                     "frame_system",
-//!                     "extensions",
-//!                     "check_genesis",
-//!                     "CheckGenesis",
-//!                 ])
-//!                     .unwrap()
-//!                     .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
-//!     },
-//!     ExtendedData {
-//!         data: ParsedData::Composite(vec![
-//!             FieldData {
-//!                 field_name: None,
-//!                 type_name: Some(String::from("Era")),
-//!                 field_docs: String::new(),
-//!                 data: ExtendedData {
-//!                     info: vec![
-//!                         Info {
-//!                             docs: String::new(),
-//!                             path: Path::from_segments(vec![
-//!                                 "sp_runtime",
-//!                                 "generic",
-//!                                 "era",
-//!                                 "Era",
-//!                             ])
-//!                             .unwrap()
-//!                             .into_portable(&mut Registry::new()),
-//!                         }
-//!                     ],
-//!                     data: ParsedData::Era(Era::Mortal(64, 61)),
+//!     ExtensionData {
+//!         identifier: String::from("CheckSpecVersion"),
+//!         extra: ExtendedData {
+//!             data: ParsedData::Composite(Vec::new()),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                         "frame_system",
+//!                         "extensions",
+//!                         "check_spec_version",
+//!                         "CheckSpecVersion",
+//!                     ])
+//!                         .unwrap()
+//!                         .into_portable(&mut Registry::new()),
 //!                 }
-//!             }
-//!         ]),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                     "frame_system",
-//!                     "extensions",
-//!                     "check_mortality",
-//!                     "CheckMortality",
-//!                 ])
-//!                 .unwrap()
-//!                 .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
+//!             ]
+//!         },
+//!         additional_signed: ExtendedData {
+//!             data: ParsedData::PrimitiveU32 {
+//!                 value: 9111,
+//!                 specialty: SpecialtyUnsignedInteger::SpecVersion
+//!             },
+//!             info: Vec::new()
+//!         },
 //!     },
-//!     ExtendedData {
-//!         data: ParsedData::Composite(vec![
-//!             FieldData {
-//!                 field_name: None,
-//!                 type_name: Some(String::from("T::Index")),
-//!                 field_docs: String::new(),
-//!                 data: ExtendedData {
-//!                     data: ParsedData::PrimitiveU32 {
-//!                         value: 261,
-//!                         specialty: SpecialtyUnsignedInteger::Nonce,
-//!                     },
-//!                     info: Vec::new()
+//!     ExtensionData {
+//!         identifier: String::from("CheckTxVersion"),
+//!         extra: ExtendedData {
+//!             data: ParsedData::Composite(Vec::new()),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                         "frame_system",
+//!                         "extensions",
+//!                         "check_tx_version",
+//!                         "CheckTxVersion",
+//!                     ])
+//!                         .unwrap()
+//!                         .into_portable(&mut Registry::new()),
 //!                 }
-//!             }
-//!         ]),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                     "frame_system",
-//!                     "extensions",
-//!                     "check_nonce",
-//!                     "CheckNonce",
-//!                 ])
-//!                     .unwrap()
-//!                     .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
+//!             ]
+//!         },
+//!         additional_signed: ExtendedData {
+//!             data: ParsedData::PrimitiveU32 {
+//!                 value: 7,
+//!                 specialty: SpecialtyUnsignedInteger::TxVersion
+//!             },
+//!             info: Vec::new()
+//!         },
 //!     },
-//!     ExtendedData {
-//!         data: ParsedData::Composite(Vec::new()),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                     "frame_system",
-//!                     "extensions",
-//!                     "check_weight",
-//!                     "CheckWeight",
-//!                 ])
-//!                     .unwrap()
-//!                     .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
+//!     ExtensionData {
+//!         identifier: String::from("CheckGenesis"),
+//!         extra: ExtendedData {
+//!             data: ParsedData::Composite(Vec::new()),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                         "frame_system",
+//!                         "extensions",
+//!                         "check_genesis",
+//!                         "CheckGenesis",
+//!                     ])
+//!                         .unwrap()
+//!                         .into_portable(&mut Registry::new()),
+//!                 }
+//!             ]
+//!         },
+//!         additional_signed: ExtendedData {
+//!             data: ParsedData::GenesisHash(H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap()),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                        "primitive_types",
+//!                        "H256",
+//!                    ])
+//!                        .unwrap()
+//!                        .into_portable(&mut Registry::new()),
+//!                 }
+//!             ]
+//!         },
 //!     },
-//!     ExtendedData {
-//!         data: ParsedData::Composite(vec![
-//!             FieldData {
-//!                 field_name: None,
-//!                 type_name: Some(String::from("BalanceOf<T>")),
-//!                 field_docs: String::new(),
-//!                 data: ExtendedData {
-//!                     data: ParsedData::PrimitiveU128 {
-//!                         value: 10000000,
-//!                         specialty: SpecialtyUnsignedInteger::Tip
-//!                     },
-//!                     info: Vec::new()
+//!     ExtensionData {
+//!         identifier: String::from("CheckMortality"),
+//!         extra: ExtendedData {
+//!             data: ParsedData::Composite(vec![
+//!                 FieldData {
+//!                     field_name: None,
+//!                     type_name: Some(String::from("Era")),
+//!                     field_docs: String::new(),
+//!                     data: ExtendedData {
+//!                         info: vec![
+//!                             Info {
+//!                                 docs: String::new(),
+//!                                 path: Path::from_segments(vec![
+//!                                     "sp_runtime",
+//!                                     "generic",
+//!                                     "era",
+//!                                     "Era",
+//!                                 ])
+//!                                 .unwrap()
+//!                                 .into_portable(&mut Registry::new()),
+//!                             }
+//!                         ],
+//!                         data: ParsedData::Era(Era::Mortal(64, 61)),
+//!                     }
 //!                 }
-//!             }
-//!         ]),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                     "pallet_transaction_payment",
-//!                     "ChargeTransactionPayment",
-//!                 ])
+//!             ]),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                         "frame_system",
+//!                         "extensions",
+//!                         "check_mortality",
+//!                         "CheckMortality",
+//!                     ])
 //!                     .unwrap()
 //!                     .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
-//!     },
-//!     ExtendedData {
-//!         data: ParsedData::PrimitiveU32 {
-//!             value: 9111,
-//!             specialty: SpecialtyUnsignedInteger::SpecVersion
+//!                 }
+//!             ]
 //!         },
-//!         info: Vec::new()
-//!     },
-//!     ExtendedData {
-//!         data: ParsedData::PrimitiveU32 {
-//!             value: 7,
-//!             specialty: SpecialtyUnsignedInteger::TxVersion
+//!         additional_signed: ExtendedData {
+//!             data: ParsedData::BlockHash(H256::from_str("98a8ee9e389043cd8a9954b254d822d34138b9ae97d3b7f50dc6781b13df8d84").unwrap()),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                        "primitive_types",
+//!                        "H256",
+//!                    ])
+//!                        .unwrap()
+//!                        .into_portable(&mut Registry::new()),
+//!                 }
+//!             ]
 //!         },
-//!         info: Vec::new()
 //!     },
-//!     ExtendedData {
-//!         data: ParsedData::GenesisHash(H256::from_str("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e").unwrap()),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                    "primitive_types",
-//!                    "H256",
-//!                ])
-//!                    .unwrap()
-//!                    .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
-//!     },
-//!     ExtendedData {
-//!         data: ParsedData::BlockHash(H256::from_str("98a8ee9e389043cd8a9954b254d822d34138b9ae97d3b7f50dc6781b13df8d84").unwrap()),
-//!         info: vec![
-//!             Info {
-//!                 docs: String::new(),
-//!                 path: Path::from_segments(vec![
-//!                    "primitive_types",
-//!                    "H256",
-//!                ])
-//!                    .unwrap()
-//!                    .into_portable(&mut Registry::new()),
-//!             }
-//!         ]
-//!     },
-//!     ExtendedData {
-//!         data: ParsedData::Tuple(Vec::new()),
-//!         info: Vec::new()
+//!     ExtensionData {
+//!         identifier: String::from("CheckNonce"),
+//!         extra: ExtendedData {
+//!             data: ParsedData::Composite(vec![
+//!                 FieldData {
+//!                     field_name: None,
+//!                     type_name: Some(String::from("T::Index")),
+//!                     field_docs: String::new(),
+//!                     data: ExtendedData {
+//!                         data: ParsedData::PrimitiveU32 {
+//!                             value: 261,
+//!                             specialty: SpecialtyUnsignedInteger::Nonce,
+//!                         },
+//!                         info: Vec::new()
+//!                     }
+//!                 }
+//!             ]),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                         "frame_system",
+//!                         "extensions",
+//!                         "check_nonce",
+//!                         "CheckNonce",
+//!                     ])
+//!                         .unwrap()
+//!                         .into_portable(&mut Registry::new()),
+//!                 }
+//!             ]
+//!         },
+//!         additional_signed: ExtendedData {
+//!             data: ParsedData::Tuple(Vec::new()),
+//!             info: Vec::new()
+//!         },
 //!     },
-//!     ExtendedData {
-//!         data: ParsedData::Tuple(Vec::new()),
-//!         info: Vec::new()
+//!     ExtensionData {
+//!         identifier: String::from("CheckWeight"),
+//!         extra: ExtendedData {
+//!             data: ParsedData::Composite(Vec::new()),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                         "frame_system",
+//!                         "extensions",
+//!                         "check_weight",
+//!                         "CheckWeight",
+//!                     ])
+//!                         .unwrap()
+//!                         .into_portable(&mut Registry::new()),
+//!                 }
+//!             ]
+//!         },
+//!         additional_signed: ExtendedData {
+//!             data: ParsedData::Tuple(Vec::new()),
+//!             info: Vec::new()
+//!         },
 //!     },
-//!     ExtendedData {
-//!         data: ParsedData::Tuple(Vec::new()),
-//!         info: Vec::new()
+//!     ExtensionData {
+//!         identifier: String::from("ChargeTransactionPayment"),
+//!         extra: ExtendedData {
+//!             data: ParsedData::Composite(vec![
+//!                 FieldData {
+//!                     field_name: None,
+//!                     type_name: Some(String::from("BalanceOf<T>")),
+//!                     field_docs: String::new(),
+//!                     data: ExtendedData {
+//!                         data: ParsedData::PrimitiveU128 {
+//!                             value: 10000000,
+//!                             specialty: SpecialtyUnsignedInteger::Tip
+//!                         },
+//!                         info: Vec::new()
+//!                     }
+//!                 }
+//!             ]),
+//!             info: vec![
+//!                 Info {
+//!                     docs: String::new(),
+//!                     path: Path::from_segments(vec![
+//!                         "pallet_transaction_payment",
+//!                         "ChargeTransactionPayment",
+//!                     ])
+//!                         .unwrap()
+//!                         .into_portable(&mut Registry::new()),
+//!                 }
+//!             ]
+//!         },
+//!         additional_signed: ExtendedData {
+//!             data: ParsedData::Tuple(Vec::new()),
+//!             info: Vec::new()
+//!         },
 //!     }
 //! ];
 //!
@@ -459,21 +561,36 @@ pub use external_memory_tools::{AddressableBuffer, ExternalMemory};
 use parity_scale_codec::{Decode, Encode};
 use primitive_types::H256;
 use scale_info::interner::UntrackedSymbol;
+use sp_core_hashing::blake2_256;
 
 #[cfg(not(feature = "std"))]
 pub mod additional_types;
+pub mod block;
+pub mod call_tree;
 pub mod cards;
 pub mod compacts;
+pub mod constants;
 pub mod decoding_sci;
 mod decoding_sci_ext;
+pub mod encoding;
 pub mod error;
+pub mod events;
+pub mod extract;
+pub mod header;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod mortality;
+pub mod pallet_error;
 pub mod printing_balance;
 pub mod propagated;
+pub mod runtime_api;
+pub mod skipping;
 pub mod special_indicators;
 mod special_types;
 pub mod storage_data;
 pub mod traits;
 pub mod unchecked_extrinsic;
+pub mod visitor;
 
 #[cfg(any(feature = "std", feature = "embed-display"))]
 #[cfg(test)]
@@ -496,14 +613,22 @@ use std::{any::TypeId, marker::PhantomData};
 use core::{any::TypeId, marker::PhantomData};
 
 pub use decoding_sci::{decode_as_call, decode_as_call_unmarked, ResolvedTy};
-pub use decoding_sci_ext::{decode_extensions, decode_extensions_unmarked};
-pub use traits::{AsMetadata, ResolveType};
+pub use decoding_sci_ext::{
+    decode_extensions, decode_extensions_unmarked, decode_extensions_unmarked_with_handlers,
+    decode_extensions_with_handlers, ExtensionHandler, ExtensionHandlers,
+};
+pub use special_types::AccountId20;
+pub use traits::{
+    AsMetadata, MetadataVersioned, MetadataWithCustomTypes, RegistryWithCustomTypes, ResolveType,
+};
 
-use cards::{Call, ExtendedCard, ExtendedData};
+use cards::{Call, ExtendedCard, ExtendedData, ExtensionCards, ExtensionData};
 use compacts::get_compact;
-use decoding_sci::{decode_with_type, Ty};
+use decoding_sci::{decode_with_type, decode_with_type_tolerant, visit_with_type, Ty};
 use error::{ParserError, SignableError};
 use propagated::Propagated;
+use unchecked_extrinsic::MAX_SIGNED_PAYLOAD_LEN;
+use visitor::Visitor;
 
 /// Chain data necessary to display decoded data correctly.
 ///
@@ -582,6 +707,39 @@ where
     pub fn extensions_start(&self) -> usize {
         self.extensions_start
     }
+
+    /// `blake2_256` hash of the call data.
+    pub fn call_hash(&self, ext_memory: &mut E) -> Result<H256, SignableError<E, M>> {
+        let call_data = self
+            .data
+            .read_slice(
+                ext_memory,
+                self.call_start,
+                self.extensions_start - self.call_start,
+            )
+            .map_err(|e| SignableError::Parsing(ParserError::Buffer(e)))?;
+        Ok(H256(blake2_256(call_data.as_ref())))
+    }
+
+    /// Signing payload: call data followed by extensions data, without the
+    /// call length prefix.
+    ///
+    /// Payloads longer than [`MAX_SIGNED_PAYLOAD_LEN`] are hashed.
+    pub fn signing_payload(&self, ext_memory: &mut E) -> Result<Vec<u8>, SignableError<E, M>> {
+        let payload = self
+            .data
+            .read_slice(
+                ext_memory,
+                self.call_start,
+                self.data.total_len() - self.call_start,
+            )
+            .map_err(|e| SignableError::Parsing(ParserError::Buffer(e)))?;
+        if payload.as_ref().len() > MAX_SIGNED_PAYLOAD_LEN {
+            Ok(blake2_256(payload.as_ref()).to_vec())
+        } else {
+            Ok(payload.as_ref().to_vec())
+        }
+    }
 }
 
 /// Signable transaction parsing outcome.
@@ -594,7 +752,7 @@ where
     M: AsMetadata<E>,
 {
     pub call_result: Result<Call, SignableError<E, M>>,
-    pub extensions: Vec<ExtendedData>,
+    pub extensions: Vec<ExtensionData>,
 }
 
 /// Signable transaction parsing outcome represented as formatted flat cards.
@@ -605,7 +763,7 @@ where
     M: AsMetadata<E>,
 {
     pub call_result: Result<Vec<ExtendedCard>, SignableError<E, M>>,
-    pub extensions: Vec<ExtendedCard>,
+    pub extensions: Vec<ExtensionCards>,
 }
 
 impl<E, M> TransactionParsed<E, M>
@@ -616,13 +774,11 @@ where
     /// Transform nested data from `TransactionParsed` into flat cards.
     pub fn card(self, short_specs: &ShortSpecs, spec_name: &str) -> TransactionCarded<E, M> {
         let start_indent = 0;
-        let mut extensions: Vec<ExtendedCard> = Vec::new();
-        for ext in self.extensions.iter() {
-            let addition_set = ext.card(start_indent, true, short_specs, spec_name);
-            if !addition_set.is_empty() {
-                extensions.extend_from_slice(&addition_set)
-            }
-        }
+        let extensions = self
+            .extensions
+            .iter()
+            .map(|ext| ext.card(start_indent, short_specs, spec_name))
+            .collect();
         TransactionCarded {
             call_result: self
                 .call_result
@@ -632,6 +788,20 @@ where
     }
 }
 
+impl<E, M> TransactionCarded<E, M>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    /// Add mortality window, calculated for the reference block, into
+    /// extensions `Era` cards.
+    pub fn set_reference_block(&mut self, reference_block: u64) {
+        for extension in self.extensions.iter_mut() {
+            extension.set_reference_block(reference_block)
+        }
+    }
+}
+
 /// Parse a signable transaction.
 pub fn parse_transaction<B, E, M>(
     data: &B,
@@ -666,7 +836,7 @@ where
 #[derive(Debug)]
 pub struct TransactionUnmarkedParsed {
     pub call: Call,
-    pub extensions: Vec<ExtendedData>,
+    pub extensions: Vec<ExtensionData>,
 }
 
 /// Signable transaction parsing outcome represented as formatted flat cards,
@@ -674,20 +844,18 @@ pub struct TransactionUnmarkedParsed {
 #[derive(Debug)]
 pub struct TransactionUnmarkedCarded {
     pub call: Vec<ExtendedCard>,
-    pub extensions: Vec<ExtendedCard>,
+    pub extensions: Vec<ExtensionCards>,
 }
 
 impl TransactionUnmarkedParsed {
     /// Transform nested data from `TransactionUnmarkedParsed` into flat cards.
     pub fn card(self, short_specs: &ShortSpecs, spec_name: &str) -> TransactionUnmarkedCarded {
         let start_indent = 0;
-        let mut extensions: Vec<ExtendedCard> = Vec::new();
-        for ext in self.extensions.iter() {
-            let addition_set = ext.card(start_indent, true, short_specs, spec_name);
-            if !addition_set.is_empty() {
-                extensions.extend_from_slice(&addition_set)
-            }
-        }
+        let extensions = self
+            .extensions
+            .iter()
+            .map(|ext| ext.card(start_indent, short_specs, spec_name))
+            .collect();
         TransactionUnmarkedCarded {
             call: self.call.card(start_indent, short_specs, spec_name),
             extensions,
@@ -695,6 +863,16 @@ impl TransactionUnmarkedParsed {
     }
 }
 
+impl TransactionUnmarkedCarded {
+    /// Add mortality window, calculated for the reference block, into
+    /// extensions `Era` cards.
+    pub fn set_reference_block(&mut self, reference_block: u64) {
+        for extension in self.extensions.iter_mut() {
+            extension.set_reference_block(reference_block)
+        }
+    }
+}
+
 /// Parse a signable transaction, Ledger format. Call is not prefixed with call length.
 pub fn parse_transaction_unmarked<B, E, M>(
     data: &B,
@@ -784,3 +962,101 @@ where
         Ok(out)
     }
 }
+
+/// Decode part of `&[u8]` slice as a known type using `V14` metadata, in
+/// tolerant mode.
+///
+/// Decoding failures do not discard the data decoded so far, and are recorded
+/// as [`ParsedData::Error`](crate::cards::ParsedData::Error) nodes instead.
+/// Resulting partial [`ExtendedData`] could be carded as usual.
+///
+/// Input `position` marks the first element in data that goes into the
+/// decoding. As decoding proceeds, `position` gets changed.
+///
+/// Some data may remain undecoded here.
+pub fn decode_as_type_at_position_tolerant<B, E, M>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    data: &B,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    position: &mut usize,
+) -> Result<ExtendedData, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    decode_with_type_tolerant::<B, E, M>(
+        &Ty::Symbol(ty_symbol),
+        data,
+        ext_memory,
+        position,
+        registry,
+        Propagated::new(),
+    )
+}
+
+/// Decode part of `&[u8]` slice as a known type using `V14` metadata, passing
+/// decoded data to [`Visitor`].
+///
+/// Input `position` marks the first element in data that goes into the
+/// decoding. As decoding proceeds, `position` gets changed.
+///
+/// Some data may remain undecoded here.
+pub fn visit_as_type_at_position<B, E, M, V>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    data: &B,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    position: &mut usize,
+    visitor: &mut V,
+) -> Result<V::Value, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+    V: Visitor<E>,
+{
+    visit_with_type::<B, E, M, V>(
+        &Ty::Symbol(ty_symbol),
+        data,
+        ext_memory,
+        position,
+        registry,
+        Propagated::new(),
+        visitor,
+    )
+}
+
+/// Decode whole `&[u8]` slice as a known type using `V14` metadata, passing
+/// decoded data to [`Visitor`].
+///
+/// All data is expected to be used for the decoding.
+pub fn visit_all_as_type<B, E, M, V>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    data: &B,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    visitor: &mut V,
+) -> Result<V::Value, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+    V: Visitor<E>,
+{
+    let mut position: usize = 0;
+    let out = visit_as_type_at_position::<B, E, M, V>(
+        ty_symbol,
+        data,
+        ext_memory,
+        registry,
+        &mut position,
+        visitor,
+    )?;
+    if position != data.total_len() {
+        Err(ParserError::SomeDataNotUsedBlob { from: position })
+    } else {
+        Ok(out)
+    }
+}
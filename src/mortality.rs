@@ -0,0 +1,79 @@
+//! Mortality window of a transaction, from decoded [`Era`].
+//!
+//! Mortal transaction is valid only within a limited block range. `Era`
+//! contains the `period`, i.e. the length of the range, and the `phase`, i.e.
+//! the position of the transaction birth block within the period. Actual block
+//! numbers could be calculated only with some reference block within the
+//! range, typically the current block or the block used as the block hash in
+//! signable transaction extensions.
+//!
+//! Calculation exactly follows `birth` and `death` methods of
+//! `sp_runtime::generic::Era`.
+#[cfg(not(feature = "std"))]
+use crate::additional_types::Era;
+#[cfg(feature = "std")]
+use sp_runtime::generic::Era;
+
+/// Transaction mortality, with block range if the reference block is known.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mortality {
+    /// Transaction never expires.
+    Immortal,
+
+    /// Transaction is valid only within `period` blocks.
+    Mortal {
+        period: u64,
+        phase: u64,
+        window: Option<MortalityWindow>,
+    },
+}
+
+impl Mortality {
+    /// Mortality for decoded `Era`.
+    ///
+    /// Mortality window is calculated if the reference block is provided, and
+    /// if the window fits into `u64` block numbers.
+    pub fn from_era(era: &Era, reference_block: Option<u64>) -> Self {
+        match *era {
+            Era::Immortal => Mortality::Immortal,
+            Era::Mortal(period, phase) => Mortality::Mortal {
+                period,
+                phase,
+                window: reference_block.and_then(|reference_block| {
+                    MortalityWindow::new(period, phase, reference_block)
+                }),
+            },
+        }
+    }
+}
+
+/// Block range in which the mortal transaction is valid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MortalityWindow {
+    /// First block in which the transaction is valid.
+    pub birth: u64,
+
+    /// First block in which the transaction is no longer valid.
+    pub death: u64,
+
+    /// Number of blocks after the reference block in which the transaction
+    /// remains valid, including the reference block itself.
+    pub remaining: u64,
+}
+
+impl MortalityWindow {
+    /// Mortality window for `Era` period and phase, and reference block.
+    ///
+    /// Returns `None` if the window could not be calculated, i.e. if `period`
+    /// is zero or if the death block overflows `u64`. Decoded `Era` always has
+    /// non-zero period.
+    pub fn new(period: u64, phase: u64, reference_block: u64) -> Option<Self> {
+        let birth = (reference_block.max(phase) - phase).checked_div(period)? * period + phase;
+        let death = birth.checked_add(period)?;
+        Some(MortalityWindow {
+            birth,
+            death,
+            remaining: death.saturating_sub(reference_block),
+        })
+    }
+}
@@ -0,0 +1,173 @@
+//! Interpret pallet errors.
+//!
+//! Dispatch errors caused by pallets are `DispatchError::Module(ModuleError)`,
+//! with [`ModuleError`](https://docs.rs/sp-runtime/latest/sp_runtime/struct.ModuleError.html)
+//! containing pallet index and encoded pallet error. Pallet error type is not
+//! found in the types registry directly, and is selected by pallet index from
+//! the metadata, see [`AsMetadata::pallet_error_ty`].
+//!
+//! Encoded pallet error has fixed length, its first byte is error variant
+//! index. Any data remaining after the error variant is decoded is padding.
+use external_memory_tools::ExternalMemory;
+use scale_info::TypeDef;
+
+use crate::std::{borrow::ToOwned, vec::Vec};
+
+use crate::cards::{
+    Call, Event, ExtendedData, Info, PalletError, PalletSpecificData, ParsedData, Sequence,
+    SequenceData, SequenceRawData,
+};
+use crate::decoding_sci::decode_variant;
+use crate::error::ModuleErrorError;
+use crate::traits::{AsMetadata, ResolveType};
+
+/// [`Path`](scale_info::Path) of `sp_runtime::ModuleError`.
+pub const MODULE_ERROR_PATH: &[&str] = &["sp_runtime", "ModuleError"];
+
+/// `ModuleError` field name for pallet index.
+const MODULE_ERROR_INDEX: &str = "index";
+
+/// `ModuleError` field name for encoded pallet error.
+const MODULE_ERROR_ERROR: &str = "error";
+
+/// Decode pallet error for pallet with given index.
+pub fn decode_pallet_error<E, M>(
+    pallet_index: u8,
+    error_data: &[u8],
+    ext_memory: &mut E,
+    meta: &M,
+) -> Result<PalletError, ModuleErrorError<E, M>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let pallet_error_ty = meta
+        .pallet_error_ty(pallet_index)
+        .map_err(ModuleErrorError::MetaStructure)?
+        .ok_or(ModuleErrorError::NoPalletErrorTy { pallet_index })?;
+    let registry = meta.types();
+    let ty = registry.resolve_ty(pallet_error_ty.ty.id, ext_memory)?;
+    if let TypeDef::Variant(type_def_variant) = &ty.type_def {
+        let mut position = 0;
+        let variant_data = decode_variant::<&[u8], E, M>(
+            &type_def_variant.variants,
+            &error_data,
+            ext_memory,
+            &mut position,
+            &registry,
+        )?;
+        Ok(PalletError(PalletSpecificData {
+            pallet_info: Info::from_ty(&ty),
+            variant_docs: variant_data.variant_docs,
+            pallet_name: pallet_error_ty.pallet_name,
+            variant_name: variant_data.variant_name,
+            fields: variant_data.fields,
+        }))
+    } else {
+        Err(ModuleErrorError::NotAnEnum {
+            id: pallet_error_ty.ty.id,
+        })
+    }
+}
+
+/// Replace all `ModuleError` entries in parsed data with decoded
+/// [`PalletError`]s.
+///
+/// Entries that could not be decoded are left as is.
+pub fn resolve_module_errors<E, M>(extended_data: &mut ExtendedData, ext_memory: &mut E, meta: &M)
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    resolve_parsed_data(
+        &mut extended_data.data,
+        &extended_data.info,
+        ext_memory,
+        meta,
+    )
+}
+
+/// Replace `ModuleError` entries in [`ParsedData`] with associated set of
+/// [`Info`].
+fn resolve_parsed_data<E, M>(
+    parsed_data: &mut ParsedData,
+    info: &[Info],
+    ext_memory: &mut E,
+    meta: &M,
+) where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    if info
+        .iter()
+        .any(|info| info.path.segments == MODULE_ERROR_PATH)
+    {
+        if let Some((pallet_index, error_data)) = module_error_content(parsed_data) {
+            if let Ok(pallet_error) =
+                decode_pallet_error::<E, M>(pallet_index, &error_data, ext_memory, meta)
+            {
+                *parsed_data = ParsedData::PalletError(pallet_error);
+            }
+            return;
+        }
+    }
+    match parsed_data {
+        ParsedData::Call(Call(pallet_specific_data))
+        | ParsedData::Event(Event(pallet_specific_data))
+        | ParsedData::PalletError(PalletError(pallet_specific_data)) => {
+            for field_data in pallet_specific_data.fields.iter_mut() {
+                resolve_module_errors(&mut field_data.data, ext_memory, meta)
+            }
+        }
+        ParsedData::Composite(field_data_set) => {
+            for field_data in field_data_set.iter_mut() {
+                resolve_module_errors(&mut field_data.data, ext_memory, meta)
+            }
+        }
+        ParsedData::SequenceRaw(SequenceRawData { element_info, data }) => {
+            for element in data.iter_mut() {
+                resolve_parsed_data(element, element_info, ext_memory, meta)
+            }
+        }
+        ParsedData::Tuple(extended_data_set) => {
+            for extended_data in extended_data_set.iter_mut() {
+                resolve_module_errors(extended_data, ext_memory, meta)
+            }
+        }
+        ParsedData::Variant(variant_data) => {
+            for field_data in variant_data.fields.iter_mut() {
+                resolve_module_errors(&mut field_data.data, ext_memory, meta)
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Get pallet index and encoded pallet error from parsed `ModuleError`.
+fn module_error_content(parsed_data: &ParsedData) -> Option<(u8, Vec<u8>)> {
+    if let ParsedData::Composite(field_data_set) = parsed_data {
+        let mut found_index = None;
+        let mut found_error = None;
+        for field_data in field_data_set.iter() {
+            match (field_data.field_name.as_deref(), &field_data.data.data) {
+                (Some(MODULE_ERROR_INDEX), ParsedData::PrimitiveU8 { value, .. }) => {
+                    found_index = Some(*value)
+                }
+                (Some(MODULE_ERROR_ERROR), ParsedData::PrimitiveU8 { value, .. }) => {
+                    found_error = Some(vec![*value])
+                }
+                (
+                    Some(MODULE_ERROR_ERROR),
+                    ParsedData::Sequence(SequenceData {
+                        data: Sequence::U8(value),
+                        ..
+                    }),
+                ) => found_error = Some(value.to_owned()),
+                _ => (),
+            }
+        }
+        Some((found_index?, found_error?))
+    } else {
+        None
+    }
+}
@@ -1,6 +1,6 @@
 //! Data that can propagate hierarchically during parsing.
 use external_memory_tools::ExternalMemory;
-use frame_metadata::v14::SignedExtensionMetadata;
+use frame_metadata::v14::{PalletConstantMetadata, SignedExtensionMetadata};
 use scale_info::{form::PortableForm, Field, Path, Type};
 
 use crate::std::vec::Vec;
@@ -220,6 +220,34 @@ impl Propagated {
         }
     }
 
+    /// Initiate new `Propagated` with known [`Hint`].
+    pub fn from_hint(hint: Hint) -> Self {
+        Self {
+            checker: Checker {
+                specialty_set: SpecialtySet {
+                    compact_at: None,
+                    hint,
+                },
+                cycle_check: Vec::new(),
+            },
+            info: Vec::new(),
+        }
+    }
+
+    /// Initiate new `Propagated` for pallet constant.
+    pub fn from_constant_meta(constant_meta: &PalletConstantMetadata<PortableForm>) -> Self {
+        Self {
+            checker: Checker {
+                specialty_set: SpecialtySet {
+                    compact_at: None,
+                    hint: Hint::from_constant_meta(constant_meta),
+                },
+                cycle_check: Vec::new(),
+            },
+            info: Vec::new(),
+        }
+    }
+
     /// Initiate new `Propagated` with known, propagated from above `Checker`.
     pub fn with_checker(checker: Checker) -> Self {
         Self {
@@ -0,0 +1,148 @@
+//! Interpret runtime api calls.
+//!
+//! Runtime apis are described in [`RuntimeMetadataV15`] only. Each runtime api
+//! has a set of methods, each method has a set of named inputs and an output,
+//! all typed with metadata in-built types registry.
+//!
+//! Runtime api methods could be called by rpc `state_call` with method name
+//! `<api name>_<method name>`, for example `TransactionPaymentApi_query_info`,
+//! and SCALE-encoded inputs, concatenated in order of appearance in metadata.
+//! Call returns SCALE-encoded output.
+//!
+//! Both inputs and outputs are decoded here using the types from metadata.
+use external_memory_tools::{AddressableBuffer, ExternalMemory};
+use frame_metadata::v15::{RuntimeApiMetadata, RuntimeApiMethodMetadata, RuntimeMetadataV15};
+use scale_info::form::PortableForm;
+
+use crate::std::{borrow::ToOwned, string::String, vec::Vec};
+
+use crate::cards::{Documented, ExtendedData, FieldData, RuntimeApiCall};
+use crate::decode_all_as_type;
+use crate::decoding_sci::{decode_with_type, Ty};
+use crate::error::RuntimeApiError;
+use crate::propagated::Propagated;
+
+/// Decode runtime api method inputs.
+///
+/// Inputs are decoded one after another, all data is expected to be used.
+pub fn decode_runtime_api_call<B, E>(
+    api_name: &str,
+    method_name: &str,
+    data: &B,
+    ext_memory: &mut E,
+    meta_v15: &RuntimeMetadataV15,
+) -> Result<RuntimeApiCall, RuntimeApiError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    let (api, method) = find_method::<E>(&meta_v15.apis, api_name, method_name)?;
+    let mut position: usize = 0;
+    let mut inputs: Vec<FieldData> = Vec::new();
+    for input in method.inputs.iter() {
+        let input_data = decode_with_type::<B, E, RuntimeMetadataV15>(
+            &Ty::Symbol(&input.ty),
+            data,
+            ext_memory,
+            &mut position,
+            &meta_v15.types,
+            Propagated::new(),
+        )
+        .map_err(|parser_error| RuntimeApiError::ParsingInput {
+            input_name: input.name.to_owned(),
+            parser_error,
+        })?;
+        inputs.push(FieldData {
+            field_name: Some(input.name.to_owned()),
+            type_name: None,
+            field_docs: String::new(),
+            data: input_data,
+        })
+    }
+    if position != data.total_len() {
+        return Err(RuntimeApiError::SomeDataNotUsedInputs { from: position });
+    }
+    Ok(RuntimeApiCall {
+        api_name: api.name.to_owned(),
+        api_docs: api.collect_docs(),
+        method_name: method.name.to_owned(),
+        method_docs: method.collect_docs(),
+        inputs,
+    })
+}
+
+/// Decode runtime api method output.
+///
+/// All data is expected to be used.
+pub fn decode_runtime_api_output<B, E>(
+    api_name: &str,
+    method_name: &str,
+    data: &B,
+    ext_memory: &mut E,
+    meta_v15: &RuntimeMetadataV15,
+) -> Result<ExtendedData, RuntimeApiError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    let (_, method) = find_method::<E>(&meta_v15.apis, api_name, method_name)?;
+    decode_all_as_type::<B, E, RuntimeMetadataV15>(
+        &method.output,
+        data,
+        ext_memory,
+        &meta_v15.types,
+    )
+    .map_err(RuntimeApiError::ParsingOutput)
+}
+
+/// Split `state_call` method name into runtime api name and method name.
+///
+/// Split is done only if the runtime api with such method exists in the
+/// metadata.
+pub fn split_state_call_method<'a>(
+    state_call_method: &str,
+    meta_v15: &'a RuntimeMetadataV15,
+) -> Option<(&'a str, &'a str)> {
+    for api in meta_v15.apis.iter() {
+        if let Some(method_name) = state_call_method
+            .strip_prefix(api.name.as_str())
+            .and_then(|remainder| remainder.strip_prefix('_'))
+        {
+            for method in api.methods.iter() {
+                if method.name == method_name {
+                    return Some((api.name.as_str(), method.name.as_str()));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Find runtime api and runtime api method metadata by names.
+fn find_method<'a, E: ExternalMemory>(
+    apis: &'a [RuntimeApiMetadata<PortableForm>],
+    api_name: &str,
+    method_name: &str,
+) -> Result<
+    (
+        &'a RuntimeApiMetadata<PortableForm>,
+        &'a RuntimeApiMethodMetadata<PortableForm>,
+    ),
+    RuntimeApiError<E>,
+> {
+    let api = apis
+        .iter()
+        .find(|api| api.name == api_name)
+        .ok_or_else(|| RuntimeApiError::NoApi {
+            api_name: api_name.to_owned(),
+        })?;
+    let method = api
+        .methods
+        .iter()
+        .find(|method| method.name == method_name)
+        .ok_or_else(|| RuntimeApiError::NoMethod {
+            api_name: api_name.to_owned(),
+            method_name: method_name.to_owned(),
+        })?;
+    Ok((api, method))
+}
@@ -0,0 +1,752 @@
+//! Skip encoded data without decoding it.
+//!
+//! Types are resolved same way as in
+//! [`decode_with_type`](crate::decoding_sci::decode_with_type), with the same
+//! rules for compacts, bitvecs and special types, but no [`ParsedData`] is
+//! produced: only the parser position moves. This is useful to find where the
+//! encoded value ends, for example to skip some of the concatenated values.
+//!
+//! Values are not validated beyond what is needed to find their encoded
+//! length, e.g. `bool` and `char` values are not checked.
+//!
+//! Encoded length of some types does not depend on the data. It could be
+//! found with [`fixed_encoded_len`].
+//!
+//! [`ParsedData`]: crate::cards::ParsedData
+use external_memory_tools::{AddressableBuffer, BufferError, ExternalMemory};
+use num_bigint::{BigInt, BigUint};
+use parity_scale_codec::{Compact, Decode, HasCompact};
+use primitive_types::{H160, H256, H512};
+use scale_info::{
+    form::PortableForm, interner::UntrackedSymbol, Field, TypeDef, TypeDefBitSequence,
+    TypeDefPrimitive, Variant,
+};
+use sp_arithmetic::{PerU16, Perbill, Percent, Permill, Perquintill};
+
+#[cfg(not(feature = "std"))]
+use crate::additional_types::{
+    AccountId32, PublicEcdsa, PublicEd25519, PublicSr25519, SignatureEcdsa, SignatureEd25519,
+    SignatureSr25519,
+};
+#[cfg(feature = "std")]
+use sp_core::{
+    crypto::AccountId32,
+    ecdsa::{Public as PublicEcdsa, Signature as SignatureEcdsa},
+    ed25519::{Public as PublicEd25519, Signature as SignatureEd25519},
+    sr25519::{Public as PublicSr25519, Signature as SignatureSr25519},
+};
+
+#[cfg(not(feature = "std"))]
+use core::any::TypeId;
+#[cfg(feature = "std")]
+use std::any::TypeId;
+
+use crate::std::borrow::ToOwned;
+
+use crate::compacts::{find_compact, get_compact};
+use crate::decoding_sci::{
+    find_bit_order, husk_type, pick_variant, BitVecPositions, ResolvedTy, Ty,
+};
+use crate::error::ParserError;
+use crate::propagated::{Checker, SpecialtySet};
+use crate::special_indicators::{
+    SpecialtyTypeChecked, SpecialtyTypeHinted, ENUM_INDEX_ENCODED_LEN,
+};
+use crate::special_types::{special_case_era, AccountId20, StableLength};
+use crate::traits::{AsMetadata, ResolveType};
+
+/// Skip part of data as a known type.
+///
+/// Input `position` marks the first element in data that goes into the
+/// skipping. As skipping proceeds, `position` gets changed.
+///
+/// Returns encoded length of the skipped value.
+pub fn skip_as_type_at_position<B, E, M>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    data: &B,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    position: &mut usize,
+) -> Result<usize, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let start = *position;
+    skip_with_type::<B, E, M>(
+        &Ty::Symbol(ty_symbol),
+        data,
+        ext_memory,
+        position,
+        registry,
+        Checker::new(),
+    )?;
+    Ok(*position - start)
+}
+
+/// Encoded length of a type, if it does not depend on the data.
+///
+/// `None` is returned for types with variable encoded length, such as
+/// vectors, compacts, strings, or enums with variants of different length,
+/// and for types with encoded length overflowing `usize`.
+pub fn fixed_encoded_len<E, M>(
+    ty_symbol: &UntrackedSymbol<TypeId>,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+) -> Result<Option<usize>, ParserError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    fixed_len_with_type::<E, M>(&Ty::Symbol(ty_symbol), ext_memory, registry, Checker::new())
+}
+
+/// General skipping function. Skip part of data as [`Ty`].
+///
+/// Current parser position gets changed. Type resolving follows
+/// [`decode_with_type`](crate::decoding_sci::decode_with_type), including
+/// the cycle checks.
+pub fn skip_with_type<B, E, M>(
+    ty_input: &Ty,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    mut checker: Checker,
+) -> Result<(), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let (ty, id) = match ty_input {
+        Ty::Resolved(resolved_ty) => (resolved_ty.ty.to_owned(), resolved_ty.id),
+        Ty::Symbol(ty_symbol) => (registry.resolve_ty(ty_symbol.id, ext_memory)?, ty_symbol.id),
+    };
+    let compact_at = checker.specialty_set.compact_at;
+    match SpecialtyTypeChecked::from_type::<B, E, M>(&ty, data, ext_memory, position, registry) {
+        SpecialtyTypeChecked::None => match &ty.type_def {
+            TypeDef::Composite(x) => {
+                skip_fields::<B, E, M>(&x.fields, data, ext_memory, position, registry, checker)
+            }
+            TypeDef::Variant(x) => {
+                checker.reject_compact()?;
+                if !x.variants.is_empty() {
+                    skip_variant::<B, E, M>(&x.variants, data, ext_memory, position, registry)
+                } else {
+                    Ok(())
+                }
+            }
+            TypeDef::Sequence(x) => {
+                let number_of_elements = get_compact::<u32, B, E>(data, ext_memory, position)?;
+                checker.drop_cycle_check();
+                skip_elements_set::<B, E, M>(
+                    &x.type_param,
+                    number_of_elements,
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    checker,
+                )
+            }
+            TypeDef::Array(x) => skip_elements_set::<B, E, M>(
+                &x.type_param,
+                x.len,
+                data,
+                ext_memory,
+                position,
+                registry,
+                checker,
+            ),
+            TypeDef::Tuple(x) => {
+                if x.fields.len() > 1 {
+                    checker.reject_compact()?;
+                }
+                for inner_ty_symbol in x.fields.iter() {
+                    let mut element_checker = checker.clone();
+                    element_checker.check_id(inner_ty_symbol.id)?;
+                    skip_with_type::<B, E, M>(
+                        &Ty::Symbol(inner_ty_symbol),
+                        data,
+                        ext_memory,
+                        position,
+                        registry,
+                        element_checker,
+                    )?;
+                }
+                Ok(())
+            }
+            TypeDef::Primitive(x) => {
+                skip_type_def_primitive::<B, E>(x, data, ext_memory, position, compact_at)
+            }
+            TypeDef::Compact(x) => {
+                checker.reject_compact()?;
+                checker.specialty_set.compact_at = Some(id);
+                checker.check_id(x.type_param.id)?;
+                skip_with_type::<B, E, M>(
+                    &Ty::Symbol(&x.type_param),
+                    data,
+                    ext_memory,
+                    position,
+                    registry,
+                    checker,
+                )
+            }
+            TypeDef::BitSequence(x) => {
+                checker.reject_compact()?;
+                skip_type_def_bit_sequence::<B, E, M>(x, id, data, ext_memory, position, registry)
+            }
+        },
+        SpecialtyTypeChecked::AccountId20 => {
+            skip_block_compact::<AccountId20, B, E>(data, position, compact_at)
+        }
+        SpecialtyTypeChecked::AccountId32 => {
+            skip_block_compact::<AccountId32, B, E>(data, position, compact_at)
+        }
+        SpecialtyTypeChecked::Era => {
+            checker.reject_compact()?;
+            special_case_era::<B, E>(data, ext_memory, position).map(|_| ())
+        }
+        SpecialtyTypeChecked::H160 => skip_block_compact::<H160, B, E>(data, position, compact_at),
+        SpecialtyTypeChecked::H256 => {
+            checker.reject_compact()?;
+            skip_stable_length::<H256, B, E>(data, position)
+        }
+        SpecialtyTypeChecked::H512 => skip_block_compact::<H512, B, E>(data, position, compact_at),
+        SpecialtyTypeChecked::PalletSpecific { variants, .. } => {
+            checker.reject_compact()?;
+            skip_variant::<B, E, M>(&variants, data, ext_memory, position, registry)
+        }
+        SpecialtyTypeChecked::Perbill => {
+            skip_allow_compact::<Perbill, B, E>(data, ext_memory, position, compact_at)
+        }
+        SpecialtyTypeChecked::Percent => {
+            skip_allow_compact::<Percent, B, E>(data, ext_memory, position, compact_at)
+        }
+        SpecialtyTypeChecked::Permill => {
+            skip_allow_compact::<Permill, B, E>(data, ext_memory, position, compact_at)
+        }
+        SpecialtyTypeChecked::Perquintill => {
+            skip_allow_compact::<Perquintill, B, E>(data, ext_memory, position, compact_at)
+        }
+        SpecialtyTypeChecked::PerU16 => {
+            skip_allow_compact::<PerU16, B, E>(data, ext_memory, position, compact_at)
+        }
+        SpecialtyTypeChecked::PublicEd25519 => {
+            skip_block_compact::<PublicEd25519, B, E>(data, position, compact_at)
+        }
+        SpecialtyTypeChecked::PublicSr25519 => {
+            skip_block_compact::<PublicSr25519, B, E>(data, position, compact_at)
+        }
+        SpecialtyTypeChecked::PublicEcdsa => {
+            skip_block_compact::<PublicEcdsa, B, E>(data, position, compact_at)
+        }
+        SpecialtyTypeChecked::SignatureEd25519 => {
+            skip_block_compact::<SignatureEd25519, B, E>(data, position, compact_at)
+        }
+        SpecialtyTypeChecked::SignatureSr25519 => {
+            skip_block_compact::<SignatureSr25519, B, E>(data, position, compact_at)
+        }
+        SpecialtyTypeChecked::SignatureEcdsa => {
+            skip_block_compact::<SignatureEcdsa, B, E>(data, position, compact_at)
+        }
+    }
+}
+
+/// Skip part of data as a set of [`Field`]s.
+///
+/// Current parser position gets changed.
+fn skip_fields<B, E, M>(
+    fields: &[Field<PortableForm>],
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    checker: Checker,
+) -> Result<(), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    if fields.len() > 1 {
+        // Only single-field structs can be processed as a compact.
+        checker.reject_compact()?;
+    }
+    for field in fields.iter() {
+        skip_with_type::<B, E, M>(
+            &Ty::Symbol(&field.ty),
+            data,
+            ext_memory,
+            position,
+            registry,
+            Checker::update_for_field(&checker, field)?,
+        )?;
+    }
+    Ok(())
+}
+
+/// Skip part of data as a variant.
+///
+/// Current parser position gets changed.
+fn skip_variant<B, E, M>(
+    variants: &[Variant<PortableForm>],
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+) -> Result<(), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let found_variant = pick_variant::<B, E>(variants, data, ext_memory, *position)?;
+    *position += ENUM_INDEX_ENCODED_LEN;
+    skip_fields::<B, E, M>(
+        &found_variant.fields,
+        data,
+        ext_memory,
+        position,
+        registry,
+        Checker::new(),
+    )
+}
+
+/// Skip part of data as a known number of identical elements.
+///
+/// If the element has fixed encoded length, all elements are skipped at once.
+///
+/// Current parser position gets changed.
+fn skip_elements_set<B, E, M>(
+    element: &UntrackedSymbol<TypeId>,
+    number_of_elements: u32,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+    checker: Checker,
+) -> Result<(), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    checker.reject_compact()?;
+
+    let husked = husk_type::<E, M>(element, registry, ext_memory, checker, false)?;
+
+    if husked.checker.specialty_set.compact_at.is_none() {
+        if let Some(element_len) = fixed_len_with_type::<E, M>(
+            &Ty::Resolved(ResolvedTy {
+                ty: husked.ty.to_owned(),
+                id: husked.id,
+            }),
+            ext_memory,
+            registry,
+            husked.checker.clone(),
+        )? {
+            // Number of elements comes from the data, and the total length
+            // could overflow on 32-bit targets.
+            return match element_len.checked_mul(number_of_elements as usize) {
+                Some(length) => skip_bytes::<B, E>(data, position, length),
+                None => Err(ParserError::Buffer(BufferError::DataTooShort {
+                    position: *position,
+                    minimal_length: usize::MAX,
+                })),
+            };
+        }
+    }
+
+    for _i in 0..number_of_elements {
+        skip_with_type::<B, E, M>(
+            &Ty::Resolved(ResolvedTy {
+                ty: husked.ty.to_owned(),
+                id: husked.id,
+            }),
+            data,
+            ext_memory,
+            position,
+            registry,
+            husked.checker.clone(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Skip part of data as a primitive.
+///
+/// Current parser position gets changed.
+fn skip_type_def_primitive<B, E>(
+    found_ty: &TypeDefPrimitive,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    compact_at: Option<u32>,
+) -> Result<(), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    match found_ty {
+        TypeDefPrimitive::Bool => skip_block_compact::<bool, B, E>(data, position, compact_at),
+        TypeDefPrimitive::Char => skip_block_compact::<char, B, E>(data, position, compact_at),
+        TypeDefPrimitive::Str => {
+            if let Some(id) = compact_at {
+                return Err(ParserError::UnexpectedCompactInsides { id });
+            }
+            let str_length = get_compact::<u32, B, E>(data, ext_memory, position)? as usize;
+            skip_bytes::<B, E>(data, position, str_length)
+        }
+        TypeDefPrimitive::U8 => {
+            skip_allow_compact::<u8, B, E>(data, ext_memory, position, compact_at)
+        }
+        TypeDefPrimitive::U16 => {
+            skip_allow_compact::<u16, B, E>(data, ext_memory, position, compact_at)
+        }
+        TypeDefPrimitive::U32 => {
+            skip_allow_compact::<u32, B, E>(data, ext_memory, position, compact_at)
+        }
+        TypeDefPrimitive::U64 => {
+            skip_allow_compact::<u64, B, E>(data, ext_memory, position, compact_at)
+        }
+        TypeDefPrimitive::U128 => {
+            skip_allow_compact::<u128, B, E>(data, ext_memory, position, compact_at)
+        }
+        TypeDefPrimitive::U256 => skip_block_compact::<BigUint, B, E>(data, position, compact_at),
+        TypeDefPrimitive::I8 => skip_block_compact::<i8, B, E>(data, position, compact_at),
+        TypeDefPrimitive::I16 => skip_block_compact::<i16, B, E>(data, position, compact_at),
+        TypeDefPrimitive::I32 => skip_block_compact::<i32, B, E>(data, position, compact_at),
+        TypeDefPrimitive::I64 => skip_block_compact::<i64, B, E>(data, position, compact_at),
+        TypeDefPrimitive::I128 => skip_block_compact::<i128, B, E>(data, position, compact_at),
+        TypeDefPrimitive::I256 => skip_block_compact::<BigInt, B, E>(data, position, compact_at),
+    }
+}
+
+/// Skip part of data as a bitvec.
+///
+/// Current parser position gets changed.
+fn skip_type_def_bit_sequence<B, E, M>(
+    bit_ty: &TypeDefBitSequence<PortableForm>,
+    id: u32,
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    registry: &M::TypeRegistry,
+) -> Result<(), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    find_bit_order::<E, M>(bit_ty, id, ext_memory, registry)?;
+
+    let bitstore_type = registry.resolve_ty(bit_ty.bit_store_type.id, ext_memory)?;
+    let bitvec_positions = match bitstore_type.type_def {
+        TypeDef::Primitive(TypeDefPrimitive::U8) => {
+            BitVecPositions::new::<u8, B, E>(data, ext_memory, *position)?
+        }
+        TypeDef::Primitive(TypeDefPrimitive::U16) => {
+            BitVecPositions::new::<u16, B, E>(data, ext_memory, *position)?
+        }
+        TypeDef::Primitive(TypeDefPrimitive::U32) => {
+            BitVecPositions::new::<u32, B, E>(data, ext_memory, *position)?
+        }
+        TypeDef::Primitive(TypeDefPrimitive::U64) => {
+            BitVecPositions::new::<u64, B, E>(data, ext_memory, *position)?
+        }
+        _ => return Err(ParserError::NotBitStoreType { id }),
+    };
+    skip_bytes::<B, E>(data, position, bitvec_positions.bitvec_end - *position)
+}
+
+/// Skip stable length type that could be a compact.
+fn skip_allow_compact<T, B, E>(
+    data: &B,
+    ext_memory: &mut E,
+    position: &mut usize,
+    compact_at: Option<u32>,
+) -> Result<(), ParserError<E>>
+where
+    T: StableLength + HasCompact,
+    Compact<T>: Decode,
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    if compact_at.is_some() {
+        *position = find_compact::<T, B, E>(data, ext_memory, *position)?.start_next_unit;
+        Ok(())
+    } else {
+        skip_stable_length::<T, B, E>(data, position)
+    }
+}
+
+/// Skip stable length type that can not be a compact.
+fn skip_block_compact<T, B, E>(
+    data: &B,
+    position: &mut usize,
+    compact_at: Option<u32>,
+) -> Result<(), ParserError<E>>
+where
+    T: StableLength,
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    if let Some(id) = compact_at {
+        Err(ParserError::UnexpectedCompactInsides { id })
+    } else {
+        skip_stable_length::<T, B, E>(data, position)
+    }
+}
+
+/// Skip stable length type.
+fn skip_stable_length<T, B, E>(data: &B, position: &mut usize) -> Result<(), ParserError<E>>
+where
+    T: StableLength,
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    skip_bytes::<B, E>(data, position, T::len_encoded())
+}
+
+/// Skip given number of bytes, checking that the data is long enough.
+fn skip_bytes<B, E>(data: &B, position: &mut usize, length: usize) -> Result<(), ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    match position.checked_add(length) {
+        Some(end) if end <= data.total_len() => {
+            *position = end;
+            Ok(())
+        }
+        _ => Err(ParserError::Buffer(BufferError::DataTooShort {
+            position: *position,
+            minimal_length: length,
+        })),
+    }
+}
+
+/// Encoded length of [`Ty`], if it does not depend on the data.
+fn fixed_len_with_type<E, M>(
+    ty_input: &Ty,
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    mut checker: Checker,
+) -> Result<Option<usize>, ParserError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let (ty, id) = match ty_input {
+        Ty::Resolved(resolved_ty) => (resolved_ty.ty.to_owned(), resolved_ty.id),
+        Ty::Symbol(ty_symbol) => (registry.resolve_ty(ty_symbol.id, ext_memory)?, ty_symbol.id),
+    };
+    let compact_at = checker.specialty_set.compact_at;
+    match SpecialtyTypeHinted::from_type(&ty) {
+        SpecialtyTypeHinted::None | SpecialtyTypeHinted::UncheckedExtrinsic => match &ty.type_def {
+            TypeDef::Composite(x) => {
+                fields_fixed_len::<E, M>(&x.fields, ext_memory, registry, checker)
+            }
+            TypeDef::Variant(x) => {
+                checker.reject_compact()?;
+                let mut found_len: Option<usize> = None;
+                for variant in x.variants.iter() {
+                    // Variants referring back to enclosing type have no fixed
+                    // length.
+                    let variant_checker = Checker {
+                        specialty_set: SpecialtySet::new(),
+                        cycle_check: checker.cycle_check.to_owned(),
+                    };
+                    let variant_len = match fields_fixed_len::<E, M>(
+                        &variant.fields,
+                        ext_memory,
+                        registry,
+                        variant_checker,
+                    ) {
+                        Ok(Some(len)) => len,
+                        Ok(None) | Err(ParserError::CyclicMetadata { .. }) => return Ok(None),
+                        Err(e) => return Err(e),
+                    };
+                    match found_len {
+                        Some(len) if len != variant_len => return Ok(None),
+                        _ => found_len = Some(variant_len),
+                    }
+                }
+                match found_len {
+                    Some(len) => Ok(len.checked_add(ENUM_INDEX_ENCODED_LEN)),
+                    None => Ok(Some(0)),
+                }
+            }
+            TypeDef::Sequence(_) => Ok(None),
+            TypeDef::Array(x) => {
+                checker.reject_compact()?;
+                checker.check_id(x.type_param.id)?;
+                Ok(fixed_len_with_type::<E, M>(
+                    &Ty::Symbol(&x.type_param),
+                    ext_memory,
+                    registry,
+                    checker,
+                )?
+                .and_then(|element_len| element_len.checked_mul(x.len as usize)))
+            }
+            TypeDef::Tuple(x) => {
+                if x.fields.len() > 1 {
+                    checker.reject_compact()?;
+                }
+                let mut total_len: usize = 0;
+                for inner_ty_symbol in x.fields.iter() {
+                    let mut element_checker = checker.clone();
+                    element_checker.check_id(inner_ty_symbol.id)?;
+                    match fixed_len_with_type::<E, M>(
+                        &Ty::Symbol(inner_ty_symbol),
+                        ext_memory,
+                        registry,
+                        element_checker,
+                    )? {
+                        Some(len) => match total_len.checked_add(len) {
+                            Some(new_total_len) => total_len = new_total_len,
+                            None => return Ok(None),
+                        },
+                        None => return Ok(None),
+                    }
+                }
+                Ok(Some(total_len))
+            }
+            TypeDef::Primitive(x) => primitive_fixed_len::<E>(x, compact_at),
+            TypeDef::Compact(x) => {
+                checker.reject_compact()?;
+                checker.specialty_set.compact_at = Some(id);
+                checker.check_id(x.type_param.id)?;
+                fixed_len_with_type::<E, M>(
+                    &Ty::Symbol(&x.type_param),
+                    ext_memory,
+                    registry,
+                    checker,
+                )
+            }
+            TypeDef::BitSequence(_) => {
+                checker.reject_compact()?;
+                Ok(None)
+            }
+        },
+        SpecialtyTypeHinted::AccountId20 => block_compact_fixed_len::<AccountId20, E>(compact_at),
+        SpecialtyTypeHinted::AccountId32 => block_compact_fixed_len::<AccountId32, E>(compact_at),
+        SpecialtyTypeHinted::Era => {
+            checker.reject_compact()?;
+            Ok(None)
+        }
+        SpecialtyTypeHinted::H160 => block_compact_fixed_len::<H160, E>(compact_at),
+        SpecialtyTypeHinted::H256 => block_compact_fixed_len::<H256, E>(compact_at),
+        SpecialtyTypeHinted::H512 => block_compact_fixed_len::<H512, E>(compact_at),
+        SpecialtyTypeHinted::PalletSpecific(_) => {
+            checker.reject_compact()?;
+            Ok(None)
+        }
+        SpecialtyTypeHinted::Perbill => Ok(allow_compact_fixed_len::<Perbill>(compact_at)),
+        SpecialtyTypeHinted::Percent => Ok(allow_compact_fixed_len::<Percent>(compact_at)),
+        SpecialtyTypeHinted::Permill => Ok(allow_compact_fixed_len::<Permill>(compact_at)),
+        SpecialtyTypeHinted::Perquintill => Ok(allow_compact_fixed_len::<Perquintill>(compact_at)),
+        SpecialtyTypeHinted::PerU16 => Ok(allow_compact_fixed_len::<PerU16>(compact_at)),
+        SpecialtyTypeHinted::PublicEd25519 => {
+            block_compact_fixed_len::<PublicEd25519, E>(compact_at)
+        }
+        SpecialtyTypeHinted::PublicSr25519 => {
+            block_compact_fixed_len::<PublicSr25519, E>(compact_at)
+        }
+        SpecialtyTypeHinted::PublicEcdsa => block_compact_fixed_len::<PublicEcdsa, E>(compact_at),
+        SpecialtyTypeHinted::SignatureEd25519 => {
+            block_compact_fixed_len::<SignatureEd25519, E>(compact_at)
+        }
+        SpecialtyTypeHinted::SignatureSr25519 => {
+            block_compact_fixed_len::<SignatureSr25519, E>(compact_at)
+        }
+        SpecialtyTypeHinted::SignatureEcdsa => {
+            block_compact_fixed_len::<SignatureEcdsa, E>(compact_at)
+        }
+    }
+}
+
+/// Encoded length of a set of [`Field`]s, if it does not depend on the data.
+fn fields_fixed_len<E, M>(
+    fields: &[Field<PortableForm>],
+    ext_memory: &mut E,
+    registry: &M::TypeRegistry,
+    checker: Checker,
+) -> Result<Option<usize>, ParserError<E>>
+where
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    if fields.len() > 1 {
+        checker.reject_compact()?;
+    }
+    let mut total_len: usize = 0;
+    for field in fields.iter() {
+        match fixed_len_with_type::<E, M>(
+            &Ty::Symbol(&field.ty),
+            ext_memory,
+            registry,
+            Checker::update_for_field(&checker, field)?,
+        )? {
+            Some(len) => match total_len.checked_add(len) {
+                Some(new_total_len) => total_len = new_total_len,
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(total_len))
+}
+
+/// Encoded length of a primitive, if it does not depend on the data.
+fn primitive_fixed_len<E: ExternalMemory>(
+    found_ty: &TypeDefPrimitive,
+    compact_at: Option<u32>,
+) -> Result<Option<usize>, ParserError<E>> {
+    match found_ty {
+        TypeDefPrimitive::Bool => block_compact_fixed_len::<bool, E>(compact_at),
+        TypeDefPrimitive::Char => block_compact_fixed_len::<char, E>(compact_at),
+        TypeDefPrimitive::Str => {
+            if let Some(id) = compact_at {
+                Err(ParserError::UnexpectedCompactInsides { id })
+            } else {
+                Ok(None)
+            }
+        }
+        TypeDefPrimitive::U8 => Ok(allow_compact_fixed_len::<u8>(compact_at)),
+        TypeDefPrimitive::U16 => Ok(allow_compact_fixed_len::<u16>(compact_at)),
+        TypeDefPrimitive::U32 => Ok(allow_compact_fixed_len::<u32>(compact_at)),
+        TypeDefPrimitive::U64 => Ok(allow_compact_fixed_len::<u64>(compact_at)),
+        TypeDefPrimitive::U128 => Ok(allow_compact_fixed_len::<u128>(compact_at)),
+        TypeDefPrimitive::U256 => block_compact_fixed_len::<BigUint, E>(compact_at),
+        TypeDefPrimitive::I8 => block_compact_fixed_len::<i8, E>(compact_at),
+        TypeDefPrimitive::I16 => block_compact_fixed_len::<i16, E>(compact_at),
+        TypeDefPrimitive::I32 => block_compact_fixed_len::<i32, E>(compact_at),
+        TypeDefPrimitive::I64 => block_compact_fixed_len::<i64, E>(compact_at),
+        TypeDefPrimitive::I128 => block_compact_fixed_len::<i128, E>(compact_at),
+        TypeDefPrimitive::I256 => block_compact_fixed_len::<BigInt, E>(compact_at),
+    }
+}
+
+/// Encoded length of stable length type that could be a compact.
+fn allow_compact_fixed_len<T: StableLength>(compact_at: Option<u32>) -> Option<usize> {
+    if compact_at.is_some() {
+        None
+    } else {
+        Some(T::len_encoded())
+    }
+}
+
+/// Encoded length of stable length type that can not be a compact.
+fn block_compact_fixed_len<T: StableLength, E: ExternalMemory>(
+    compact_at: Option<u32>,
+) -> Result<Option<usize>, ParserError<E>> {
+    if let Some(id) = compact_at {
+        Err(ParserError::UnexpectedCompactInsides { id })
+    } else {
+        Ok(Some(T::len_encoded()))
+    }
+}
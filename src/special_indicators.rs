@@ -8,12 +8,18 @@
 //! mentioned in metadata descriptors, rather than decoded as more generalized
 //! type and cast into custom type later on.
 use external_memory_tools::{AddressableBuffer, ExternalMemory};
-use frame_metadata::v14::SignedExtensionMetadata;
+use frame_metadata::v14::{PalletConstantMetadata, SignedExtensionMetadata};
 use scale_info::{form::PortableForm, Field, Path, Type, TypeDef, Variant};
 
-use crate::std::{borrow::ToOwned, string::String, vec::Vec};
+use crate::std::{
+    borrow::ToOwned,
+    boxed::Box,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    string::String,
+    vec::Vec,
+};
 
-use crate::cards::Info;
+use crate::cards::{CustomValue, Info};
 use crate::decoding_sci::pick_variant;
 use crate::traits::{AsMetadata, ResolveType};
 
@@ -31,6 +37,17 @@ pub const BALANCE_ID_SET: &[&str] = &[
     "T::Balance",
 ];
 
+/// [`PalletConstantMetadata`] `name` set indicating that the constant value
+/// *may* be currency-related.
+///
+/// If the value is unsigned integer, it will be considered currency.
+pub const BALANCE_CONSTANT_ID_SET: &[&str] = &[
+    "DepositBase",
+    "DepositFactor",
+    "ExistentialDeposit",
+    "MinimumBalance",
+];
+
 /// [`Field`] `name` set indicating the value *may* be nonce.
 ///
 /// If the value is unsigned integer, it will be considered nonce.
@@ -41,11 +58,31 @@ pub const NONCE_ID_SET: &[&str] = &["nonce"];
 /// If the value is unsigned integer, it will be considered spec version.
 pub const SPEC_VERSION_ID_SET: &[&str] = &["spec_version"];
 
+/// [`Field`] `name` set indicating the value *may* be transaction version.
+///
+/// If the value is unsigned integer, it will be considered tx version.
+pub const TX_VERSION_ID_SET: &[&str] = &["transaction_version"];
+
+/// [`Field`] `name` set indicating the value *may* be runtime implementation
+/// version.
+///
+/// Is used only when decoding runtime version, no specialty is attributed.
+pub const IMPL_VERSION_ID_SET: &[&str] = &["impl_version"];
+
+/// [`Field`] `name` set indicating the value *may* be runtime state version.
+///
+/// Is used only when decoding runtime version, no specialty is attributed.
+pub const STATE_VERSION_ID_SET: &[&str] = &["state_version"];
+
 /// [`Field`] `name` set indicating the value *may* be metadata spec name.
 ///
 /// If the value is `str`, it will be considered spec name.
 pub const SPEC_NAME_ID_SET: &[&str] = &["spec_name"];
 
+/// [`Type`]-associated [`Path`] `ident` for Ethereum-style
+/// [`AccountId20`](crate::AccountId20).
+pub const ACCOUNT_ID20: &str = "AccountId20";
+
 /// [`Type`]-associated [`Path`] `ident` for
 /// [`sp_core::crypto::AccountId32`](https://docs.rs/sp-core/latest/sp_core/crypto/struct.AccountId32.html).
 pub const ACCOUNT_ID32: &str = "AccountId32";
@@ -63,7 +100,7 @@ pub const ERA: &str = "Era";
 pub const EVENT: &[&str] = &["Event", "RuntimeEvent"];
 
 /// [`Type`]-associated [`Path`] `ident` set for [primitive_types::H160].
-pub const H160: &[&str] = &["AccountId20", "H160"];
+pub const H160: &[&str] = &["H160"];
 
 /// [`Type`]-associated [`Path`] `ident` for [primitive_types::H256].
 pub const H256: &str = "H256";
@@ -168,7 +205,8 @@ pub const ENUM_INDEX_ENCODED_LEN: usize = 1;
 /// determines the card type.
 ///
 /// Is determined by propagating [`Hint`] from [`SignedExtensionMetadata`]
-/// identifier or from [`Field`] descriptor.
+/// identifier, from [`Field`] descriptor, or from [`PalletConstantMetadata`]
+/// name.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SpecialtyUnsignedInteger {
     /// Regular unsigned integer.
@@ -188,7 +226,8 @@ pub enum SpecialtyUnsignedInteger {
     /// Value is metadata spec version from signable transaction extensions.
     SpecVersion,
 
-    /// Value is tx version from signable transaction extensions.
+    /// Value is tx version from signable transaction extensions or from
+    /// runtime version.
     TxVersion,
 }
 
@@ -216,8 +255,8 @@ pub enum SpecialtyH256 {
 /// Propagates during the decoding into compacts and single-field structs and
 /// gets used only if suitable type is encountered.
 ///
-/// `Hint` can originate from [`SignedExtensionMetadata`] identifier or from
-/// [`Field`] descriptor.
+/// `Hint` can originate from [`SignedExtensionMetadata`] identifier, from
+/// [`Field`] descriptor, or from [`PalletConstantMetadata`] name.
 ///
 /// If non-`None` `Hint` is encountered during decoding, it does not get updated
 /// until the extension or the field are decoded through.
@@ -234,6 +273,7 @@ pub enum Hint {
     FieldNonce,
     FieldSpecName,
     FieldSpecVersion,
+    FieldTxVersion,
 }
 
 impl Hint {
@@ -245,6 +285,7 @@ impl Hint {
                 a if NONCE_ID_SET.contains(&a) => Self::FieldNonce,
                 a if SPEC_VERSION_ID_SET.contains(&a) => Self::FieldSpecVersion,
                 a if SPEC_NAME_ID_SET.contains(&a) => Self::FieldSpecName,
+                a if TX_VERSION_ID_SET.contains(&a) => Self::FieldTxVersion,
                 _ => Self::None,
             },
             None => Self::None,
@@ -273,6 +314,14 @@ impl Hint {
         }
     }
 
+    /// `Hint` for pallet constant.
+    pub fn from_constant_meta(constant_meta: &PalletConstantMetadata<PortableForm>) -> Self {
+        match constant_meta.name.as_str() {
+            a if BALANCE_CONSTANT_ID_SET.contains(&a) => Self::FieldBalance,
+            _ => Self::None,
+        }
+    }
+
     /// `Hint` from [`Path`].
     ///
     /// Can appear only when decoding tuples, as each tuple field has a [`Type`]
@@ -294,7 +343,7 @@ impl Hint {
             Hint::CheckSpecVersion | Hint::FieldSpecVersion => {
                 SpecialtyUnsignedInteger::SpecVersion
             }
-            Hint::CheckTxVersion => SpecialtyUnsignedInteger::TxVersion,
+            Hint::CheckTxVersion | Hint::FieldTxVersion => SpecialtyUnsignedInteger::TxVersion,
             Hint::CheckNonce | Hint::FieldNonce => SpecialtyUnsignedInteger::Nonce,
             Hint::ChargeTransactionPayment => SpecialtyUnsignedInteger::Tip,
             Hint::FieldBalance => SpecialtyUnsignedInteger::Balance,
@@ -329,6 +378,7 @@ impl Hint {
 /// specialty that causes parser action.
 pub enum SpecialtyTypeHinted {
     None,
+    AccountId20,
     AccountId32,
     Era,
     H160,
@@ -366,6 +416,7 @@ impl SpecialtyTypeHinted {
     pub fn from_type(ty: &Type<PortableForm>) -> Self {
         match ty.path.ident() {
             Some(a) => match a.as_str() {
+                ACCOUNT_ID20 => Self::AccountId20,
                 ACCOUNT_ID32 => Self::AccountId32,
                 a if CALL.contains(&a) => Self::PalletSpecific(PalletSpecificItem::Call),
                 ERA => Self::Era,
@@ -422,12 +473,65 @@ impl SpecialtyTypeHinted {
     }
 }
 
+/// Special type registered by user, attached to matching type [`Path`]s with
+/// [`CustomTypes::register`].
+///
+/// Registered custom types are checked before the built-in specialties, both
+/// in decoding and in resolving of set element types. Matched value is decoded
+/// into [`ParsedData::Custom`](crate::cards::ParsedData::Custom).
+pub trait CustomType {
+    /// Type with given [`Path`] is handled by this custom type.
+    fn matches(&self, path: &Path<PortableForm>) -> bool;
+
+    /// Decode complete encoded value of the type into [`CustomValue`].
+    ///
+    /// Encoded value length is found by skipping the value as a regular type.
+    /// If `None` is returned, the value is decoded as a regular type.
+    fn decode(&self, path: &Path<PortableForm>, encoded: &[u8]) -> Option<CustomValue>;
+}
+
+/// Set of registered [`CustomType`]s.
+#[derive(Default)]
+pub struct CustomTypes {
+    custom_types: Vec<Box<dyn CustomType>>,
+}
+
+impl Debug for CustomTypes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("CustomTypes")
+            .field("registered", &self.custom_types.len())
+            .finish()
+    }
+}
+
+impl CustomTypes {
+    /// New empty set, only built-in specialties are used.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add custom type. For paths matched by several custom types, the
+    /// earliest registered one is used.
+    pub fn register(&mut self, custom_type: Box<dyn CustomType>) {
+        self.custom_types.push(custom_type)
+    }
+
+    /// Custom type matching the [`Path`], if any.
+    pub fn find(&self, path: &Path<PortableForm>) -> Option<&dyn CustomType> {
+        self.custom_types
+            .iter()
+            .find(|custom_type| custom_type.matches(path))
+            .map(|custom_type| custom_type.as_ref())
+    }
+}
+
 /// [`Type`] specialty, based on [`Path`] and type internal structure.
 ///
 /// Does not propagate. If found, causes parser to decode through special route.
 /// If decoding through special route fails, it is considered parser error.
 pub enum SpecialtyTypeChecked {
     None,
+    AccountId20,
     AccountId32,
     Era,
     H160,
@@ -473,6 +577,7 @@ impl SpecialtyTypeChecked {
     {
         match SpecialtyTypeHinted::from_type(ty) {
             SpecialtyTypeHinted::None => Self::None,
+            SpecialtyTypeHinted::AccountId20 => Self::AccountId20,
             SpecialtyTypeHinted::AccountId32 => Self::AccountId32,
             SpecialtyTypeHinted::Era => Self::Era,
             SpecialtyTypeHinted::H160 => Self::H160,
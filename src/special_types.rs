@@ -1,9 +1,10 @@
 //! Decoders for special types: primitives, `PerThing` items, well-known arrays.
 use external_memory_tools::{AddressableBuffer, ExternalMemory};
 use num_bigint::{BigInt, BigUint};
-use parity_scale_codec::{DecodeAll, HasCompact};
+use parity_scale_codec::{Decode, DecodeAll, Encode, HasCompact};
 use primitive_types::{H160, H256, H512};
 use sp_arithmetic::{PerU16, Perbill, Percent, Permill, Perquintill};
+use sp_core_hashing::keccak_256;
 
 #[cfg(not(feature = "std"))]
 use crate::additional_types::Era;
@@ -15,7 +16,7 @@ use core::{convert::TryInto, mem::size_of};
 #[cfg(any(feature = "std", test))]
 use std::{convert::TryInto, mem::size_of};
 
-use crate::std::{borrow::ToOwned, vec::Vec};
+use crate::std::{borrow::ToOwned, string::String, vec::Vec};
 
 #[cfg(not(feature = "std"))]
 use crate::additional_types::{
@@ -24,7 +25,7 @@ use crate::additional_types::{
 };
 #[cfg(feature = "std")]
 use sp_core::{
-    crypto::{AccountId32, ByteArray},
+    crypto::AccountId32,
     ecdsa::{Public as PublicEcdsa, Signature as SignatureEcdsa},
     ed25519::{Public as PublicEd25519, Signature as SignatureEd25519},
     sr25519::{Public as PublicSr25519, Signature as SignatureSr25519},
@@ -195,10 +196,22 @@ macro_rules! impl_stable_length_array_closed {
     }
 }
 
-impl_stable_length_array_closed!(AccountId32, Self::LEN, new);
-impl_stable_length_array_closed!(PublicEd25519, Self::LEN, from_raw);
-impl_stable_length_array_closed!(PublicSr25519, Self::LEN, from_raw);
-impl_stable_length_array_closed!(PublicEcdsa, Self::LEN, from_raw);
+/// Known size for `sp_core::crypto::AccountId32`.
+pub const ACCOUNT_ID_32_LEN: usize = 32;
+
+/// Known size for `sp_core::ed25519::Public`.
+pub const PUBLIC_LEN_ED25519: usize = 32;
+
+/// Known size for `sp_core::sr25519::Public`.
+pub const PUBLIC_LEN_SR25519: usize = 32;
+
+/// Known size for `sp_core::ecdsa::Public`.
+pub const PUBLIC_LEN_ECDSA: usize = 33;
+
+impl_stable_length_array_closed!(AccountId32, ACCOUNT_ID_32_LEN, new);
+impl_stable_length_array_closed!(PublicEd25519, PUBLIC_LEN_ED25519, from_raw);
+impl_stable_length_array_closed!(PublicSr25519, PUBLIC_LEN_SR25519, from_raw);
+impl_stable_length_array_closed!(PublicEcdsa, PUBLIC_LEN_ECDSA, from_raw);
 
 /// Known size for `sp_core::ed25519::Signature`.
 pub const SIGNATURE_LEN_ED25519: usize = 64;
@@ -236,7 +249,46 @@ macro_rules! impl_stable_length_array_open {
     }
 }
 
-impl_stable_length_array_open!(H160, H256, H512);
+/// Known size for `AccountId20`.
+pub const ACCOUNT_ID20_LEN: usize = 20;
+
+/// Ethereum-style account id, such as `fp_account::AccountId20` used in
+/// EVM-compatible chains.
+#[derive(Clone, Copy, Debug, Decode, Encode, Eq, PartialEq)]
+pub struct AccountId20(pub [u8; ACCOUNT_ID20_LEN]);
+
+impl AccountId20 {
+    pub const fn len_bytes() -> usize {
+        ACCOUNT_ID20_LEN
+    }
+
+    /// Address with [EIP-55](https://eips.ethereum.org/EIPS/eip-55)
+    /// mixed-case checksum, `0x`-prefixed.
+    ///
+    /// Hex digit is uppercase if the corresponding nibble of `keccak256`
+    /// hash of lowercase hex address is `8` or above.
+    pub fn to_checksummed(&self) -> String {
+        let lowercase = hex::encode(self.0);
+        let hash = keccak_256(lowercase.as_bytes());
+        let mut out = String::with_capacity(2 + lowercase.len());
+        out.push_str("0x");
+        for (i, digit) in lowercase.chars().enumerate() {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                out.push(digit.to_ascii_uppercase())
+            } else {
+                out.push(digit)
+            }
+        }
+        out
+    }
+}
+
+impl_stable_length_array_open!(AccountId20, H160, H256, H512);
 
 #[cfg(not(feature = "std"))]
 impl_stable_length_array_open!(
@@ -363,6 +415,7 @@ impl_block_compact!(i64, PrimitiveI64);
 impl_block_compact!(i128, PrimitiveI128);
 impl_block_compact!(BigInt, PrimitiveI256);
 impl_block_compact!(BigUint, PrimitiveU256);
+impl_block_compact!(AccountId20, AccountId20);
 impl_block_compact!(AccountId32, Id);
 impl_block_compact!(PublicEd25519, PublicEd25519);
 impl_block_compact!(PublicSr25519, PublicSr25519);
@@ -1,35 +1,313 @@
 use crate::std::{
     any::TypeId,
+    borrow::ToOwned,
+    boxed::Box,
     string::{String, ToString},
     vec::Vec,
 };
 use external_memory_tools::BufferError;
-use frame_metadata::v14::{RuntimeMetadataV14, StorageEntryMetadata};
-use parity_scale_codec::Decode;
+use frame_metadata::{
+    v14::{RuntimeMetadataV14, StorageEntryMetadata},
+    v15::{
+        CustomMetadata, ExtrinsicMetadata, OuterEnums, PalletMetadata, RuntimeApiMetadata,
+        RuntimeApiMethodMetadata, RuntimeApiMethodParamMetadata, RuntimeMetadataV15,
+        SignedExtensionMetadata,
+    },
+    RuntimeMetadata, RuntimeMetadataPrefixed, META_RESERVED,
+};
+use parity_scale_codec::{Compact, Encode};
 use primitive_types::H256;
 use scale_info::{
-    form::PortableForm, interner::UntrackedSymbol, IntoPortable, Path, Registry, TypeDef,
+    form::PortableForm, interner::UntrackedSymbol, Field, IntoPortable, Path, PortableRegistry,
+    PortableType, Registry, Type, TypeDef, TypeDefArray, TypeDefComposite, TypeDefPrimitive,
+    TypeDefSequence, TypeDefVariant, Variant,
 };
 #[cfg(feature = "std")]
-use sp_core::{crypto::AccountId32, sr25519::Signature as SignatureSr25519};
+use sp_core::{
+    crypto::{AccountId32, Pair},
+    ecdsa, sr25519,
+    sr25519::Signature as SignatureSr25519,
+};
+use sp_core_hashing::blake2_256;
 #[cfg(feature = "std")]
 use sp_runtime::generic::Era;
 
+#[cfg(not(feature = "std"))]
+use crate::additional_types::Era;
+
+#[cfg(feature = "std")]
+use crate::block::{decode_block, decode_block_body};
+use crate::call_tree::{BatchMode, CallWrapper};
 use crate::cards::{
-    ExtendedData, FieldData, Info, ParsedData, Sequence, SequenceData, SequenceRawData, VariantData,
+    CustomValue, ExtendedData, ExtensionCards, FieldData, Info, ParsedData, ParserCard, Sequence,
+    SequenceData, SequenceRawData, VariantData,
+};
+use crate::constants::{decode_constant, decode_pallet_constants};
+use crate::decoding_sci::{
+    decode_as_call, decode_as_call_unmarked, extrinsic_type_params, CALL_INDICATOR,
+};
+use crate::encoding::{encode_as_type, encode_extended_data_as_type};
+#[cfg(feature = "json")]
+use crate::error::JsonError;
+#[cfg(feature = "std")]
+use crate::error::{BlockError, SignatureError, UncheckedExtrinsicError};
+use crate::error::{
+    ConstantError, EncodeError, ExtensionsError, ExtractError, MetadataPrefixedError, ParserError,
+    RuntimeApiError, SignableError, TypeCrumb,
+};
+use crate::events::{decode_events, events_for_extrinsic, group_events_by_extrinsic, Phase};
+use crate::extract::extract_field;
+#[cfg(feature = "std")]
+use crate::header::{
+    decode_header, BabePreDigest, DigestItem, EngineId, PreRuntimeData, AURA_ENGINE_ID,
+    BABE_ENGINE_ID,
+};
+#[cfg(feature = "json")]
+use crate::json::encode_json_as_type;
+#[cfg(feature = "std")]
+use crate::mortality::{Mortality, MortalityWindow};
+use crate::pallet_error::resolve_module_errors;
+use crate::runtime_api::{
+    decode_runtime_api_call, decode_runtime_api_output, split_state_call_method,
+};
+use crate::skipping::{fixed_encoded_len, skip_as_type_at_position};
+use crate::special_indicators::{
+    CustomType, CustomTypes, Hint, PalletSpecificItem, SpecialtyStr, SpecialtyUnsignedInteger,
 };
-use crate::error::{ParserError, SignableError};
-use crate::special_indicators::SpecialtyUnsignedInteger;
+use crate::special_types::AccountId20;
 use crate::storage_data::{decode_as_storage_entry, KeyData, KeyPart};
-use crate::traits::AsMetadata;
+use crate::traits::{
+    AsMetadata, MetadataVersioned, MetadataWithCustomTypes, RegistryWithCustomTypes,
+};
 #[cfg(feature = "std")]
-use crate::unchecked_extrinsic::{decode_as_unchecked_extrinsic, UncheckedExtrinsic};
-use crate::{decode_all_as_type, parse_transaction, parse_transaction_unmarked, ShortSpecs};
+use crate::unchecked_extrinsic::{
+    decode_as_unchecked_extrinsic, AdditionalSignedInfo, ExtensionAdditionalSigned,
+    UncheckedExtrinsic,
+};
+use crate::visitor::Visitor;
+use crate::{
+    decode_all_as_type, decode_as_type_at_position, decode_as_type_at_position_tolerant,
+    decode_extensions_with_handlers, parse_transaction, parse_transaction_unmarked,
+    visit_all_as_type, ExtensionHandler, ExtensionHandlers, MarkedData, ShortSpecs,
+};
 
 fn metadata(filename: &str) -> RuntimeMetadataV14 {
     let metadata_hex = std::fs::read_to_string(filename).unwrap();
-    let metadata_vec = hex::decode(metadata_hex.trim()).unwrap()[5..].to_vec();
-    RuntimeMetadataV14::decode(&mut &metadata_vec[..]).unwrap()
+    match MetadataVersioned::from_prefixed_hex(&metadata_hex).unwrap() {
+        MetadataVersioned::V14(meta_v14) => meta_v14,
+        MetadataVersioned::V15(_) => panic!("Test metadata is expected to be `V14`."),
+    }
+}
+
+/// `V15` metadata assembled from `V14` metadata, with no runtime apis and no
+/// custom metadata.
+///
+/// Outer error enum is not in `V14` metadata types registry, and is added
+/// here.
+fn metadata_v15(filename: &str) -> RuntimeMetadataV15 {
+    let mut meta_v14 = metadata(filename);
+    let error_enum_ty = meta_v14.types.types.len() as u32;
+    let error_variants = meta_v14
+        .pallets
+        .iter()
+        .filter_map(|pallet| {
+            pallet.error.as_ref().map(|error| Variant {
+                name: pallet.name.to_string(),
+                fields: vec![Field {
+                    name: None,
+                    ty: error.ty,
+                    type_name: None,
+                    docs: Vec::new(),
+                }],
+                index: pallet.index,
+                docs: Vec::new(),
+            })
+        })
+        .collect();
+    meta_v14.types.types.push(PortableType {
+        id: error_enum_ty,
+        ty: Type {
+            path: Path {
+                segments: vec!["runtime".to_string(), "RuntimeError".to_string()],
+            },
+            type_params: Vec::new(),
+            type_def: TypeDef::Variant(TypeDefVariant {
+                variants: error_variants,
+            }),
+            docs: Vec::new(),
+        },
+    });
+    let extrinsic_ty = meta_v14.types.resolve(meta_v14.extrinsic.ty.id).unwrap();
+    let mut address_ty = None;
+    let mut call_ty = None;
+    let mut signature_ty = None;
+    let mut extra_ty = None;
+    for param in extrinsic_ty.type_params.iter() {
+        match param.name.as_str() {
+            "Address" => address_ty = param.ty,
+            "Call" => call_ty = param.ty,
+            "Signature" => signature_ty = param.ty,
+            "Extra" => extra_ty = param.ty,
+            _ => (),
+        }
+    }
+    let pallets = meta_v14
+        .pallets
+        .into_iter()
+        .map(|pallet| PalletMetadata {
+            name: pallet.name,
+            storage: pallet.storage,
+            calls: pallet.calls,
+            event: pallet.event,
+            constants: pallet.constants,
+            error: pallet.error,
+            index: pallet.index,
+            docs: Vec::new(),
+        })
+        .collect();
+    let signed_extensions = meta_v14
+        .extrinsic
+        .signed_extensions
+        .into_iter()
+        .map(|signed_extension| SignedExtensionMetadata {
+            identifier: signed_extension.identifier,
+            ty: signed_extension.ty,
+            additional_signed: signed_extension.additional_signed,
+        })
+        .collect();
+    RuntimeMetadataV15 {
+        types: meta_v14.types,
+        pallets,
+        extrinsic: ExtrinsicMetadata {
+            version: meta_v14.extrinsic.version,
+            address_ty: address_ty.unwrap(),
+            call_ty: call_ty.unwrap(),
+            signature_ty: signature_ty.unwrap(),
+            extra_ty: extra_ty.unwrap(),
+            signed_extensions,
+        },
+        ty: meta_v14.ty,
+        apis: Vec::new(),
+        outer_enums: OuterEnums {
+            call_enum_ty: call_ty.unwrap(),
+            event_enum_ty: meta_v14.ty,
+            error_enum_ty: error_enum_ty.into(),
+        },
+        custom: CustomMetadata {
+            map: Default::default(),
+        },
+    }
+}
+
+/// Find type id in metadata types registry by type `Path` ident.
+fn ty_id_by_ident(types: &PortableRegistry, ident: &str) -> u32 {
+    types
+        .types
+        .iter()
+        .find(|portable_type| portable_type.ty.path.ident().as_deref() == Some(ident))
+        .unwrap()
+        .id
+}
+
+/// Find type id in metadata types registry for a primitive type.
+fn ty_id_primitive(types: &PortableRegistry, primitive: TypeDefPrimitive) -> u32 {
+    types
+        .types
+        .iter()
+        .find(|portable_type| portable_type.ty.type_def == TypeDef::Primitive(primitive.clone()))
+        .unwrap()
+        .id
+}
+
+/// `V15` metadata assembled from `V14` metadata, with `AccountNonceApi` and
+/// `TransactionPaymentApi` runtime apis.
+///
+/// `RuntimeDispatchInfo` type is not in `V14` metadata types registry, and is
+/// added here.
+fn metadata_v15_with_apis(filename: &str) -> RuntimeMetadataV15 {
+    let mut meta_v15 = metadata_v15(filename);
+    let account_id32_ty = ty_id_by_ident(&meta_v15.types, "AccountId32");
+    let dispatch_class_ty = ty_id_by_ident(&meta_v15.types, "DispatchClass");
+    let u32_ty = ty_id_primitive(&meta_v15.types, TypeDefPrimitive::U32);
+    let u64_ty = ty_id_primitive(&meta_v15.types, TypeDefPrimitive::U64);
+    let u128_ty = ty_id_primitive(&meta_v15.types, TypeDefPrimitive::U128);
+    let unchecked_extrinsic_ty = <RuntimeMetadataV15 as AsMetadata<()>>::extrinsic(&meta_v15)
+        .unwrap()
+        .ty;
+
+    let runtime_dispatch_info_ty = meta_v15.types.types.len() as u32;
+    meta_v15.types.types.push(PortableType {
+        id: runtime_dispatch_info_ty,
+        ty: Type {
+            path: Path {
+                segments: vec![
+                    "pallet_transaction_payment".to_string(),
+                    "types".to_string(),
+                    "RuntimeDispatchInfo".to_string(),
+                ],
+            },
+            type_params: Vec::new(),
+            type_def: TypeDef::Composite(TypeDefComposite {
+                fields: vec![
+                    Field {
+                        name: Some("weight".to_string()),
+                        ty: u64_ty.into(),
+                        type_name: Some("Weight".to_string()),
+                        docs: Vec::new(),
+                    },
+                    Field {
+                        name: Some("class".to_string()),
+                        ty: dispatch_class_ty.into(),
+                        type_name: Some("DispatchClass".to_string()),
+                        docs: Vec::new(),
+                    },
+                    Field {
+                        name: Some("partial_fee".to_string()),
+                        ty: u128_ty.into(),
+                        type_name: Some("Balance".to_string()),
+                        docs: Vec::new(),
+                    },
+                ],
+            }),
+            docs: Vec::new(),
+        },
+    });
+
+    meta_v15.apis = vec![
+        RuntimeApiMetadata {
+            name: "AccountNonceApi".to_string(),
+            methods: vec![RuntimeApiMethodMetadata {
+                name: "account_nonce".to_string(),
+                inputs: vec![RuntimeApiMethodParamMetadata {
+                    name: "account".to_string(),
+                    ty: account_id32_ty.into(),
+                }],
+                output: u32_ty.into(),
+                docs: vec![" Get current account nonce of given `AccountId`.".to_string()],
+            }],
+            docs: vec![" The API to query account nonce.".to_string()],
+        },
+        RuntimeApiMetadata {
+            name: "TransactionPaymentApi".to_string(),
+            methods: vec![RuntimeApiMethodMetadata {
+                name: "query_info".to_string(),
+                inputs: vec![
+                    RuntimeApiMethodParamMetadata {
+                        name: "uxt".to_string(),
+                        ty: unchecked_extrinsic_ty,
+                    },
+                    RuntimeApiMethodParamMetadata {
+                        name: "len".to_string(),
+                        ty: u32_ty.into(),
+                    },
+                ],
+                output: runtime_dispatch_info_ty.into(),
+                docs: Vec::new(),
+            }],
+            docs: Vec::new(),
+        },
+    ];
+    meta_v15
 }
 
 fn genesis_hash_acala() -> H256 {
@@ -117,6 +395,25 @@ fn specs_westend() -> ShortSpecs {
     }
 }
 
+/// Print extension cards in the order of the encoded data: `ty` parts of all
+/// extensions first, then `additional_signed` parts.
+fn show_extensions(extensions: &[ExtensionCards]) -> String {
+    format!(
+        "\n{}\n",
+        extensions
+            .iter()
+            .flat_map(|extension| extension.extra.iter())
+            .chain(
+                extensions
+                    .iter()
+                    .flat_map(|extension| extension.additional_signed.iter())
+            )
+            .map(|card| card.show())
+            .collect::<Vec<String>>()
+            .join("\n")
+    )
+}
+
 fn system_digest_ty(meta_v14: &RuntimeMetadataV14) -> UntrackedSymbol<TypeId> {
     let mut ty = None;
     for pallet in meta_v14.pallets.iter() {
@@ -217,15 +514,7 @@ Pallet: Utility
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 5, period: 64
 Nonce: 2
@@ -238,6 +527,151 @@ Block Hash: 5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff
     assert_eq!(extensions_known, extensions_printed);
 }
 
+#[cfg(not(feature = "std"))]
+#[test]
+fn era_encode_1() {
+    assert_eq!(Era::Mortal(64, 44).encode(), [0xc5, 0x02]);
+
+    // Period not produced by `Era::mortal` is rounded to a power of two.
+    assert_eq!(Era::Mortal(1, 0).encode(), Era::Mortal(4, 0).encode());
+    assert_eq!(Era::Mortal(50, 44).encode(), Era::Mortal(64, 44).encode());
+}
+
+#[test]
+fn tr_1_extensions_grouped() {
+    let metadata_westend = metadata("for_tests/westend9111");
+
+    // Same transaction as in `tr_1` test.
+    let data = hex::decode("4d0210020806000046ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a07001b2c3ef70006050c0008264834504a64ace1373f0c8ed5d57381ddf54a2f67a318fa42b1352681606d00aebb0211dbb07b4d335a657257b8ac5e53794c901e4f616d4a254f2490c43934009ae581fef1fc06828723715731adcf810e42ce4dadad629b1b7fa5c3c144a81d550008009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff").unwrap();
+
+    let reply = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend,
+        Some(genesis_hash_westend()),
+    )
+    .unwrap()
+    .card(&specs_westend(), "westend");
+
+    let extensions_printed = reply
+        .extensions
+        .iter()
+        .map(|extension| {
+            format!(
+                "{}: {} / {}",
+                extension.identifier,
+                extension
+                    .extra
+                    .iter()
+                    .map(|card| card.show())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                extension
+                    .additional_signed
+                    .iter()
+                    .map(|card| card.show())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        })
+        .collect::<Vec<String>>();
+    let extensions_known = [
+        "CheckSpecVersion:  / Chain: westend9111",
+        "CheckTxVersion:  / Tx Version: 7",
+        "CheckGenesis:  / Genesis Hash: e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e",
+        "CheckMortality: Era: Mortal, phase: 5, period: 64 / Block Hash: 5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff",
+        "CheckNonce: Nonce: 2 / ",
+        "CheckWeight:  / ",
+        "ChargeTransactionPayment: Tip: 0 pWND / ",
+    ];
+    assert_eq!(extensions_printed, extensions_known);
+}
+
+#[test]
+fn tr_1_reference_block() {
+    let metadata_westend = metadata("for_tests/westend9111");
+
+    // Same transaction as in `tr_1` test.
+    let data = hex::decode("4d0210020806000046ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a07001b2c3ef70006050c0008264834504a64ace1373f0c8ed5d57381ddf54a2f67a318fa42b1352681606d00aebb0211dbb07b4d335a657257b8ac5e53794c901e4f616d4a254f2490c43934009ae581fef1fc06828723715731adcf810e42ce4dadad629b1b7fa5c3c144a81d550008009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff").unwrap();
+
+    let mut reply = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend,
+        Some(genesis_hash_westend()),
+    )
+    .unwrap()
+    .card(&specs_westend(), "westend");
+    reply.set_reference_block(10_000_000);
+
+    let extensions_printed = show_extensions(&reply.extensions);
+    let extensions_known = "
+Era: Mortal, phase: 5, period: 64, valid from #9999941 to #10000005
+Nonce: 2
+Tip: 0 pWND
+Chain: westend9111
+Tx Version: 7
+Genesis Hash: e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e
+Block Hash: 5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff
+";
+    assert_eq!(extensions_known, extensions_printed);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn mortality_1() {
+    assert_eq!(
+        Mortality::from_era(&Era::Immortal, Some(10_000_000)),
+        Mortality::Immortal
+    );
+    assert_eq!(
+        Mortality::from_era(&Era::Mortal(64, 5), None),
+        Mortality::Mortal {
+            period: 64,
+            phase: 5,
+            window: None
+        }
+    );
+    assert_eq!(
+        Mortality::from_era(&Era::Mortal(64, 0), Some(1234567)),
+        Mortality::Mortal {
+            period: 64,
+            phase: 0,
+            window: Some(MortalityWindow {
+                birth: 1234560,
+                death: 1234624,
+                remaining: 57,
+            })
+        }
+    );
+
+    // Same birth and death as in `sp_runtime`.
+    for (period, phase) in [(4, 0), (64, 5), (2048, 1764), (65536, 12288)] {
+        let era = Era::mortal(period, phase);
+        let (period, phase) = match era {
+            Era::Mortal(period, phase) => (period, phase),
+            Era::Immortal => panic!("Expected mortal era!"),
+        };
+        for reference_block in [0, 3, 1234567, 10_000_000] {
+            let window = MortalityWindow::new(period, phase, reference_block).unwrap();
+            assert_eq!(window.birth, era.birth(reference_block));
+            assert_eq!(window.death, era.death(reference_block));
+        }
+    }
+
+    // Window is not calculated for zero period or for overflowing death block.
+    assert_eq!(MortalityWindow::new(0, 0, 1234567), None);
+    assert_eq!(MortalityWindow::new(64, 5, u64::MAX), None);
+    assert_eq!(
+        Mortality::from_era(&Era::Mortal(64, 5), Some(u64::MAX)),
+        Mortality::Mortal {
+            period: 64,
+            phase: 5,
+            window: None
+        }
+    );
+}
+
 #[test]
 fn tr_2() {
     let metadata_westend = metadata("for_tests/westend9111");
@@ -394,15 +828,7 @@ Pallet: Balances
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 61, period: 64
 Nonce: 261
@@ -453,15 +879,7 @@ Pallet: System
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 36, period: 64
 Nonce: 11
@@ -515,15 +933,7 @@ Pallet: Balances
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 18, period: 32
 Nonce: 0
@@ -666,7 +1076,7 @@ fn storage_2_spoiled_digest() {
     )
     .unwrap_err();
     let reply_known = ParserError::CyclicMetadata { id: 11 };
-    assert_eq!(reply_known, reply);
+    assert_eq!(&reply_known, reply.without_trail());
 }
 
 #[test]
@@ -837,9 +1247,54 @@ fn parser_error_1() {
     assert_eq!(error_known, error);
 }
 
+#[test]
+fn parser_error_tx_version() {
+    // Transaction with tx version `5`, while `westend9111` metadata has tx
+    // version `7`.
+    let data = hex::decode("a40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
+    let error = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata("for_tests/westend9111"),
+        Some(genesis_hash_westend()),
+    )
+    .unwrap_err();
+    let error_known = SignableError::WrongTxVersion {
+        as_decoded: String::from("5"),
+        in_metadata: String::from("7"),
+    };
+    assert_eq!(error_known, error);
+}
+
+#[test]
+fn spec_name_version_1() {
+    let spec_name_version = <RuntimeMetadataV14 as AsMetadata<()>>::spec_name_version(&metadata(
+        "for_tests/westend9111",
+    ))
+    .unwrap();
+    assert_eq!(spec_name_version.printed_spec_version, "9111");
+    assert_eq!(spec_name_version.spec_name, "westend");
+    assert_eq!(
+        spec_name_version.printed_tx_version,
+        Some(String::from("7"))
+    );
+    assert_eq!(spec_name_version.impl_version, Some(0));
+    assert_eq!(spec_name_version.state_version, None);
+
+    let spec_name_version = <RuntimeMetadataV14 as AsMetadata<()>>::spec_name_version(&metadata(
+        "for_tests/polkadot9430",
+    ))
+    .unwrap();
+    assert_eq!(
+        spec_name_version.printed_tx_version,
+        Some(String::from("24"))
+    );
+    assert_eq!(spec_name_version.state_version, Some(0));
+}
+
 #[test]
 fn parser_error_2() {
-    let data = hex::decode("a40403048eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
+    let data = hex::decode("a40403048eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
     let parsed = parse_transaction(
         &data.as_ref(),
         &mut (),
@@ -854,7 +1309,7 @@ fn parser_error_2() {
 
 #[test]
 fn parser_error3() {
-    let data = hex::decode("a40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f3300").unwrap();
+    let data = hex::decode("a40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f3300").unwrap();
     let signable_error = parse_transaction(
         &data.as_ref(),
         &mut (),
@@ -868,7 +1323,7 @@ fn parser_error3() {
 
 #[test]
 fn parser_error_4() {
-    let data = hex::decode("a40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a481700e8764817b501b8009723000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
+    let data = hex::decode("a40403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a481700e8764817b501b8009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
     let parsed = parse_transaction(
         &data.as_ref(),
         &mut (),
@@ -877,13 +1332,23 @@ fn parser_error_4() {
     )
     .unwrap();
     let call_error = parsed.call_result.unwrap_err();
-    let call_error_known = SignableError::Parsing(ParserError::NoCompact { position: 36 });
-    assert_eq!(call_error_known, call_error);
+    if let SignableError::Parsing(parser_error) = call_error {
+        assert_eq!(
+            parser_error.without_trail(),
+            &ParserError::NoCompact { position: 36 }
+        );
+        assert_eq!(
+            parser_error.trail().unwrap().to_string(),
+            "Call > Balances::transfer_keep_alive > value"
+        );
+    } else {
+        panic!("Expected parsing error.")
+    }
 }
 
 #[test]
 fn parser_error_5() {
-    let data = hex::decode("a40403068eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
+    let data = hex::decode("a40403068eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
     let parsed = parse_transaction(
         &data.as_ref(),
         &mut (),
@@ -892,14 +1357,23 @@ fn parser_error_5() {
     )
     .unwrap();
     let call_error = parsed.call_result.unwrap_err();
-    let call_error_known =
-        SignableError::Parsing(ParserError::UnexpectedEnumVariant { position: 3 });
-    assert_eq!(call_error_known, call_error);
+    if let SignableError::Parsing(parser_error) = call_error {
+        assert_eq!(
+            parser_error.without_trail(),
+            &ParserError::UnexpectedEnumVariant { position: 3 }
+        );
+        assert_eq!(
+            parser_error.to_string(),
+            "Encountered unexpected enum variant at position 3. Type trail: Call > Balances::transfer_keep_alive > dest > MultiAddress."
+        );
+    } else {
+        panic!("Expected parsing error.")
+    }
 }
 
 #[test]
 fn parser_error_6() {
-    let data = hex::decode("a40403028eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000005000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
+    let data = hex::decode("a40403028eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480700e8764817b501b8009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e538a7d7a0ac17eb6dd004578cb8e238c384a10f57c999a3fa1200409cd9b3f33").unwrap();
     let parsed = parse_transaction(
         &data.as_ref(),
         &mut (),
@@ -916,11 +1390,23 @@ fn parser_error_6() {
     //  i.e. `88157155` `u8` elements are expected to be found).  When all call
     // data is exhausted, i.e. after element `42` of the data, no new `u8`
     // element could be found, therefore the error.
-    let call_error_known = SignableError::Parsing(ParserError::Buffer(BufferError::DataTooShort {
-        position: 42,
-        minimal_length: 1,
-    }));
-    assert_eq!(call_error_known, call_error);
+    //
+    // Element `34` of the vector is the first one not found.
+    if let SignableError::Parsing(parser_error) = call_error {
+        assert_eq!(
+            parser_error.without_trail(),
+            &ParserError::Buffer(BufferError::DataTooShort {
+                position: 42,
+                minimal_length: 1,
+            })
+        );
+        assert_eq!(
+            parser_error.trail().unwrap().to_string(),
+            "Call > Balances::transfer_keep_alive > dest > MultiAddress > Raw.0[34]"
+        );
+    } else {
+        panic!("Expected parsing error.")
+    }
 }
 
 #[cfg(feature = "std")]
@@ -935,6 +1421,7 @@ fn unchecked_extrinsic_1() {
             signature,
             extra,
             call,
+            ..
         } => {
             let expected_address = ExtendedData {
                 data: ParsedData::Variant(VariantData {
@@ -1220,99 +1707,458 @@ fn unchecked_extrinsic_1() {
                 ];
             assert_eq!(expected_call_fields, call.0.fields);
         }
-        UncheckedExtrinsic::Unsigned { .. } => panic!("Expected signed extrinsic!"),
+        UncheckedExtrinsic::Unsigned { .. } | UncheckedExtrinsic::General { .. } => {
+            panic!("Expected signed extrinsic!")
+        }
     }
 }
 
+/// Signed westend unchecked extrinsic: immortal `transfer_keep_alive` with
+/// nonce 1 and no tip.
+#[cfg(feature = "std")]
+fn westend_signed_extrinsic(address: &[u8], signature: &[u8]) -> Vec<u8> {
+    let body = [
+        &[0x84][..],
+        address,
+        signature,
+        &hex::decode("000400").unwrap(),
+        &hex::decode(
+            "0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717",
+        )
+        .unwrap(),
+    ]
+    .concat();
+    [Compact(body.len() as u32).encode(), body].concat()
+}
+
+#[cfg(feature = "std")]
 #[test]
-fn tr_7() {
-    let data = hex::decode("a00a0304a84b841c4d9d1a179be03bb31131c14ebf6ce22233158139ae28a3dfaac5fe1560a5e9e05cd5038d248ed73e0d9808000003000000fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64cfc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c").unwrap();
+fn unchecked_extrinsic_signature_1() {
+    let metadata = metadata("for_tests/westend9111");
+    let genesis_hash = H256(
+        hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
+            .unwrap()
+            .try_into()
+            .unwrap(),
+    );
+    let additional_signed_info = AdditionalSignedInfo {
+        genesis_hash,
+        block_hash: None,
+        spec_version: 9111,
+        tx_version: 7,
+        other: Vec::new(),
+    };
 
-    let metadata_acala = metadata("for_tests/acala2200");
+    // Call, extra, spec version, tx version, genesis hash, and genesis hash
+    // again as the block hash for immortal transaction.
+    let payload = [
+        hex::decode(
+            "0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717000400",
+        )
+        .unwrap(),
+        9111u32.encode(),
+        7u32.encode(),
+        genesis_hash.0.to_vec(),
+        genesis_hash.0.to_vec(),
+    ]
+    .concat();
 
-    let reply = parse_transaction(
-        &data.as_ref(),
-        &mut (),
-        &metadata_acala,
-        Some(genesis_hash_acala()),
-    )
-    .unwrap()
-    .card(
-        &specs_acala(),
-        &<RuntimeMetadataV14 as AsMetadata<()>>::spec_name_version(&metadata_acala)
-            .unwrap()
-            .spec_name,
+    let pair_sr25519 = sr25519::Pair::from_string("//Alice", None).unwrap();
+    let data = westend_signed_extrinsic(
+        &[&[0x00][..], &pair_sr25519.public().0].concat(),
+        &[&[0x01][..], &pair_sr25519.sign(&payload).0].concat(),
+    );
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert!(parsed
+        .verify_signature(&data.as_ref(), &mut (), &metadata, &additional_signed_info)
+        .is_ok());
+    assert_eq!(
+        parsed.verify_signature(
+            &data.as_ref(),
+            &mut (),
+            &metadata,
+            &AdditionalSignedInfo {
+                tx_version: 8,
+                ..additional_signed_info.clone()
+            }
+        ),
+        Err(SignatureError::Invalid)
     );
 
-    let call_printed = format!(
-        "\n{}\n",
-        reply
-            .call_result
-            .unwrap()
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
+    // For ecdsa the account id is the hash of the public key.
+    let pair_ecdsa = ecdsa::Pair::from_string("//Alice", None).unwrap();
+    let data = westend_signed_extrinsic(
+        &[&[0x00][..], &blake2_256(&pair_ecdsa.public().0)].concat(),
+        &[&[0x02][..], &pair_ecdsa.sign(&payload).0].concat(),
     );
-    let call_known = "
-Pallet: Balances
-  Call: transfer_keep_alive
-    Field Name: dest
-      Enum
-        Enum Variant Name: Address20
-          Sequence u8: a84b841c4d9d1a179be03bb31131c14ebf6ce222
-    Field Name: value
-      Balance: 123456789012345.678901234567890123456789 TACA
-";
-    assert_eq!(call_known, call_printed);
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert!(parsed
+        .verify_signature(&data.as_ref(), &mut (), &metadata, &additional_signed_info)
+        .is_ok());
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
+    // Signature by other key.
+    let data = westend_signed_extrinsic(
+        &[&[0x00][..], &pair_sr25519.public().0].concat(),
+        &[
+            &[0x01][..],
+            &sr25519::Pair::from_string("//Bob", None)
+                .unwrap()
+                .sign(&payload)
+                .0,
+        ]
+        .concat(),
+    );
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert_eq!(
+        parsed.verify_signature(&data.as_ref(), &mut (), &metadata, &additional_signed_info),
+        Err(SignatureError::Invalid)
     );
-    let extensions_known = "
-Era: Mortal, phase: 61, period: 64
-Struct: 1 field(s)
-  Field Name: nonce
-    Nonce: 2339
-Tip: 55.555555 uACA
-Chain: acala2200
-Tx Version: 3
-Genesis Hash: fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c
-Block Hash: fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c
-";
-    assert_eq!(extensions_known, extensions_printed);
 }
 
+#[cfg(feature = "std")]
 #[test]
-fn tr_8() {
-    let data = hex::decode("641a04100000083434000008383800000c31333200000c313736d503040b63ce64c10c05d62400001800000091b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c391b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3").unwrap();
+fn unchecked_extrinsic_signature_2() {
+    let mut metadata = metadata("for_tests/westend9111");
 
-    let metadata_polkadot = metadata("for_tests/polkadot9430");
+    // `CheckWeight` gets `u32` additional signed data, same type as the one
+    // in `CheckSpecVersion`.
+    let u32_ty = metadata.extrinsic.signed_extensions[0].additional_signed;
+    let check_weight = metadata
+        .extrinsic
+        .signed_extensions
+        .iter_mut()
+        .find(|signed_extension| signed_extension.identifier == "CheckWeight")
+        .unwrap();
+    check_weight.additional_signed = u32_ty;
 
-    let reply = parse_transaction(
-        &data.as_ref(),
-        &mut (),
-        &metadata_polkadot,
-        Some(genesis_hash_polkadot()),
-    )
-    .unwrap()
-    .card(
-        &specs_polkadot(),
-        &<RuntimeMetadataV14 as AsMetadata<()>>::spec_name_version(&metadata_polkadot)
+    let genesis_hash = H256(
+        hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
             .unwrap()
-            .spec_name,
+            .try_into()
+            .unwrap(),
     );
+    let mut additional_signed_info = AdditionalSignedInfo {
+        genesis_hash,
+        block_hash: None,
+        spec_version: 9111,
+        tx_version: 7,
+        other: Vec::new(),
+    };
 
-    let call_printed = format!(
-        "\n{}\n",
-        reply
-            .call_result
+    let data = westend_signed_extrinsic(
+        &[&[0x00][..], &[0x11; 32]].concat(),
+        &[&[0x01][..], &[0x22; 64]].concat(),
+    );
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert_eq!(
+        parsed.signing_payload(&data.as_ref(), &mut (), &metadata, &additional_signed_info),
+        Err(SignatureError::AdditionalSignedUnknown {
+            identifier: "CheckWeight".to_string()
+        })
+    );
+
+    // Caller-provided data goes into payload in signed extensions order.
+    additional_signed_info
+        .other
+        .push(ExtensionAdditionalSigned {
+            identifier: "CheckWeight".to_string(),
+            encoded: 42u32.encode(),
+        });
+    assert_eq!(
+        parsed
+            .signing_payload(&data.as_ref(), &mut (), &metadata, &additional_signed_info)
+            .unwrap(),
+        [
+            hex::decode(
+                "0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717000400",
+            )
+            .unwrap(),
+            9111u32.encode(),
+            7u32.encode(),
+            genesis_hash.0.to_vec(),
+            genesis_hash.0.to_vec(),
+            42u32.encode(),
+        ]
+        .concat()
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn unchecked_extrinsic_hashes_1() {
+    let metadata = metadata("for_tests/westend9111");
+    let genesis_hash = H256(
+        hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e")
+            .unwrap()
+            .try_into()
+            .unwrap(),
+    );
+    let additional_signed_info = AdditionalSignedInfo {
+        genesis_hash,
+        block_hash: None,
+        spec_version: 9111,
+        tx_version: 7,
+        other: Vec::new(),
+    };
+    let call = hex::decode(
+        "0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717",
+    )
+    .unwrap();
+
+    let data = westend_signed_extrinsic(
+        &[&[0x00][..], &[0x11; 32]].concat(),
+        &[&[0x01][..], &[0x22; 64]].concat(),
+    );
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert_eq!(
+        parsed.extrinsic_hash(&data.as_ref(), &mut ()).unwrap(),
+        H256(blake2_256(&data))
+    );
+    assert_eq!(
+        parsed.call_hash(&data.as_ref(), &mut ()).unwrap(),
+        H256(blake2_256(&call))
+    );
+    assert_eq!(
+        parsed
+            .signing_payload(&data.as_ref(), &mut (), &metadata, &additional_signed_info)
+            .unwrap(),
+        [
+            call.clone(),
+            hex::decode("000400").unwrap(),
+            9111u32.encode(),
+            7u32.encode(),
+            genesis_hash.0.to_vec(),
+            genesis_hash.0.to_vec(),
+        ]
+        .concat()
+    );
+
+    // Unsigned extrinsic has no signing payload.
+    let data = [
+        Compact(call.len() as u32 + 1).encode(),
+        vec![0x04],
+        call.clone(),
+    ]
+    .concat();
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert_eq!(
+        parsed.extrinsic_hash(&data.as_ref(), &mut ()).unwrap(),
+        H256(blake2_256(&data))
+    );
+    assert_eq!(
+        parsed.call_hash(&data.as_ref(), &mut ()).unwrap(),
+        H256(blake2_256(&call))
+    );
+    assert_eq!(
+        parsed.signing_payload(&data.as_ref(), &mut (), &metadata, &additional_signed_info),
+        Err(SignatureError::Unsigned)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn unchecked_extrinsic_hashes_2() {
+    // Same extrinsic as in `unchecked_extrinsic_1` test, mortal, with nonce 1.
+    let data = hex::decode("39028400d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0158e09098782f2e40602b37d94fe3e2d051c2e4927c34bc85525297310642db08280110b4a02b89676e966d07fdf7f362cdeb858d28d681564bd0f7d33dce5c8cc50204000403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717").unwrap();
+    let metadata = metadata("for_tests/westend9111");
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert_eq!(
+        hex::encode(parsed.extrinsic_hash(&data.as_ref(), &mut ()).unwrap()),
+        "876a0d42005d84c2c478c12b2431f72949c50d59480ee7b07aebde7ddf3ce79d"
+    );
+    assert_eq!(
+        hex::encode(parsed.call_hash(&data.as_ref(), &mut ()).unwrap()),
+        "019741f459bd799e10b9113d23bb8e36b74cc3af32706d94052dccbec484be5a"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn unchecked_extrinsic_v5_1() {
+    let metadata = metadata("for_tests/westend9111");
+    let call = hex::decode(
+        "0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717",
+    )
+    .unwrap();
+    let with_version = |version_bytes: &[u8]| {
+        let body = [version_bytes, &call].concat();
+        [Compact(body.len() as u32).encode(), body].concat()
+    };
+
+    // General extrinsic: extension version `0`, then extensions (immortal,
+    // nonce 1, no tip), then call.
+    let data = with_version(&hex::decode("4500000400").unwrap());
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    let signed_data = westend_signed_extrinsic(
+        &[&[0x00][..], &[0x11; 32]].concat(),
+        &[&[0x01][..], &[0x22; 64]].concat(),
+    );
+    let signed = decode_as_unchecked_extrinsic(&signed_data.as_ref(), &mut (), &metadata).unwrap();
+    if let (
+        UncheckedExtrinsic::General {
+            extension_version,
+            extensions,
+            call: general_call,
+            ..
+        },
+        UncheckedExtrinsic::Signed {
+            extra,
+            call: signed_call,
+            ..
+        },
+    ) = (&parsed, &signed)
+    {
+        assert_eq!(*extension_version, 0);
+        assert_eq!(extensions, extra);
+        assert_eq!(general_call, signed_call);
+    } else {
+        panic!("Expected general and signed extrinsics!")
+    }
+    assert_eq!(
+        parsed.extrinsic_hash(&data.as_ref(), &mut ()).unwrap(),
+        H256(blake2_256(&data))
+    );
+    assert_eq!(
+        parsed.call_hash(&data.as_ref(), &mut ()).unwrap(),
+        H256(blake2_256(&call))
+    );
+
+    // Bare extrinsic is decoded as unsigned, and is hashed with its own
+    // version byte, not the metadata one.
+    let data = with_version(&[0x05]);
+    let parsed = decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata).unwrap();
+    assert!(matches!(parsed, UncheckedExtrinsic::Unsigned { .. }));
+    assert_eq!(
+        hex::encode(parsed.extrinsic_hash(&data.as_ref(), &mut ()).unwrap()),
+        "5efca342c1ff6187727ce2a29f610738ed7722273fd3381029506dbd3dedf571"
+    );
+    assert_eq!(
+        parsed.call_hash(&data.as_ref(), &mut ()).unwrap(),
+        H256(blake2_256(&call))
+    );
+
+    // Unknown format version, signed version 5, and general version 4.
+    for version_byte in [0x06, 0x85, 0x44] {
+        let data = with_version(&[version_byte]);
+        assert_eq!(
+            decode_as_unchecked_extrinsic(&data.as_ref(), &mut (), &metadata),
+            Err(UncheckedExtrinsicError::UnsupportedVersion { version_byte })
+        );
+    }
+}
+
+#[test]
+fn marked_data_hashes_1() {
+    let data = hex::decode("9c0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717d5031504025a62029723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e98a8ee9e389043cd8a9954b254d822d34138b9ae97d3b7f50dc6781b13df8d84").unwrap();
+    let input = data.as_slice();
+    let marked_data = MarkedData::<&[u8], (), RuntimeMetadataV14>::mark(&input, &mut ()).unwrap();
+    assert_eq!(
+        marked_data.call_hash(&mut ()).unwrap(),
+        H256(blake2_256(&data[1..40]))
+    );
+    assert_eq!(marked_data.signing_payload(&mut ()).unwrap(), data[1..]);
+
+    // `system.remark` with 300 bytes of data, payload gets hashed.
+    let call = [vec![0x00, 0x01], Compact(300u32).encode(), vec![0x42; 300]].concat();
+    let data = [
+        Compact(call.len() as u32).encode(),
+        call.clone(),
+        hex::decode("d50315040000").unwrap(),
+    ]
+    .concat();
+    let input = data.as_slice();
+    let marked_data = MarkedData::<&[u8], (), RuntimeMetadataV14>::mark(&input, &mut ()).unwrap();
+    assert_eq!(
+        marked_data.call_hash(&mut ()).unwrap(),
+        H256(blake2_256(&call))
+    );
+    assert_eq!(
+        marked_data.signing_payload(&mut ()).unwrap(),
+        blake2_256(&[call, hex::decode("d50315040000").unwrap()].concat())
+    );
+}
+
+#[test]
+fn tr_7() {
+    let data = hex::decode("a00a0304a84b841c4d9d1a179be03bb31131c14ebf6ce22233158139ae28a3dfaac5fe1560a5e9e05cd5038d248ed73e0d9808000003000000fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64cfc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c").unwrap();
+
+    let metadata_acala = metadata("for_tests/acala2200");
+
+    let reply = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata_acala,
+        Some(genesis_hash_acala()),
+    )
+    .unwrap()
+    .card(
+        &specs_acala(),
+        &<RuntimeMetadataV14 as AsMetadata<()>>::spec_name_version(&metadata_acala)
+            .unwrap()
+            .spec_name,
+    );
+
+    let call_printed = format!(
+        "\n{}\n",
+        reply
+            .call_result
+            .unwrap()
+            .iter()
+            .map(|card| card.show())
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    let call_known = "
+Pallet: Balances
+  Call: transfer_keep_alive
+    Field Name: dest
+      Enum
+        Enum Variant Name: Address20
+          Sequence u8: a84b841c4d9d1a179be03bb31131c14ebf6ce222
+    Field Name: value
+      Balance: 123456789012345.678901234567890123456789 TACA
+";
+    assert_eq!(call_known, call_printed);
+
+    let extensions_printed = show_extensions(&reply.extensions);
+    let extensions_known = "
+Era: Mortal, phase: 61, period: 64
+Struct: 1 field(s)
+  Field Name: nonce
+    Nonce: 2339
+Tip: 55.555555 uACA
+Chain: acala2200
+Tx Version: 3
+Genesis Hash: fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c
+Block Hash: fc41b9bd8ef8fe53d58c7ea67c794c7ec9a73daf05e6d54b14ff6342c99ba64c
+";
+    assert_eq!(extensions_known, extensions_printed);
+}
+
+#[test]
+fn tr_8() {
+    let data = hex::decode("641a04100000083434000008383800000c31333200000c313736d503040b63ce64c10c05d62400001800000091b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c391b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3").unwrap();
+
+    let metadata_polkadot = metadata("for_tests/polkadot9430");
+
+    let reply = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata_polkadot,
+        Some(genesis_hash_polkadot()),
+    )
+    .unwrap()
+    .card(
+        &specs_polkadot(),
+        &<RuntimeMetadataV14 as AsMetadata<()>>::spec_name_version(&metadata_polkadot)
+            .unwrap()
+            .spec_name,
+    );
+
+    let call_printed = format!(
+        "\n{}\n",
+        reply
+            .call_result
             .unwrap()
             .iter()
             .map(|card| card.show())
@@ -1343,15 +2189,7 @@ Pallet: Utility
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 61, period: 64
 Nonce: 1
@@ -1586,15 +2424,7 @@ Pallet: XcmPallet
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 61, period: 64
 Nonce: 100
@@ -1648,15 +2478,7 @@ Pallet: Balances
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 61, period: 64
 Nonce: 1
@@ -1717,17 +2539,9 @@ Pallet: Referenda
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
-Era: Immortal
+Era: Immortal, warning: transaction never expires
 Nonce: 3046252921
 Tip: 2158321035032515.9632029318439228220671 TDOT
 Chain: polkadot9430
@@ -1797,15 +2611,7 @@ Pallet: SystemStaking
 ";
     assert_eq!(call_known, call_printed);
 
-    let extensions_printed = format!(
-        "\n{}\n",
-        reply
-            .extensions
-            .iter()
-            .map(|card| card.show())
-            .collect::<Vec<String>>()
-            .join("\n")
-    );
+    let extensions_printed = show_extensions(&reply.extensions);
     let extensions_known = "
 Era: Mortal, phase: 1764, period: 2048
 Nonce: 193051997
@@ -1817,3 +2623,1791 @@ Block Hash: c16d68cf9978c938e405eec35d283be02e720072e8a0f66b11c722bb85d86f01
 ";
     assert_eq!(extensions_known, extensions_printed);
 }
+
+#[test]
+fn tr_v15_1() {
+    let metadata_westend_v14 = metadata("for_tests/westend9111");
+    let metadata_westend_v15 = metadata_v15("for_tests/westend9111");
+
+    assert_eq!(
+        <RuntimeMetadataV14 as AsMetadata<()>>::spec_name_version(&metadata_westend_v14).unwrap(),
+        <RuntimeMetadataV15 as AsMetadata<()>>::spec_name_version(&metadata_westend_v15).unwrap()
+    );
+    assert_eq!(
+        <RuntimeMetadataV14 as AsMetadata<()>>::extrinsic(&metadata_westend_v14).unwrap(),
+        <RuntimeMetadataV15 as AsMetadata<()>>::extrinsic(&metadata_westend_v15).unwrap()
+    );
+
+    let data = hex::decode("4d0210020806000046ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a07001b2c3ef70006050c0008264834504a64ace1373f0c8ed5d57381ddf54a2f67a318fa42b1352681606d00aebb0211dbb07b4d335a657257b8ac5e53794c901e4f616d4a254f2490c43934009ae581fef1fc06828723715731adcf810e42ce4dadad629b1b7fa5c3c144a81d550008009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff").unwrap();
+
+    let parsed_v14 = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend_v14,
+        Some(genesis_hash_westend()),
+    )
+    .unwrap();
+    let parsed_v15 = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend_v15,
+        Some(genesis_hash_westend()),
+    )
+    .unwrap();
+    assert_eq!(parsed_v14.extensions, parsed_v15.extensions);
+    assert_eq!(
+        parsed_v14.call_result.unwrap(),
+        parsed_v15.call_result.unwrap()
+    );
+}
+
+#[test]
+fn metadata_prefixed_1() {
+    let metadata_hex = std::fs::read_to_string("for_tests/westend9111").unwrap();
+    let metadata_versioned =
+        MetadataVersioned::from_prefixed_hex(&format!("0x{}", metadata_hex.trim())).unwrap();
+    assert_eq!(metadata_versioned.version(), 14);
+    assert_eq!(
+        metadata_versioned,
+        MetadataVersioned::from_prefixed_hex(&metadata_hex).unwrap()
+    );
+
+    let metadata_westend_v15 = metadata_v15("for_tests/westend9111");
+    let metadata_v15_encoded = RuntimeMetadataPrefixed(
+        META_RESERVED,
+        RuntimeMetadata::V15(metadata_westend_v15.clone()),
+    )
+    .encode();
+    let metadata_versioned = MetadataVersioned::from_prefixed_bytes(&metadata_v15_encoded).unwrap();
+    assert_eq!(metadata_versioned.version(), 15);
+    assert_eq!(
+        <MetadataVersioned as AsMetadata<()>>::spec_name_version(&metadata_versioned).unwrap(),
+        <RuntimeMetadataV15 as AsMetadata<()>>::spec_name_version(&metadata_westend_v15).unwrap()
+    );
+    assert_eq!(
+        metadata_versioned,
+        MetadataVersioned::V15(metadata_westend_v15)
+    );
+}
+
+#[test]
+fn metadata_prefixed_2() {
+    let metadata_hex = std::fs::read_to_string("for_tests/westend9111").unwrap();
+    let metadata_vec = hex::decode(metadata_hex.trim()).unwrap();
+
+    let mut wrong_magic = metadata_vec.clone();
+    wrong_magic[0] = 0;
+    assert_eq!(
+        MetadataVersioned::from_prefixed_bytes(&wrong_magic).unwrap_err(),
+        MetadataPrefixedError::WrongMagic { found: 0x61746500 }
+    );
+
+    let mut unsupported_version = metadata_vec.clone();
+    unsupported_version[4] = 13;
+    assert_eq!(
+        MetadataVersioned::from_prefixed_bytes(&unsupported_version).unwrap_err(),
+        MetadataPrefixedError::UnsupportedVersion(13)
+    );
+
+    let mut trailing_bytes = metadata_vec.clone();
+    trailing_bytes.extend_from_slice(&[0, 1]);
+    assert_eq!(
+        MetadataVersioned::from_prefixed_bytes(&trailing_bytes).unwrap_err(),
+        MetadataPrefixedError::SomeDataNotUsed {
+            from: metadata_vec.len()
+        }
+    );
+
+    assert_eq!(
+        MetadataVersioned::from_prefixed_bytes(&metadata_vec[..4]).unwrap_err(),
+        MetadataPrefixedError::TooShort
+    );
+    assert_eq!(
+        MetadataVersioned::from_prefixed_hex("0xmeta").unwrap_err(),
+        MetadataPrefixedError::NotHex
+    );
+}
+
+#[test]
+fn runtime_api_1() {
+    let metadata_westend = metadata_v15_with_apis("for_tests/westend9111");
+
+    let (api_name, method_name) =
+        split_state_call_method("AccountNonceApi_account_nonce", &metadata_westend).unwrap();
+    assert_eq!(api_name, "AccountNonceApi");
+    assert_eq!(method_name, "account_nonce");
+
+    let input =
+        hex::decode("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d").unwrap();
+    let call = decode_runtime_api_call(
+        api_name,
+        method_name,
+        &input.as_ref(),
+        &mut (),
+        &metadata_westend,
+    )
+    .unwrap();
+    let call_printed = format!(
+        "\n{}\n",
+        call.card(0, false, &specs_westend(), "westend")
+            .iter()
+            .map(|card| card.show_with_docs())
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    let call_known = "
+Runtime Api: AccountNonceApi
+(docs:  The API to query account nonce., path: None)
+  Method: account_nonce
+  (docs:  Get current account nonce of given `AccountId`., path: None)
+    Field Name: account
+      Id: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY
+      (docs: None, path: sp_core >> crypto >> AccountId32)
+";
+    assert_eq!(call_known, call_printed);
+
+    let output = hex::decode("05000000").unwrap();
+    let output_data = decode_runtime_api_output(
+        api_name,
+        method_name,
+        &output.as_ref(),
+        &mut (),
+        &metadata_westend,
+    )
+    .unwrap();
+    assert_eq!(
+        output_data,
+        ExtendedData {
+            data: ParsedData::PrimitiveU32 {
+                value: 5,
+                specialty: SpecialtyUnsignedInteger::None
+            },
+            info: Vec::new()
+        }
+    );
+}
+
+#[test]
+fn runtime_api_2() {
+    let metadata_westend = metadata_v15_with_apis("for_tests/westend9111");
+
+    let (api_name, method_name) =
+        split_state_call_method("TransactionPaymentApi_query_info", &metadata_westend).unwrap();
+
+    // Unchecked extrinsic followed by its length
+    let input = hex::decode("39028400d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0158e09098782f2e40602b37d94fe3e2d051c2e4927c34bc85525297310642db08280110b4a02b89676e966d07fdf7f362cdeb858d28d681564bd0f7d33dce5c8cc50204000403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d71790000000").unwrap();
+    let call = decode_runtime_api_call(
+        api_name,
+        method_name,
+        &input.as_ref(),
+        &mut (),
+        &metadata_westend,
+    )
+    .unwrap();
+    assert_eq!(call.inputs.len(), 2);
+    assert_eq!(
+        call.inputs[1].data.data,
+        ParsedData::PrimitiveU32 {
+            value: 144,
+            specialty: SpecialtyUnsignedInteger::None
+        }
+    );
+
+    let output = hex::decode("80d1f008000000000000f2052a010000000000000000000000").unwrap();
+    let output_data = decode_runtime_api_output(
+        api_name,
+        method_name,
+        &output.as_ref(),
+        &mut (),
+        &metadata_westend,
+    )
+    .unwrap();
+    let output_printed = format!(
+        "\n{}\n",
+        output_data
+            .card(0, true, &specs_westend(), "westend")
+            .iter()
+            .map(|card| card.show())
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    let output_known = "
+Struct: 3 field(s)
+  Field Name: weight
+    u64: 150000000
+  Field Name: class
+    Enum
+      Enum Variant Name: Normal
+  Field Name: partial_fee
+    Balance: 5.000000000 mWND
+";
+    assert_eq!(output_known, output_printed);
+
+    assert_eq!(
+        decode_runtime_api_output(
+            "TransactionPaymentApi",
+            "query_fee_details",
+            &output.as_ref(),
+            &mut (),
+            &metadata_westend,
+        )
+        .unwrap_err(),
+        RuntimeApiError::NoMethod {
+            api_name: "TransactionPaymentApi".to_string(),
+            method_name: "query_fee_details".to_string()
+        }
+    );
+}
+
+#[test]
+fn pallet_error_1() {
+    let metadata_polkadot = metadata("for_tests/polkadot9430");
+    let dispatch_error_ty: UntrackedSymbol<TypeId> =
+        ty_id_by_ident(&metadata_polkadot.types, "DispatchError").into();
+
+    // `DispatchError::Module`, pallet index 5, error index 2
+    let data = hex::decode("030502000000").unwrap();
+    let mut parsed = decode_all_as_type::<&[u8], (), RuntimeMetadataV14>(
+        &dispatch_error_ty,
+        &data.as_ref(),
+        &mut (),
+        &metadata_polkadot.types,
+    )
+    .unwrap();
+    resolve_module_errors(&mut parsed, &mut (), &metadata_polkadot);
+
+    let cards = parsed.card(0, false, &specs_polkadot(), "polkadot");
+    let printed = format!(
+        "\n{}\n",
+        cards
+            .iter()
+            .map(|card| card.show())
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    let known = "
+Enum
+  Enum Variant Name: Module
+    Pallet Error: Balances::InsufficientBalance
+";
+    assert_eq!(known, printed);
+    assert!(cards[2]
+        .show_with_docs()
+        .ends_with("(docs: Balance too low to send value., path: None)"));
+
+    let metadata_polkadot_v15 = metadata_v15("for_tests/polkadot9430");
+    let mut parsed_v15 = decode_all_as_type::<&[u8], (), RuntimeMetadataV15>(
+        &dispatch_error_ty,
+        &data.as_ref(),
+        &mut (),
+        &metadata_polkadot_v15.types,
+    )
+    .unwrap();
+    resolve_module_errors(&mut parsed_v15, &mut (), &metadata_polkadot_v15);
+    assert_eq!(parsed, parsed_v15);
+}
+
+#[test]
+fn events_1() {
+    let metadata_polkadot = metadata("for_tests/polkadot9430");
+
+    // Three event records:
+    // `System::ExtrinsicSuccess` for extrinsic 0,
+    // `System::ExtrinsicFailed` with `Balances::InsufficientBalance` error for
+    // extrinsic 1,
+    // `System::CodeUpdated` during finalization, with a single topic.
+    let data = hex::decode("0c00000000000000a10f0000000000010000000001030502000000a10f000000000100020442f4f2b2d8e02a4e0b2d5c65a4e4f8d1e05bbd2a4dc14d83fa8e1a48d4b96f76").unwrap();
+    let records = decode_events(&data.as_ref(), &mut (), &metadata_polkadot).unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].phase, Phase::ApplyExtrinsic(0));
+    assert_eq!(records[1].phase, Phase::ApplyExtrinsic(1));
+    assert_eq!(records[2].phase, Phase::Finalization);
+    assert_eq!(records[2].extrinsic_index(), None);
+    assert_eq!(records[0].event.0.variant_name, "ExtrinsicSuccess");
+    assert_eq!(records[2].event.0.variant_name, "CodeUpdated");
+    assert!(records[0].topics.is_empty());
+    assert_eq!(
+        records[2].topics,
+        vec![H256(
+            hex::decode("42f4f2b2d8e02a4e0b2d5c65a4e4f8d1e05bbd2a4dc14d83fa8e1a48d4b96f76")
+                .unwrap()
+                .try_into()
+                .unwrap()
+        )]
+    );
+
+    let printed = format!(
+        "\n{}\n",
+        records[1]
+            .event
+            .card(0, &specs_polkadot(), "polkadot")
+            .iter()
+            .map(|card| card.show())
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    let known = "
+Pallet: System
+  Event: ExtrinsicFailed
+    Field Name: dispatch_error
+      Enum
+        Enum Variant Name: Module
+          Pallet Error: Balances::InsufficientBalance
+    Field Name: dispatch_info
+      Struct: 3 field(s)
+        Field Name: weight
+          Struct: 2 field(s)
+            Field Name: ref_time
+              u64: 1000
+            Field Name: proof_size
+              u64: 0
+        Field Name: class
+          Enum
+            Enum Variant Name: Normal
+        Field Name: pays_fee
+          Enum
+            Enum Variant Name: Yes
+";
+    assert_eq!(known, printed);
+
+    assert_eq!(events_for_extrinsic(&records, 1), vec![&records[1]]);
+    assert!(events_for_extrinsic(&records, 2).is_empty());
+    let grouped = group_events_by_extrinsic(&records);
+    assert_eq!(grouped.len(), 2);
+    assert_eq!(grouped[&0], vec![&records[0]]);
+    assert_eq!(grouped[&1], vec![&records[1]]);
+
+    let metadata_polkadot_v15 = metadata_v15("for_tests/polkadot9430");
+    let records_v15 = decode_events(&data.as_ref(), &mut (), &metadata_polkadot_v15).unwrap();
+    assert_eq!(records, records_v15);
+}
+
+#[test]
+fn constants_1() {
+    let metadata_polkadot = metadata("for_tests/polkadot9430");
+
+    let existential_deposit = decode_constant(
+        "Balances",
+        "ExistentialDeposit",
+        &mut (),
+        &metadata_polkadot,
+    )
+    .unwrap();
+    assert_eq!(
+        existential_deposit.value.data,
+        ParsedData::PrimitiveU128 {
+            value: 10000000000,
+            specialty: SpecialtyUnsignedInteger::Balance
+        }
+    );
+    assert!(existential_deposit
+        .docs
+        .starts_with(" The minimum amount required to keep an account open."));
+
+    let printed = format!(
+        "\n{}\n",
+        existential_deposit
+            .card(0, &specs_polkadot(), "polkadot")
+            .iter()
+            .map(|card| card.show())
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    let known = "
+Pallet: Balances
+  Constant: ExistentialDeposit
+    Balance: 1.0000000000 DOT
+";
+    assert_eq!(known, printed);
+
+    let balances_constants =
+        decode_pallet_constants("Balances", &mut (), &metadata_polkadot).unwrap();
+    let constant_names = balances_constants
+        .iter()
+        .map(|constant| constant.constant_name.as_str())
+        .collect::<Vec<&str>>();
+    assert_eq!(
+        constant_names,
+        vec![
+            "ExistentialDeposit",
+            "MaxLocks",
+            "MaxReserves",
+            "MaxHolds",
+            "MaxFreezes"
+        ]
+    );
+    assert_eq!(balances_constants[0], existential_deposit);
+    assert_eq!(
+        balances_constants[1].value.data,
+        ParsedData::PrimitiveU32 {
+            value: 50,
+            specialty: SpecialtyUnsignedInteger::None
+        }
+    );
+
+    let block_hash_count =
+        decode_constant("System", "BlockHashCount", &mut (), &metadata_polkadot).unwrap();
+    assert_eq!(
+        block_hash_count.value.data,
+        ParsedData::PrimitiveU32 {
+            value: 4096,
+            specialty: SpecialtyUnsignedInteger::None
+        }
+    );
+
+    assert_eq!(
+        decode_constant("Balances", "NotAConstant", &mut (), &metadata_polkadot).unwrap_err(),
+        ConstantError::NoConstant {
+            pallet_name: String::from("Balances"),
+            constant_name: String::from("NotAConstant"),
+        }
+    );
+    assert_eq!(
+        decode_pallet_constants("NotAPallet", &mut (), &metadata_polkadot).unwrap_err(),
+        ConstantError::NoPallet {
+            pallet_name: String::from("NotAPallet"),
+        }
+    );
+
+    let metadata_polkadot_v15 = metadata_v15("for_tests/polkadot9430");
+    let existential_deposit_v15 = decode_constant(
+        "Balances",
+        "ExistentialDeposit",
+        &mut (),
+        &metadata_polkadot_v15,
+    )
+    .unwrap();
+    assert_eq!(existential_deposit, existential_deposit_v15);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn block_1() {
+    let metadata_westend = metadata("for_tests/westend9111");
+
+    // Block body with three extrinsics. First and last extrinsics are
+    // identical signed extrinsics, second extrinsic has unsupported version
+    // byte.
+    let body = hex::decode("0c39028400d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0158e09098782f2e40602b37d94fe3e2d051c2e4927c34bc85525297310642db08280110b4a02b89676e966d07fdf7f362cdeb858d28d681564bd0f7d33dce5c8cc50204000403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d71708060039028400d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0158e09098782f2e40602b37d94fe3e2d051c2e4927c34bc85525297310642db08280110b4a02b89676e966d07fdf7f362cdeb858d28d681564bd0f7d33dce5c8cc50204000403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717").unwrap();
+    let extrinsics = decode_block_body(&body.as_ref(), &mut (), &metadata_westend).unwrap();
+    assert_eq!(extrinsics.len(), 3);
+    assert_eq!(extrinsics[0].index, 0);
+    assert_eq!(extrinsics[0].range, 1..145);
+    assert!(matches!(
+        extrinsics[0].extrinsic,
+        Ok(UncheckedExtrinsic::Signed { .. })
+    ));
+    assert_eq!(extrinsics[1].index, 1);
+    assert_eq!(extrinsics[1].range, 145..148);
+    assert_eq!(
+        extrinsics[1].extrinsic,
+        Err(UncheckedExtrinsicError::UnsupportedVersion { version_byte: 6 })
+    );
+    assert_eq!(extrinsics[2].index, 2);
+    assert_eq!(extrinsics[2].range, 148..292);
+    assert_eq!(extrinsics[2].extrinsic, extrinsics[0].extrinsic);
+
+    // Block body is cut in the middle of the second extrinsic.
+    let body_cut = &body[..147];
+    assert!(matches!(
+        decode_block_body(&body_cut, &mut (), &metadata_westend),
+        Err(BlockError::Parsing(ParserError::Buffer(_)))
+    ));
+
+    // Full block, with header: parent hash, block number `100`, state root,
+    // extrinsics root, and empty digest.
+    let header = hex::decode("e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e910198a8ee9e389043cd8a9954b254d822d34138b9ae97d3b7f50dc6781b13df8d8442f4f2b2d8e02a4e0b2d5c65a4e4f8d1e05bbd2a4dc14d83fa8e1a48d4b96f7600").unwrap();
+    let header_len = header.len();
+    let block_data = [header, body].concat();
+    let block = decode_block(&block_data.as_ref(), &mut (), &metadata_westend).unwrap();
+    assert_eq!(block.header.number, 100);
+    assert!(block.header.digest.is_empty());
+    assert_eq!(block.extrinsics.len(), 3);
+    assert_eq!(block.extrinsics[0].range, header_len + 1..header_len + 145);
+    assert_eq!(block.extrinsics[2].extrinsic, extrinsics[2].extrinsic);
+
+    // Metadata without header type in types registry, header is decoded
+    // using well-known header layout.
+    let metadata_acala = metadata("for_tests/acala2012");
+    let block_well_known = decode_block(&block_data.as_ref(), &mut (), &metadata_acala).unwrap();
+    assert_eq!(block_well_known.header, block.header);
+    assert_eq!(block_well_known.extrinsics.len(), 3);
+    assert_eq!(
+        block_well_known.extrinsics[2].range,
+        block.extrinsics[2].range
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn header_1() {
+    let metadata_westend = metadata("for_tests/westend9111");
+    let metadata_acala = metadata("for_tests/acala2012");
+
+    // Header with block number `100` and four digest items: `BABE`
+    // pre-runtime (secondary plain, authority index `5`, slot `280000000`),
+    // `GRANDPA` consensus, `BABE` seal, and runtime environment update.
+    let data = hex::decode("11111111111111111111111111111111111111111111111111111111111111119101222222222222222222222222222222222222222222222222222222222222222233333333333333333333333333333333333333333333333333333333333333331006424142453402050000000076b010000000000446524e4b080102054241424501014444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444408").unwrap();
+
+    // Header type is found in the metadata.
+    let header = decode_header(&data.as_ref(), &mut (), &metadata_westend).unwrap();
+    assert_eq!(header.parent_hash, H256([0x11; 32]));
+    assert_eq!(header.number, 100);
+    assert_eq!(header.digest.len(), 4);
+    assert_eq!(
+        header.digest[0],
+        DigestItem::PreRuntime {
+            engine_id: EngineId(BABE_ENGINE_ID),
+            data: PreRuntimeData::Babe(BabePreDigest::SecondaryPlain {
+                authority_index: 5,
+                slot: 280000000
+            })
+        }
+    );
+    assert_eq!(header.digest[3], DigestItem::RuntimeEnvironmentUpdated);
+
+    // No header type in the metadata, well-known header layout is used.
+    let header_well_known = decode_header(&data.as_ref(), &mut (), &metadata_acala).unwrap();
+    assert_eq!(header_well_known, header);
+
+    let header_printed = header
+        .card(0)
+        .iter()
+        .map(|card| card.show())
+        .collect::<Vec<String>>()
+        .join("\n");
+    let header_known = "Parent Hash: 1111111111111111111111111111111111111111111111111111111111111111
+Block Number: 100
+State Root: 2222222222222222222222222222222222222222222222222222222222222222
+Extrinsics Root: 3333333333333333333333333333333333333333333333333333333333333333
+Digest: 4 item(s)
+  Pre-Runtime: BABE
+    BABE Pre-Digest: SecondaryPlain
+      Authority Index: 5
+      Slot: 280000000
+  Consensus: FRNK
+    Sequence u8: 0102
+  Seal: BABE
+    Sequence u8: 44444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444444
+  Runtime Environment Updated";
+    assert_eq!(header_printed, header_known);
+
+    // `aura` pre-runtime digest contains only the slot.
+    assert_eq!(
+        PreRuntimeData::new(&EngineId(AURA_ENGINE_ID), 280000000u64.encode()),
+        PreRuntimeData::Aura { slot: 280000000 }
+    );
+
+    // Unexpected data for `BABE` pre-runtime digest is kept raw.
+    assert_eq!(
+        PreRuntimeData::new(&EngineId(BABE_ENGINE_ID), vec![7, 0]),
+        PreRuntimeData::Raw(vec![7, 0])
+    );
+
+    // Header data is cut in the middle of the seal.
+    let data_cut = &data[..data.len() - 10];
+    assert!(decode_header(&data_cut, &mut (), &metadata_acala).is_err());
+}
+
+/// Visitor collecting `u128` values and sequence lengths, without assembling
+/// `ExtendedData`.
+#[derive(Debug, Default)]
+struct CollectingVisitor {
+    values_u128: Vec<u128>,
+    sequence_lengths: Vec<usize>,
+}
+
+impl Visitor<()> for CollectingVisitor {
+    type Value = ();
+
+    fn needs_info(&self) -> bool {
+        false
+    }
+
+    fn visit_composite(
+        &mut self,
+        _fields: &[Field<PortableForm>],
+        _values: Vec<()>,
+        info: Vec<Info>,
+    ) -> Result<(), ParserError<()>> {
+        assert!(info.is_empty());
+        Ok(())
+    }
+
+    fn visit_variant(
+        &mut self,
+        _variant: &Variant<PortableForm>,
+        _values: Vec<()>,
+        info: Vec<Info>,
+    ) -> Result<(), ParserError<()>> {
+        assert!(info.is_empty());
+        Ok(())
+    }
+
+    fn visit_empty_enum(&mut self, _info: Vec<Info>) -> Result<(), ParserError<()>> {
+        Ok(())
+    }
+
+    fn visit_sequence(
+        &mut self,
+        element_info: Vec<Info>,
+        values: Vec<()>,
+        info: Vec<Info>,
+    ) -> Result<(), ParserError<()>> {
+        assert!(element_info.is_empty());
+        assert!(info.is_empty());
+        self.sequence_lengths.push(values.len());
+        Ok(())
+    }
+
+    fn visit_tuple(&mut self, _values: Vec<()>, _info: Vec<Info>) -> Result<(), ParserError<()>> {
+        Ok(())
+    }
+
+    fn visit_primitive(
+        &mut self,
+        parsed_data: ParsedData,
+        _info: Vec<Info>,
+    ) -> Result<(), ParserError<()>> {
+        if let ParsedData::PrimitiveU128 { value, .. } = parsed_data {
+            self.values_u128.push(value)
+        }
+        Ok(())
+    }
+
+    fn visit_bit_sequence(
+        &mut self,
+        _parsed_data: ParsedData,
+        _info: Vec<Info>,
+    ) -> Result<(), ParserError<()>> {
+        Ok(())
+    }
+
+    fn visit_special(
+        &mut self,
+        _parsed_data: ParsedData,
+        _info: Vec<Info>,
+    ) -> Result<(), ParserError<()>> {
+        Ok(())
+    }
+
+    fn visit_pallet_specific(
+        &mut self,
+        _item: PalletSpecificItem,
+        _pallet_name: String,
+        _pallet_info: Info,
+        _variant: &Variant<PortableForm>,
+        _values: Vec<()>,
+        _info: Vec<Info>,
+    ) -> Result<(), ParserError<()>> {
+        Ok(())
+    }
+}
+
+#[test]
+fn visitor_1() {
+    // Value from westmint `Assets` pallet `Metadata` storage, same as in
+    // `storage_3_assets_with_key` test.
+    let value_input = hex::decode(
+        "c07a64621700000000000000000000003c4f70656e5371756172652054657374104f534e540a00",
+    )
+    .unwrap();
+
+    let metadata = metadata("for_tests/westmint9270");
+    let storage_entry_metadata = assets_metadata_storage_entry(&metadata);
+    let value_ty = match &storage_entry_metadata.ty {
+        frame_metadata::v14::StorageEntryType::Map { value, .. } => value,
+        frame_metadata::v14::StorageEntryType::Plain(_) => panic!("Expected map storage."),
+    };
+
+    let mut visitor = CollectingVisitor::default();
+    visit_all_as_type::<&[u8], (), RuntimeMetadataV14, CollectingVisitor>(
+        value_ty,
+        &value_input.as_ref(),
+        &mut (),
+        &metadata.types,
+        &mut visitor,
+    )
+    .unwrap();
+
+    // Deposit.
+    assert_eq!(visitor.values_u128, vec![100435000000]);
+
+    // Name `OpenSquare Test` and symbol `OSNT`.
+    assert_eq!(visitor.sequence_lengths, vec![15, 4]);
+
+    // Data remaining after the value is not accepted.
+    let value_input_extended = [value_input, vec![0]].concat();
+    let mut visitor = CollectingVisitor::default();
+    assert_eq!(
+        visit_all_as_type::<&[u8], (), RuntimeMetadataV14, CollectingVisitor>(
+            value_ty,
+            &value_input_extended.as_ref(),
+            &mut (),
+            &metadata.types,
+            &mut visitor,
+        ),
+        Err(ParserError::SomeDataNotUsedBlob { from: 39 })
+    );
+}
+
+#[test]
+fn skip_1() {
+    // Two concatenated values from westmint `Assets` pallet `Metadata`
+    // storage, same as in `storage_3_assets_with_key` test.
+    let value_input = hex::decode(
+        "c07a64621700000000000000000000003c4f70656e5371756172652054657374104f534e540a00",
+    )
+    .unwrap();
+    let data = [value_input.to_vec(), value_input].concat();
+
+    let metadata = metadata("for_tests/westmint9270");
+    let storage_entry_metadata = assets_metadata_storage_entry(&metadata);
+    let value_ty = match &storage_entry_metadata.ty {
+        frame_metadata::v14::StorageEntryType::Map { value, .. } => value,
+        frame_metadata::v14::StorageEntryType::Plain(_) => panic!("Expected map storage."),
+    };
+
+    let mut position = 0;
+    let encoded_len = skip_as_type_at_position::<&[u8], (), RuntimeMetadataV14>(
+        value_ty,
+        &data.as_ref(),
+        &mut (),
+        &metadata.types,
+        &mut position,
+    )
+    .unwrap();
+    assert_eq!(encoded_len, 39);
+    assert_eq!(position, 39);
+
+    // Second value is skipped, and decodes same as the first one.
+    let decoded = decode_as_type_at_position::<&[u8], (), RuntimeMetadataV14>(
+        value_ty,
+        &data.as_ref(),
+        &mut (),
+        &metadata.types,
+        &mut position,
+    )
+    .unwrap();
+    assert_eq!(position, 78);
+    assert_eq!(
+        decoded,
+        decode_all_as_type::<&[u8], (), RuntimeMetadataV14>(
+            value_ty,
+            &data[..39].as_ref(),
+            &mut (),
+            &metadata.types,
+        )
+        .unwrap()
+    );
+
+    // Data is too short, symbol is cut.
+    let mut position = 0;
+    assert!(skip_as_type_at_position::<&[u8], (), RuntimeMetadataV14>(
+        value_ty,
+        &data[..35].as_ref(),
+        &mut (),
+        &metadata.types,
+        &mut position,
+    )
+    .is_err());
+
+    // Asset metadata has no fixed encoded length.
+    assert_eq!(
+        fixed_encoded_len::<(), RuntimeMetadataV14>(value_ty, &mut (), &metadata.types),
+        Ok(None)
+    );
+
+    // `System` pallet `Account` storage value is `AccountInfo` with four `u32`
+    // counters and four `u128` balances.
+    let account_entry =
+        <RuntimeMetadataV14 as AsMetadata<()>>::storage_entry(&metadata, "System", "Account")
+            .unwrap();
+    let account_ty = match &account_entry.ty {
+        frame_metadata::v14::StorageEntryType::Map { value, .. } => value,
+        frame_metadata::v14::StorageEntryType::Plain(_) => panic!("Expected map storage."),
+    };
+    assert_eq!(
+        fixed_encoded_len::<(), RuntimeMetadataV14>(account_ty, &mut (), &metadata.types),
+        Ok(Some(80))
+    );
+}
+
+#[test]
+fn skip_2() {
+    let mut metadata = metadata("for_tests/westmint9270");
+
+    // `AccountInfo` has fixed encoded length of 80 bytes.
+    let account_info_ty = ty_id_by_ident(&metadata.types, "AccountInfo");
+    let mut push_ty = |type_def: TypeDef<PortableForm>| {
+        let id = metadata.types.types.len() as u32;
+        metadata.types.types.push(PortableType {
+            id,
+            ty: Type {
+                path: Path::default(),
+                type_params: Vec::new(),
+                type_def,
+                docs: Vec::new(),
+            },
+        });
+        id
+    };
+    let array_ty = push_ty(TypeDef::Array(TypeDefArray {
+        len: u32::MAX,
+        type_param: account_info_ty.into(),
+    }));
+    let array_of_arrays_ty = push_ty(TypeDef::Array(TypeDefArray {
+        len: u32::MAX,
+        type_param: array_ty.into(),
+    }));
+    let vec_of_arrays_ty = push_ty(TypeDef::Sequence(TypeDefSequence {
+        type_param: array_ty.into(),
+    }));
+
+    // Encoded length overflows `usize`, and is not fixed.
+    assert_eq!(
+        fixed_encoded_len::<(), RuntimeMetadataV14>(
+            &array_of_arrays_ty.into(),
+            &mut (),
+            &metadata.types
+        ),
+        Ok(None)
+    );
+
+    // Large number of elements from the data does not overflow position.
+    let data = Compact(u32::MAX).encode();
+    let mut position = 0;
+    assert_eq!(
+        skip_as_type_at_position::<&[u8], (), RuntimeMetadataV14>(
+            &vec_of_arrays_ty.into(),
+            &data.as_ref(),
+            &mut (),
+            &metadata.types,
+            &mut position,
+        ),
+        Err(ParserError::Buffer(BufferError::DataTooShort {
+            position: data.len(),
+            minimal_length: usize::MAX
+        }))
+    );
+
+    let mut position = 0;
+    assert!(skip_as_type_at_position::<&[u8], (), RuntimeMetadataV14>(
+        &array_of_arrays_ty.into(),
+        &[0u8; 10].as_ref(),
+        &mut (),
+        &metadata.types,
+        &mut position,
+    )
+    .is_err());
+}
+
+#[test]
+fn extract_1() {
+    let metadata = metadata("for_tests/westmint9270");
+
+    // `System` pallet `Account` storage value, `AccountInfo`: nonce,
+    // consumers, providers, sufficients, then `AccountData` with free,
+    // reserved, misc_frozen and fee_frozen balances.
+    let account_entry =
+        <RuntimeMetadataV14 as AsMetadata<()>>::storage_entry(&metadata, "System", "Account")
+            .unwrap();
+    let account_ty = match &account_entry.ty {
+        frame_metadata::v14::StorageEntryType::Map { value, .. } => value,
+        frame_metadata::v14::StorageEntryType::Plain(_) => panic!("Expected map storage."),
+    };
+    let data = [
+        (5u32, 0u32, 1u32, 0u32).encode(),
+        (1_000_000_000_000u128, 20u128, 0u128, 0u128).encode(),
+    ]
+    .concat();
+
+    let free = extract_field::<&[u8], (), RuntimeMetadataV14>(
+        account_ty,
+        "data.free",
+        &data.as_ref(),
+        &mut (),
+        &metadata.types,
+    )
+    .unwrap();
+    assert_eq!(
+        free.data,
+        ParsedData::PrimitiveU128 {
+            value: 1_000_000_000_000,
+            specialty: SpecialtyUnsignedInteger::Balance
+        }
+    );
+
+    let nonce = extract_field::<&[u8], (), RuntimeMetadataV14>(
+        account_ty,
+        "nonce",
+        &data.as_ref(),
+        &mut (),
+        &metadata.types,
+    )
+    .unwrap();
+    assert_eq!(
+        nonce.data,
+        ParsedData::PrimitiveU32 {
+            value: 5,
+            specialty: SpecialtyUnsignedInteger::Nonce
+        }
+    );
+
+    // Unknown field.
+    assert!(matches!(
+        extract_field::<&[u8], (), RuntimeMetadataV14>(
+            account_ty,
+            "data.free2",
+            &data.as_ref(),
+            &mut (),
+            &metadata.types,
+        ),
+        Err(ExtractError::NoSegment { segment, .. }) if segment == "free2"
+    ));
+
+    // Path goes past the primitive.
+    assert!(matches!(
+        extract_field::<&[u8], (), RuntimeMetadataV14>(
+            account_ty,
+            "nonce.x",
+            &data.as_ref(),
+            &mut (),
+            &metadata.types,
+        ),
+        Err(ExtractError::NoSegment { segment, .. }) if segment == "x"
+    ));
+
+    // `Assets` pallet `Metadata` storage value, same as in
+    // `storage_3_assets_with_key` test. Name is `BoundedVec`, a struct with
+    // single unnamed field.
+    let value_input = hex::decode(
+        "c07a64621700000000000000000000003c4f70656e5371756172652054657374104f534e540a00",
+    )
+    .unwrap();
+    let storage_entry_metadata = assets_metadata_storage_entry(&metadata);
+    let value_ty = match &storage_entry_metadata.ty {
+        frame_metadata::v14::StorageEntryType::Map { value, .. } => value,
+        frame_metadata::v14::StorageEntryType::Plain(_) => panic!("Expected map storage."),
+    };
+    let letter = extract_field::<&[u8], (), RuntimeMetadataV14>(
+        value_ty,
+        "name.0.3",
+        &value_input.as_ref(),
+        &mut (),
+        &metadata.types,
+    )
+    .unwrap();
+    assert_eq!(
+        letter.data,
+        ParsedData::PrimitiveU8 {
+            value: b'n',
+            specialty: SpecialtyUnsignedInteger::None
+        }
+    );
+
+    assert_eq!(
+        extract_field::<&[u8], (), RuntimeMetadataV14>(
+            value_ty,
+            "name.0.15",
+            &value_input.as_ref(),
+            &mut (),
+            &metadata.types,
+        ),
+        Err(ExtractError::IndexOutOfRange {
+            segment: "15".to_string(),
+            len: 15
+        })
+    );
+}
+
+#[test]
+fn encode_1() {
+    let metadata_westend = metadata("for_tests/westend9111");
+
+    // Call part of the signable transaction from `tr_1` test: `Utility`
+    // pallet `batch_all` with `Staking` calls, with compact balances and
+    // enums inside.
+    let data = hex::decode("4d0210020806000046ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a07001b2c3ef70006050c0008264834504a64ace1373f0c8ed5d57381ddf54a2f67a318fa42b1352681606d00aebb0211dbb07b4d335a657257b8ac5e53794c901e4f616d4a254f2490c43934009ae581fef1fc06828723715731adcf810e42ce4dadad629b1b7fa5c3c144a81d550008009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff").unwrap();
+    let input = data.as_slice();
+    let marked_data = MarkedData::<&[u8], (), RuntimeMetadataV14>::mark(&input, &mut ()).unwrap();
+    let call_data = data[marked_data.call_start()..marked_data.extensions_start()].to_vec();
+
+    let call = decode_as_call(&marked_data, &mut (), &metadata_westend).unwrap();
+
+    let call_ty = extrinsic_type_params::<(), RuntimeMetadataV14>(
+        &mut (),
+        &metadata_westend.types,
+        &metadata_westend.extrinsic.ty,
+    )
+    .unwrap()
+    .into_iter()
+    .find(|param| param.name == CALL_INDICATOR)
+    .unwrap()
+    .ty
+    .unwrap();
+
+    let encoded = encode_as_type::<(), RuntimeMetadataV14>(
+        &call_ty,
+        &ParsedData::Call(call),
+        &mut (),
+        &metadata_westend.types,
+    )
+    .unwrap();
+    assert_eq!(encoded, call_data);
+
+    // `Assets` pallet `Metadata` storage value, same as in
+    // `storage_3_assets_with_key` test, with modified decimals.
+    let metadata = metadata("for_tests/westmint9270");
+    let value_input = hex::decode(
+        "c07a64621700000000000000000000003c4f70656e5371756172652054657374104f534e540a00",
+    )
+    .unwrap();
+    let storage_entry_metadata = assets_metadata_storage_entry(&metadata);
+    let value_ty = match &storage_entry_metadata.ty {
+        frame_metadata::v14::StorageEntryType::Map { value, .. } => value,
+        frame_metadata::v14::StorageEntryType::Plain(_) => panic!("Expected map storage."),
+    };
+    let mut decoded = decode_all_as_type::<&[u8], (), RuntimeMetadataV14>(
+        value_ty,
+        &value_input.as_ref(),
+        &mut (),
+        &metadata.types,
+    )
+    .unwrap();
+    assert_eq!(
+        encode_extended_data_as_type::<(), RuntimeMetadataV14>(
+            value_ty,
+            &decoded,
+            &mut (),
+            &metadata.types
+        )
+        .unwrap(),
+        value_input
+    );
+
+    if let ParsedData::Composite(ref mut field_data_set) = decoded.data {
+        assert_eq!(field_data_set[3].field_name, Some("decimals".to_string()));
+        field_data_set[3].data.data = ParsedData::PrimitiveU8 {
+            value: 12,
+            specialty: SpecialtyUnsignedInteger::None,
+        };
+    } else {
+        panic!("Expected struct.")
+    }
+    let mut expected = value_input.clone();
+    expected[37] = 12;
+    assert_eq!(
+        encode_extended_data_as_type::<(), RuntimeMetadataV14>(
+            value_ty,
+            &decoded,
+            &mut (),
+            &metadata.types
+        )
+        .unwrap(),
+        expected
+    );
+
+    // Parsed data does not match the type.
+    assert!(matches!(
+        encode_as_type::<(), RuntimeMetadataV14>(
+            value_ty,
+            &ParsedData::PrimitiveBool(true),
+            &mut (),
+            &metadata.types
+        ),
+        Err(EncodeError::TypeMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_1() {
+    let metadata_westend = metadata("for_tests/westend9111");
+    let call_ty = extrinsic_type_params::<(), RuntimeMetadataV14>(
+        &mut (),
+        &metadata_westend.types,
+        &metadata_westend.extrinsic.ty,
+    )
+    .unwrap()
+    .into_iter()
+    .find(|param| param.name == CALL_INDICATOR)
+    .unwrap()
+    .ty
+    .unwrap();
+
+    // Same call as in `unchecked_extrinsic_1` test.
+    let expected_call = hex::decode(
+        "0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717",
+    )
+    .unwrap();
+
+    let json = r#"{"Balances": {"transfer_keep_alive": {"dest": {"Id": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}, "value": "100000000"}}}"#;
+    let encoded = encode_json_as_type::<(), RuntimeMetadataV14>(
+        json,
+        &call_ty,
+        &mut (),
+        &metadata_westend.types,
+    )
+    .unwrap();
+    assert_eq!(encoded, expected_call);
+
+    // Account id as hex, balance as number.
+    let json = r#"{"Balances": {"transfer_keep_alive": {"dest": {"Id": "0x8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"}, "value": 100000000}}}"#;
+    let encoded = encode_json_as_type::<(), RuntimeMetadataV14>(
+        json,
+        &call_ty,
+        &mut (),
+        &metadata_westend.types,
+    )
+    .unwrap();
+    assert_eq!(encoded, expected_call);
+
+    // Negative balance.
+    let json = r#"{"Balances": {"transfer_keep_alive": {"dest": {"Id": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}, "value": "-1"}}}"#;
+    assert_eq!(
+        encode_json_as_type::<(), RuntimeMetadataV14>(
+            json,
+            &call_ty,
+            &mut (),
+            &metadata_westend.types,
+        ),
+        Err(JsonError::UnexpectedValue {
+            pointer: "/Balances/transfer_keep_alive/value".to_string(),
+            expected: "u128"
+        })
+    );
+
+    // Broken SS58 checksum.
+    let json = r#"{"Balances": {"transfer_keep_alive": {"dest": {"Id": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694tz"}, "value": "1"}}}"#;
+    assert_eq!(
+        encode_json_as_type::<(), RuntimeMetadataV14>(
+            json,
+            &call_ty,
+            &mut (),
+            &metadata_westend.types,
+        ),
+        Err(JsonError::UnexpectedValue {
+            pointer: "/Balances/transfer_keep_alive/dest/Id".to_string(),
+            expected: "AccountId32"
+        })
+    );
+
+    // Missing field.
+    let json = r#"{"Balances": {"transfer_keep_alive": {"dest": {"Id": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}}}}"#;
+    assert_eq!(
+        encode_json_as_type::<(), RuntimeMetadataV14>(
+            json,
+            &call_ty,
+            &mut (),
+            &metadata_westend.types,
+        ),
+        Err(JsonError::MissingField {
+            pointer: "/Balances/transfer_keep_alive".to_string(),
+            field_name: "value".to_string()
+        })
+    );
+
+    // Unknown call.
+    let json = r#"{"Balances": {"transfer_all_of_it": {}}}"#;
+    assert_eq!(
+        encode_json_as_type::<(), RuntimeMetadataV14>(
+            json,
+            &call_ty,
+            &mut (),
+            &metadata_westend.types,
+        ),
+        Err(JsonError::UnknownVariant {
+            pointer: "/Balances".to_string(),
+            variant_name: "transfer_all_of_it".to_string()
+        })
+    );
+}
+
+#[test]
+fn tolerant_1() {
+    let metadata_westend = metadata("for_tests/westend9111");
+    let call_ty = extrinsic_type_params::<(), RuntimeMetadataV14>(
+        &mut (),
+        &metadata_westend.types,
+        &metadata_westend.extrinsic.ty,
+    )
+    .unwrap()
+    .into_iter()
+    .find(|param| param.name == CALL_INDICATOR)
+    .unwrap()
+    .ty
+    .unwrap();
+
+    // `Utility` pallet `batch` with two calls: valid `transfer_keep_alive`
+    // (same as in `unchecked_extrinsic_1` test) and a call with unknown pallet
+    // index.
+    let data = hex::decode("1000080403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717ff0001").unwrap();
+
+    // Regular decoding fails.
+    let mut position = 0;
+    assert!(decode_as_type_at_position::<&[u8], (), RuntimeMetadataV14>(
+        &call_ty,
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend.types,
+        &mut position,
+    )
+    .is_err());
+
+    // Tolerant decoding keeps the first call.
+    let mut position = 0;
+    let parsed = decode_as_type_at_position_tolerant::<&[u8], (), RuntimeMetadataV14>(
+        &call_ty,
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend.types,
+        &mut position,
+    )
+    .unwrap();
+    assert_eq!(position, data.len());
+
+    if let ParsedData::Call(call) = &parsed.data {
+        assert_eq!(call.0.pallet_name, "Utility");
+        assert_eq!(call.0.variant_name, "batch");
+        if let ParsedData::SequenceRaw(calls) = &call.0.fields[0].data.data {
+            assert_eq!(calls.data.len(), 2);
+            if let ParsedData::Call(first_call) = &calls.data[0] {
+                assert_eq!(first_call.0.variant_name, "transfer_keep_alive");
+            } else {
+                panic!("Expected call.")
+            }
+            if let ParsedData::Error {
+                position,
+                error: _,
+                raw_remaining,
+            } = &calls.data[1]
+            {
+                assert_eq!(*position, 42);
+                assert_eq!(raw_remaining, &[0xff, 0x00, 0x01]);
+            } else {
+                panic!("Expected error.")
+            }
+        } else {
+            panic!("Expected raw sequence.")
+        }
+    } else {
+        panic!("Expected call.")
+    }
+
+    // Partial data is carded.
+    let cards = parsed.card(0, true, &specs_westend(), "westend");
+    assert!(cards
+        .iter()
+        .any(|card| card.parser_card == ParserCard::CallName("transfer_keep_alive".to_string())));
+    assert!(matches!(
+        cards.last().unwrap().parser_card,
+        ParserCard::Error { position: 42, .. }
+    ));
+}
+
+#[test]
+fn tolerant_2() {
+    let mut metadata_westend = metadata("for_tests/westend9111");
+
+    // `Vec<DispatchClass>` is added into types registry. `DispatchClass` is an
+    // enum with no fields in variants, with fixed encoded length.
+    let dispatch_class_ty = ty_id_by_ident(&metadata_westend.types, "DispatchClass");
+    let vec_dispatch_class_ty = metadata_westend.types.types.len() as u32;
+    metadata_westend.types.types.push(PortableType {
+        id: vec_dispatch_class_ty,
+        ty: Type {
+            path: Path::default(),
+            type_params: Vec::new(),
+            type_def: TypeDef::Sequence(TypeDefSequence {
+                type_param: dispatch_class_ty.into(),
+            }),
+            docs: Vec::new(),
+        },
+    });
+
+    // Second element has unknown variant index, decoding resumes after it.
+    let data = hex::decode("0c000701").unwrap();
+    let mut position = 0;
+    let parsed = decode_as_type_at_position_tolerant::<&[u8], (), RuntimeMetadataV14>(
+        &vec_dispatch_class_ty.into(),
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend.types,
+        &mut position,
+    )
+    .unwrap();
+    assert_eq!(position, data.len());
+    let dispatch_class_crumb = TypeCrumb::Ty {
+        id: dispatch_class_ty,
+        path: metadata_westend
+            .types
+            .resolve(dispatch_class_ty)
+            .unwrap()
+            .path
+            .segments
+            .to_owned(),
+    };
+    if let ParsedData::SequenceRaw(elements) = parsed.data {
+        assert_eq!(elements.data.len(), 3);
+        assert_eq!(
+            elements.data[1],
+            ParsedData::Error {
+                position: 2,
+                error: ParserError::<()>::UnexpectedEnumVariant { position: 2 }
+                    .add_crumb(dispatch_class_crumb)
+                    .to_string(),
+                raw_remaining: vec![0x07],
+            }
+        );
+        if let ParsedData::Variant(variant_data) = &elements.data[2] {
+            assert_eq!(variant_data.variant_name, "Operational");
+        } else {
+            panic!("Expected variant.")
+        }
+    } else {
+        panic!("Expected raw sequence.")
+    }
+}
+
+#[test]
+fn account_id20_checksum() {
+    // Test vectors from EIP-55.
+    for address in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        "0x52908400098527886E0F7030069857D2E4169EE7",
+        "0xde709f2102306220921060314715629080e2fb77",
+    ] {
+        let account_id20 = AccountId20(
+            hex::decode(address.trim_start_matches("0x").to_lowercase())
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(account_id20.to_checksummed(), address);
+    }
+}
+
+#[test]
+fn account_id20_1() {
+    let mut metadata_astar = metadata("for_tests/astar66");
+
+    // `AccountId20` type is added into types registry, with same structure as
+    // `H160`.
+    let h160_ty = ty_id_by_ident(&metadata_astar.types, "H160");
+    let account_id20_ty = metadata_astar.types.types.len() as u32;
+    let mut ty = metadata_astar.types.resolve(h160_ty).unwrap().to_owned();
+    ty.path = Path {
+        segments: vec!["fp_account".to_string(), "AccountId20".to_string()],
+    };
+    metadata_astar.types.types.push(PortableType {
+        id: account_id20_ty,
+        ty,
+    });
+
+    let data = hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+    let parsed = decode_all_as_type::<&[u8], (), RuntimeMetadataV14>(
+        &account_id20_ty.into(),
+        &data.as_ref(),
+        &mut (),
+        &metadata_astar.types,
+    )
+    .unwrap();
+    assert_eq!(
+        parsed.data,
+        ParsedData::AccountId20(AccountId20(data.to_owned().try_into().unwrap()))
+    );
+    let cards = parsed.card(0, false, &specs_westend(), "westend");
+    assert_eq!(
+        cards[0].show(),
+        "AccountId20: 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+    );
+    #[cfg(feature = "std")]
+    if let ParserCard::AccountId20(account_id20_data) = &cards[0].parser_card {
+        assert!(!account_id20_data.identicon.is_empty());
+    } else {
+        panic!("Expected AccountId20 card.")
+    }
+
+    let encoded = encode_extended_data_as_type::<(), RuntimeMetadataV14>(
+        &account_id20_ty.into(),
+        &parsed,
+        &mut (),
+        &metadata_astar.types,
+    )
+    .unwrap();
+    assert_eq!(encoded, data);
+
+    // `H160` is not affected.
+    let parsed = decode_all_as_type::<&[u8], (), RuntimeMetadataV14>(
+        &h160_ty.into(),
+        &data.as_ref(),
+        &mut (),
+        &metadata_astar.types,
+    )
+    .unwrap();
+    assert!(matches!(parsed.data, ParsedData::H160(_)));
+}
+
+/// Custom type for `DispatchClass`, accepting only the first two variants.
+struct DispatchClassCustom;
+
+impl CustomType for DispatchClassCustom {
+    fn matches(&self, path: &Path<PortableForm>) -> bool {
+        path.ident().as_deref() == Some("DispatchClass")
+    }
+
+    fn decode(&self, _path: &Path<PortableForm>, encoded: &[u8]) -> Option<CustomValue> {
+        let value = match encoded {
+            [0] => "Normal",
+            [1] => "Operational",
+            _ => return None,
+        };
+        Some(CustomValue {
+            title: "Dispatch Class".to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+#[test]
+fn custom_types_1() {
+    let mut metadata_westend = metadata("for_tests/westend9111");
+
+    let dispatch_class_ty = ty_id_by_ident(&metadata_westend.types, "DispatchClass");
+    let vec_dispatch_class_ty = metadata_westend.types.types.len() as u32;
+    metadata_westend.types.types.push(PortableType {
+        id: vec_dispatch_class_ty,
+        ty: Type {
+            path: Path::default(),
+            type_params: Vec::new(),
+            type_def: TypeDef::Sequence(TypeDefSequence {
+                type_param: dispatch_class_ty.into(),
+            }),
+            docs: Vec::new(),
+        },
+    });
+
+    let mut custom_types = CustomTypes::new();
+    custom_types.register(Box::new(DispatchClassCustom));
+    let registry = RegistryWithCustomTypes {
+        registry: metadata_westend.types.clone(),
+        custom_types: &custom_types,
+    };
+
+    // Third element is rejected by the custom type, and is decoded regularly.
+    let data = hex::decode("0c000102").unwrap();
+    let mut position = 0;
+    let parsed =
+        decode_as_type_at_position::<&[u8], (), MetadataWithCustomTypes<RuntimeMetadataV14>>(
+            &vec_dispatch_class_ty.into(),
+            &data.as_ref(),
+            &mut (),
+            &registry,
+            &mut position,
+        )
+        .unwrap();
+    assert_eq!(position, data.len());
+    if let ParsedData::SequenceRaw(elements) = &parsed.data {
+        assert_eq!(
+            elements.data[1],
+            ParsedData::Custom {
+                custom_value: CustomValue {
+                    title: "Dispatch Class".to_string(),
+                    value: "Operational".to_string(),
+                },
+                encoded: vec![0x01],
+            }
+        );
+        if let ParsedData::Variant(variant_data) = &elements.data[2] {
+            assert_eq!(variant_data.variant_name, "Mandatory");
+        } else {
+            panic!("Expected variant.")
+        }
+    } else {
+        panic!("Expected raw sequence.")
+    }
+
+    let cards = parsed
+        .card(0, false, &specs_westend(), "westend")
+        .iter()
+        .map(|card| card.show())
+        .collect::<Vec<String>>()
+        .join("\n");
+    assert_eq!(
+        cards,
+        "Sequence: 3 element(s)\n  Dispatch Class: Normal\n  Dispatch Class: Operational\n  Enum\n    Enum Variant Name: Mandatory"
+    );
+
+    // Custom values are encoded back as is.
+    let encoded = encode_extended_data_as_type::<(), RuntimeMetadataV14>(
+        &vec_dispatch_class_ty.into(),
+        &parsed,
+        &mut (),
+        &metadata_westend.types,
+    )
+    .unwrap();
+    assert_eq!(encoded, data);
+
+    // Without custom types the regular decoding is used.
+    let parsed = decode_all_as_type::<&[u8], (), RuntimeMetadataV14>(
+        &vec_dispatch_class_ty.into(),
+        &data.as_ref(),
+        &mut (),
+        &metadata_westend.types,
+    )
+    .unwrap();
+    if let ParsedData::SequenceRaw(elements) = &parsed.data {
+        assert!(matches!(elements.data[1], ParsedData::Variant(_)));
+    } else {
+        panic!("Expected raw sequence.")
+    }
+}
+
+/// Custom type for `RewardDestination`, accepting only variants without
+/// fields.
+struct RewardDestinationCustom;
+
+impl CustomType for RewardDestinationCustom {
+    fn matches(&self, path: &Path<PortableForm>) -> bool {
+        path.ident().as_deref() == Some("RewardDestination")
+    }
+
+    fn decode(&self, _path: &Path<PortableForm>, encoded: &[u8]) -> Option<CustomValue> {
+        let value = match encoded {
+            [0] => "Staked",
+            [1] => "Stash",
+            [2] => "Controller",
+            [4] => "None",
+            _ => return None,
+        };
+        Some(CustomValue {
+            title: "Reward Destination".to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+#[test]
+fn custom_types_2() {
+    let metadata_westend = metadata("for_tests/westend9111");
+
+    let mut custom_types = CustomTypes::new();
+    custom_types.register(Box::new(RewardDestinationCustom));
+    let metadata_with_custom_types = MetadataWithCustomTypes {
+        metadata: &metadata_westend,
+        custom_types: &custom_types,
+    };
+
+    // Same transaction as in `tr_1` test.
+    let data = hex::decode("4d0210020806000046ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a07001b2c3ef70006050c0008264834504a64ace1373f0c8ed5d57381ddf54a2f67a318fa42b1352681606d00aebb0211dbb07b4d335a657257b8ac5e53794c901e4f616d4a254f2490c43934009ae581fef1fc06828723715731adcf810e42ce4dadad629b1b7fa5c3c144a81d550008009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff").unwrap();
+
+    let reply = parse_transaction(
+        &data.as_ref(),
+        &mut (),
+        &metadata_with_custom_types,
+        Some(genesis_hash_westend()),
+    )
+    .unwrap()
+    .card(&specs_westend(), "westend");
+
+    let call_printed = format!(
+        "\n{}\n",
+        reply
+            .call_result
+            .unwrap()
+            .iter()
+            .map(|card| card.show())
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    assert!(call_printed
+        .contains("\n            Field Name: payee\n              Reward Destination: Staked\n"));
+    assert!(!call_printed.contains("Enum Variant Name: Staked"));
+
+    // Extensions are not affected.
+    let extensions_printed = show_extensions(&reply.extensions);
+    let extensions_known = "
+Era: Mortal, phase: 5, period: 64
+Nonce: 2
+Tip: 0 pWND
+Chain: westend9111
+Tx Version: 7
+Genesis Hash: e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e
+Block Hash: 5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff
+";
+    assert_eq!(extensions_known, extensions_printed);
+}
+
+/// Handler for `CheckNonce` extension with no nonce specialty, and with
+/// nonce check.
+struct NonceHandler {
+    max_nonce: u32,
+}
+
+impl ExtensionHandler for NonceHandler {
+    fn hint(&self) -> Option<Hint> {
+        Some(Hint::None)
+    }
+
+    fn check(&self, extra: &ExtendedData, _additional_signed: &ExtendedData) -> Result<(), String> {
+        if let ParsedData::Composite(field_data) = &extra.data {
+            if let ParsedData::PrimitiveU32 { value, .. } = field_data[0].data.data {
+                if value > self.max_nonce {
+                    return Err(format!("Nonce {value} is too high."));
+                }
+                return Ok(());
+            }
+        }
+        Err(String::from("Unexpected nonce format."))
+    }
+}
+
+/// Handler for `ChargeTransactionPayment` extension, replacing tip with
+/// text.
+struct NoTipHandler;
+
+impl ExtensionHandler for NoTipHandler {
+    fn display_extra(&self, extra: ExtendedData) -> ExtendedData {
+        ExtendedData {
+            data: ParsedData::Text {
+                text: String::from("No tip"),
+                specialty: SpecialtyStr::None,
+            },
+            info: extra.info,
+        }
+    }
+}
+
+#[test]
+fn extension_handlers_1() {
+    let metadata_westend = metadata("for_tests/westend9111");
+
+    // Same transaction as in `tr_1` test.
+    let data = hex::decode("4d0210020806000046ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a07001b2c3ef70006050c0008264834504a64ace1373f0c8ed5d57381ddf54a2f67a318fa42b1352681606d00aebb0211dbb07b4d335a657257b8ac5e53794c901e4f616d4a254f2490c43934009ae581fef1fc06828723715731adcf810e42ce4dadad629b1b7fa5c3c144a81d550008009723000007000000e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff").unwrap();
+    let input = data.as_slice();
+    let marked_data = MarkedData::<&[u8], (), RuntimeMetadataV14>::mark(&input, &mut ()).unwrap();
+
+    let mut handlers = ExtensionHandlers::new();
+    handlers.register("CheckNonce", Box::new(NonceHandler { max_nonce: 10 }));
+    handlers.register("ChargeTransactionPayment", Box::new(NoTipHandler));
+
+    let extensions = decode_extensions_with_handlers(
+        &marked_data,
+        &mut (),
+        &metadata_westend,
+        Some(genesis_hash_westend()),
+        &handlers,
+    )
+    .unwrap();
+    let extensions_printed = show_extensions(
+        &extensions
+            .iter()
+            .map(|extension| extension.card(0, &specs_westend(), "westend"))
+            .collect::<Vec<ExtensionCards>>(),
+    );
+    let extensions_known = "
+Era: Mortal, phase: 5, period: 64
+u32: 2
+Text: No tip
+Chain: westend9111
+Tx Version: 7
+Genesis Hash: e143f23803ac50e8f6f8e62695d1ce9e4e1d68aa36c1cd2cfd15340213f3423e
+Block Hash: 5b1d91c89d3de85a4d6eee76ecf3a303cf38b59e7d81522eb7cd24b02eb161ff
+";
+    assert_eq!(extensions_known, extensions_printed);
+
+    // Registering handler again replaces the old one.
+    handlers.register("CheckNonce", Box::new(NonceHandler { max_nonce: 1 }));
+    assert_eq!(
+        decode_extensions_with_handlers(
+            &marked_data,
+            &mut (),
+            &metadata_westend,
+            Some(genesis_hash_westend()),
+            &handlers,
+        )
+        .unwrap_err(),
+        SignableError::ExtensionsList(ExtensionsError::CustomCheck {
+            identifier: String::from("CheckNonce"),
+            reason: String::from("Nonce 2 is too high."),
+        })
+    );
+}
+
+#[test]
+fn call_tree_1() {
+    let metadata_westend = metadata("for_tests/westend9111");
+
+    // `Utility` pallet `batch_all` with two calls:
+    // - `Proxy` pallet `proxy` with `Sudo` pallet `sudo` call, wrapping the
+    // `transfer_keep_alive` (same as in `unchecked_extrinsic_1` test),
+    // - `Sudo` pallet `sudo_as` call, wrapping the same `transfer_keep_alive`.
+    let data = hex::decode("1002081600d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0015000403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717150300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d0403008eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a480284d717").unwrap();
+    let mut position = 0;
+    let call =
+        decode_as_call_unmarked(&data.as_ref(), &mut position, &mut (), &metadata_westend).unwrap();
+    assert_eq!(position, data.len());
+
+    let tree = call.call_tree();
+    assert_eq!(tree.wrapper, Some(CallWrapper::Batch(BatchMode::Atomic)));
+    assert_eq!(tree.children.len(), 2);
+
+    let paths = tree
+        .flatten()
+        .iter()
+        .map(|node| format!("{}: {}", node.path, node.call.0.variant_name))
+        .collect::<Vec<String>>();
+    assert_eq!(
+        paths,
+        [
+            ": batch_all",
+            "batch_all[0]: proxy",
+            "batch_all[0] > proxy.call: sudo",
+            "batch_all[0] > proxy.call > sudo.call: transfer_keep_alive",
+            "batch_all[1]: sudo_as",
+            "batch_all[1] > sudo_as.call: transfer_keep_alive",
+        ]
+    );
+
+    let proxy_node = &tree.children[0];
+    if let Some(CallWrapper::Proxy {
+        real,
+        force_proxy_type,
+    }) = &proxy_node.wrapper
+    {
+        assert!(matches!(real.data, ParsedData::Id(_)));
+        if let Some(force_proxy_type) = force_proxy_type {
+            if let ParsedData::Variant(variant_data) = &force_proxy_type.data {
+                assert_eq!(variant_data.variant_name, "None");
+            } else {
+                panic!("Expected variant.")
+            }
+        } else {
+            panic!("Expected force_proxy_type field.")
+        }
+    } else {
+        panic!("Expected proxy wrapper.")
+    }
+    assert_eq!(proxy_node.children[0].wrapper, Some(CallWrapper::Sudo));
+
+    let sudo_as_node = &tree.children[1];
+    if let Some(CallWrapper::SudoAs { who }) = &sudo_as_node.wrapper {
+        if let ParsedData::Variant(variant_data) = &who.data {
+            assert_eq!(variant_data.variant_name, "Id");
+        } else {
+            panic!("Expected variant.")
+        }
+    } else {
+        panic!("Expected sudo_as wrapper.")
+    }
+
+    // Innermost calls are not wrappers.
+    let transfer_node = &sudo_as_node.children[0];
+    assert_eq!(transfer_node.call.0.pallet_name, "Balances");
+    assert!(transfer_node.wrapper.is_none());
+    assert!(transfer_node.children.is_empty());
+}
@@ -16,14 +16,27 @@ use crate::std::{
 };
 
 use external_memory_tools::ExternalMemory;
-use frame_metadata::v14::{ExtrinsicMetadata, PalletMetadata, RuntimeMetadataV14};
+use frame_metadata::{
+    v14::{
+        ExtrinsicMetadata, PalletConstantMetadata, PalletStorageMetadata, RuntimeMetadataV14,
+        SignedExtensionMetadata, StorageEntryMetadata,
+    },
+    v15::{ExtrinsicMetadata as ExtrinsicMetadataV15, RuntimeMetadataV15},
+    META_RESERVED,
+};
 use parity_scale_codec::{Decode, Encode};
-use scale_info::{form::PortableForm, interner::UntrackedSymbol, PortableRegistry, Type};
+use scale_info::{form::PortableForm, interner::UntrackedSymbol, PortableRegistry, Type, TypeDef};
 
+use crate::block::HEADER_PATH;
 use crate::cards::ParsedData;
 use crate::decode_all_as_type;
-use crate::error::{MetaVersionErrorPallets, ParserError};
-use crate::special_indicators::{SpecialtyStr, SpecialtyUnsignedInteger};
+use crate::decoding_sci::CALL_INDICATOR;
+use crate::error::{MetaVersionErrorPallets, MetadataPrefixedError, ParserError};
+use crate::special_indicators::{
+    CustomTypes, SpecialtyStr, SpecialtyTypeHinted, SpecialtyUnsignedInteger, IMPL_VERSION_ID_SET,
+    STATE_VERSION_ID_SET,
+};
+use crate::unchecked_extrinsic::{ADDRESS_INDICATOR, EXTRA_INDICATOR, SIGNATURE_INDICATOR};
 
 pub trait AsMetadata<E: ExternalMemory>: Debug + Sized {
     type TypeRegistry: ResolveType<E>;
@@ -31,6 +44,47 @@ pub trait AsMetadata<E: ExternalMemory>: Debug + Sized {
     fn types(&self) -> Self::TypeRegistry;
     fn spec_name_version(&self) -> Result<SpecNameVersion, Self::MetaStructureError>;
     fn extrinsic(&self) -> Result<ExtrinsicMetadata<PortableForm>, Self::MetaStructureError>;
+
+    /// Pallet errors type for pallet with given index. Metadata without
+    /// pallet errors information has none.
+    fn pallet_error_ty(
+        &self,
+        _pallet_index: u8,
+    ) -> Result<Option<PalletErrorTy>, Self::MetaStructureError> {
+        Ok(None)
+    }
+
+    /// Storage entry with given name in pallet with given name. Metadata
+    /// without storage information has none.
+    fn storage_entry(
+        &self,
+        _pallet_name: &str,
+        _entry_name: &str,
+    ) -> Option<StorageEntryMetadata<PortableForm>> {
+        None
+    }
+
+    /// Constants of pallet with given name. Metadata without constants
+    /// information has none.
+    fn pallet_constants(
+        &self,
+        _pallet_name: &str,
+    ) -> Option<Vec<PalletConstantMetadata<PortableForm>>> {
+        None
+    }
+
+    /// Block header type. Metadata without header type in types registry has
+    /// none.
+    fn header_ty(&self) -> Option<UntrackedSymbol<TypeId>> {
+        None
+    }
+}
+
+/// Pallet name and the type describing pallet errors.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PalletErrorTy {
+    pub pallet_name: String,
+    pub ty: UntrackedSymbol<TypeId>,
 }
 
 #[repr(C)]
@@ -38,11 +92,26 @@ pub trait AsMetadata<E: ExternalMemory>: Debug + Sized {
 pub struct SpecNameVersion {
     pub printed_spec_version: String,
     pub spec_name: String,
+
+    /// Transaction version, if found in runtime version.
+    pub printed_tx_version: Option<String>,
+
+    /// Runtime implementation version, if found in runtime version.
+    pub impl_version: Option<u32>,
+
+    /// Runtime state version, if found in runtime version.
+    pub state_version: Option<u8>,
 }
 
 pub trait ResolveType<E: ExternalMemory> {
     fn resolve_ty(&self, id: u32, ext_memory: &mut E)
         -> Result<Type<PortableForm>, ParserError<E>>;
+
+    /// User-registered [`CustomTypes`], checked in decoding before the
+    /// built-in special types.
+    fn custom_types(&self) -> Option<&CustomTypes> {
+        None
+    }
 }
 
 impl<E: ExternalMemory> ResolveType<E> for PortableRegistry {
@@ -58,6 +127,27 @@ impl<E: ExternalMemory> ResolveType<E> for PortableRegistry {
     }
 }
 
+/// Types registry with attached user-registered [`CustomTypes`].
+#[derive(Debug)]
+pub struct RegistryWithCustomTypes<'a, R> {
+    pub registry: R,
+    pub custom_types: &'a CustomTypes,
+}
+
+impl<'a, E: ExternalMemory, R: ResolveType<E>> ResolveType<E> for RegistryWithCustomTypes<'a, R> {
+    fn resolve_ty(
+        &self,
+        id: u32,
+        ext_memory: &mut E,
+    ) -> Result<Type<PortableForm>, ParserError<E>> {
+        self.registry.resolve_ty(id, ext_memory)
+    }
+
+    fn custom_types(&self) -> Option<&CustomTypes> {
+        Some(self.custom_types)
+    }
+}
+
 impl<E: ExternalMemory> AsMetadata<E> for RuntimeMetadataV14 {
     type TypeRegistry = PortableRegistry;
 
@@ -68,7 +158,11 @@ impl<E: ExternalMemory> AsMetadata<E> for RuntimeMetadataV14 {
     }
 
     fn spec_name_version(&self) -> Result<SpecNameVersion, Self::MetaStructureError> {
-        let (value, ty) = runtime_version_data_and_ty(&self.pallets)?;
+        let (value, ty) = runtime_version_data_and_ty(
+            self.pallets
+                .iter()
+                .map(|pallet| (pallet.name.as_str(), pallet.constants.as_slice())),
+        )?;
         match decode_all_as_type::<&[u8], (), RuntimeMetadataV14>(
             &ty,
             &value.as_ref(),
@@ -83,17 +177,499 @@ impl<E: ExternalMemory> AsMetadata<E> for RuntimeMetadataV14 {
     fn extrinsic(&self) -> Result<ExtrinsicMetadata<PortableForm>, Self::MetaStructureError> {
         Ok(self.extrinsic.to_owned())
     }
+
+    fn pallet_error_ty(
+        &self,
+        pallet_index: u8,
+    ) -> Result<Option<PalletErrorTy>, Self::MetaStructureError> {
+        Ok(self
+            .pallets
+            .iter()
+            .find(|pallet| pallet.index == pallet_index)
+            .and_then(|pallet| {
+                pallet.error.as_ref().map(|error| PalletErrorTy {
+                    pallet_name: pallet.name.to_owned(),
+                    ty: error.ty,
+                })
+            }))
+    }
+
+    fn storage_entry(
+        &self,
+        pallet_name: &str,
+        entry_name: &str,
+    ) -> Option<StorageEntryMetadata<PortableForm>> {
+        storage_entry(
+            self.pallets
+                .iter()
+                .map(|pallet| (pallet.name.as_str(), pallet.storage.as_ref())),
+            pallet_name,
+            entry_name,
+        )
+    }
+
+    fn pallet_constants(
+        &self,
+        pallet_name: &str,
+    ) -> Option<Vec<PalletConstantMetadata<PortableForm>>> {
+        pallet_constants(
+            self.pallets
+                .iter()
+                .map(|pallet| (pallet.name.as_str(), pallet.constants.as_slice())),
+            pallet_name,
+        )
+    }
+
+    fn header_ty(&self) -> Option<UntrackedSymbol<TypeId>> {
+        header_ty(&self.types)
+    }
+}
+
+impl<E: ExternalMemory> AsMetadata<E> for RuntimeMetadataV15 {
+    type TypeRegistry = PortableRegistry;
+
+    type MetaStructureError = MetaVersionErrorPallets;
+
+    fn types(&self) -> Self::TypeRegistry {
+        self.types.to_owned()
+    }
+
+    fn spec_name_version(&self) -> Result<SpecNameVersion, Self::MetaStructureError> {
+        let (value, ty) = runtime_version_data_and_ty(
+            self.pallets
+                .iter()
+                .map(|pallet| (pallet.name.as_str(), pallet.constants.as_slice())),
+        )?;
+        match decode_all_as_type::<&[u8], (), RuntimeMetadataV15>(
+            &ty,
+            &value.as_ref(),
+            &mut (),
+            &self.types,
+        ) {
+            Ok(extended_data) => spec_name_version_from_runtime_version_data(extended_data.data),
+            Err(_) => Err(MetaVersionErrorPallets::RuntimeVersionNotDecodeable),
+        }
+    }
+
+    /// `V15` metadata has no unchecked extrinsic type in
+    /// [`ExtrinsicMetadataV15`], only the types of its parts. The unchecked
+    /// extrinsic type with exactly these parts as type parameters is found in
+    /// the types registry, so that the extrinsic metadata could be processed
+    /// identically for all metadata versions.
+    fn extrinsic(&self) -> Result<ExtrinsicMetadata<PortableForm>, Self::MetaStructureError> {
+        let ty = unchecked_extrinsic_ty(&self.types, &self.extrinsic)?;
+        let signed_extensions = self
+            .extrinsic
+            .signed_extensions
+            .iter()
+            .map(|signed_extension| SignedExtensionMetadata {
+                identifier: signed_extension.identifier.to_owned(),
+                ty: signed_extension.ty,
+                additional_signed: signed_extension.additional_signed,
+            })
+            .collect();
+        Ok(ExtrinsicMetadata {
+            ty,
+            version: self.extrinsic.version,
+            signed_extensions,
+        })
+    }
+
+    /// In `V15` metadata pallet error types are found in the outer error
+    /// enum, with variant index matching the pallet index.
+    fn pallet_error_ty(
+        &self,
+        pallet_index: u8,
+    ) -> Result<Option<PalletErrorTy>, Self::MetaStructureError> {
+        let error_enum = self
+            .types
+            .resolve(self.outer_enums.error_enum_ty.id)
+            .ok_or(MetaVersionErrorPallets::UnexpectedErrorEnumFormat)?;
+        if let TypeDef::Variant(type_def_variant) = &error_enum.type_def {
+            match type_def_variant
+                .variants
+                .iter()
+                .find(|variant| variant.index == pallet_index)
+            {
+                Some(variant) => {
+                    if variant.fields.len() == 1 {
+                        Ok(Some(PalletErrorTy {
+                            pallet_name: variant.name.to_owned(),
+                            ty: variant.fields[0].ty,
+                        }))
+                    } else {
+                        Err(MetaVersionErrorPallets::UnexpectedErrorEnumFormat)
+                    }
+                }
+                None => Ok(None),
+            }
+        } else {
+            Err(MetaVersionErrorPallets::UnexpectedErrorEnumFormat)
+        }
+    }
+
+    fn storage_entry(
+        &self,
+        pallet_name: &str,
+        entry_name: &str,
+    ) -> Option<StorageEntryMetadata<PortableForm>> {
+        storage_entry(
+            self.pallets
+                .iter()
+                .map(|pallet| (pallet.name.as_str(), pallet.storage.as_ref())),
+            pallet_name,
+            entry_name,
+        )
+    }
+
+    fn pallet_constants(
+        &self,
+        pallet_name: &str,
+    ) -> Option<Vec<PalletConstantMetadata<PortableForm>>> {
+        pallet_constants(
+            self.pallets
+                .iter()
+                .map(|pallet| (pallet.name.as_str(), pallet.constants.as_slice())),
+            pallet_name,
+        )
+    }
+
+    fn header_ty(&self) -> Option<UntrackedSymbol<TypeId>> {
+        header_ty(&self.types)
+    }
+}
+
+/// Metadata of any supported version, as decoded from
+/// [`RuntimeMetadataPrefixed`](frame_metadata::RuntimeMetadataPrefixed)
+/// SCALE-encoded data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MetadataVersioned {
+    V14(RuntimeMetadataV14),
+    V15(RuntimeMetadataV15),
+}
+
+/// Length of `META` magic prefix, as encoded `u32`.
+const MAGIC_LENGTH: usize = 4;
+
+impl MetadataVersioned {
+    /// Decode `RuntimeMetadataPrefixed` SCALE-encoded data.
+    ///
+    /// Data must start with `META` magic prefix, followed by metadata version
+    /// index and the metadata itself. All data is expected to be used.
+    pub fn from_prefixed_bytes(data: &[u8]) -> Result<Self, MetadataPrefixedError> {
+        if data.len() <= MAGIC_LENGTH {
+            return Err(MetadataPrefixedError::TooShort);
+        }
+        let magic = u32::from_le_bytes(
+            data[..MAGIC_LENGTH]
+                .try_into()
+                .expect("static length, always fits"),
+        );
+        if magic != META_RESERVED {
+            return Err(MetadataPrefixedError::WrongMagic { found: magic });
+        }
+        let version = data[MAGIC_LENGTH];
+        let mut metadata_data = &data[MAGIC_LENGTH + 1..];
+        let metadata = match version {
+            14 => RuntimeMetadataV14::decode(&mut metadata_data)
+                .map(MetadataVersioned::V14)
+                .map_err(|_| MetadataPrefixedError::MetadataNotDecodeable { version })?,
+            15 => RuntimeMetadataV15::decode(&mut metadata_data)
+                .map(MetadataVersioned::V15)
+                .map_err(|_| MetadataPrefixedError::MetadataNotDecodeable { version })?,
+            _ => return Err(MetadataPrefixedError::UnsupportedVersion(version)),
+        };
+        if !metadata_data.is_empty() {
+            return Err(MetadataPrefixedError::SomeDataNotUsed {
+                from: data.len() - metadata_data.len(),
+            });
+        }
+        Ok(metadata)
+    }
+
+    /// Decode hexadecimal `RuntimeMetadataPrefixed` SCALE-encoded data, such
+    /// as the one fetched through `state_getMetadata` rpc query.
+    ///
+    /// Hexadecimal string could be with or without `0x` prefix. Leading and
+    /// trailing whitespace is ignored.
+    pub fn from_prefixed_hex(hex_data: &str) -> Result<Self, MetadataPrefixedError> {
+        let hex_data = hex_data.trim();
+        let hex_data = hex_data.strip_prefix("0x").unwrap_or(hex_data);
+        let data = hex::decode(hex_data).map_err(|_| MetadataPrefixedError::NotHex)?;
+        Self::from_prefixed_bytes(&data)
+    }
+
+    /// Metadata version.
+    pub fn version(&self) -> u8 {
+        match &self {
+            MetadataVersioned::V14(_) => 14,
+            MetadataVersioned::V15(_) => 15,
+        }
+    }
 }
 
-fn runtime_version_data_and_ty(
-    pallets: &[PalletMetadata<PortableForm>],
-) -> Result<(Vec<u8>, UntrackedSymbol<TypeId>), MetaVersionErrorPallets> {
+impl<E: ExternalMemory> AsMetadata<E> for MetadataVersioned {
+    type TypeRegistry = PortableRegistry;
+
+    type MetaStructureError = MetaVersionErrorPallets;
+
+    fn types(&self) -> Self::TypeRegistry {
+        match &self {
+            MetadataVersioned::V14(meta_v14) => {
+                <RuntimeMetadataV14 as AsMetadata<E>>::types(meta_v14)
+            }
+            MetadataVersioned::V15(meta_v15) => {
+                <RuntimeMetadataV15 as AsMetadata<E>>::types(meta_v15)
+            }
+        }
+    }
+
+    fn spec_name_version(&self) -> Result<SpecNameVersion, Self::MetaStructureError> {
+        match &self {
+            MetadataVersioned::V14(meta_v14) => {
+                <RuntimeMetadataV14 as AsMetadata<E>>::spec_name_version(meta_v14)
+            }
+            MetadataVersioned::V15(meta_v15) => {
+                <RuntimeMetadataV15 as AsMetadata<E>>::spec_name_version(meta_v15)
+            }
+        }
+    }
+
+    fn extrinsic(&self) -> Result<ExtrinsicMetadata<PortableForm>, Self::MetaStructureError> {
+        match &self {
+            MetadataVersioned::V14(meta_v14) => {
+                <RuntimeMetadataV14 as AsMetadata<E>>::extrinsic(meta_v14)
+            }
+            MetadataVersioned::V15(meta_v15) => {
+                <RuntimeMetadataV15 as AsMetadata<E>>::extrinsic(meta_v15)
+            }
+        }
+    }
+
+    fn pallet_error_ty(
+        &self,
+        pallet_index: u8,
+    ) -> Result<Option<PalletErrorTy>, Self::MetaStructureError> {
+        match &self {
+            MetadataVersioned::V14(meta_v14) => {
+                <RuntimeMetadataV14 as AsMetadata<E>>::pallet_error_ty(meta_v14, pallet_index)
+            }
+            MetadataVersioned::V15(meta_v15) => {
+                <RuntimeMetadataV15 as AsMetadata<E>>::pallet_error_ty(meta_v15, pallet_index)
+            }
+        }
+    }
+
+    fn storage_entry(
+        &self,
+        pallet_name: &str,
+        entry_name: &str,
+    ) -> Option<StorageEntryMetadata<PortableForm>> {
+        match &self {
+            MetadataVersioned::V14(meta_v14) => {
+                <RuntimeMetadataV14 as AsMetadata<E>>::storage_entry(
+                    meta_v14,
+                    pallet_name,
+                    entry_name,
+                )
+            }
+            MetadataVersioned::V15(meta_v15) => {
+                <RuntimeMetadataV15 as AsMetadata<E>>::storage_entry(
+                    meta_v15,
+                    pallet_name,
+                    entry_name,
+                )
+            }
+        }
+    }
+
+    fn pallet_constants(
+        &self,
+        pallet_name: &str,
+    ) -> Option<Vec<PalletConstantMetadata<PortableForm>>> {
+        match &self {
+            MetadataVersioned::V14(meta_v14) => {
+                <RuntimeMetadataV14 as AsMetadata<E>>::pallet_constants(meta_v14, pallet_name)
+            }
+            MetadataVersioned::V15(meta_v15) => {
+                <RuntimeMetadataV15 as AsMetadata<E>>::pallet_constants(meta_v15, pallet_name)
+            }
+        }
+    }
+
+    fn header_ty(&self) -> Option<UntrackedSymbol<TypeId>> {
+        match &self {
+            MetadataVersioned::V14(meta_v14) => {
+                <RuntimeMetadataV14 as AsMetadata<E>>::header_ty(meta_v14)
+            }
+            MetadataVersioned::V15(meta_v15) => {
+                <RuntimeMetadataV15 as AsMetadata<E>>::header_ty(meta_v15)
+            }
+        }
+    }
+}
+
+/// Metadata with attached user-registered [`CustomTypes`].
+///
+/// Could be used in all parser entry points instead of the bare metadata, so
+/// that values of matched custom types are decoded into
+/// [`ParsedData::Custom`](crate::cards::ParsedData::Custom).
+#[derive(Debug)]
+pub struct MetadataWithCustomTypes<'a, M> {
+    pub metadata: &'a M,
+    pub custom_types: &'a CustomTypes,
+}
+
+impl<'a, E: ExternalMemory, M: AsMetadata<E>> AsMetadata<E> for MetadataWithCustomTypes<'a, M> {
+    type TypeRegistry = RegistryWithCustomTypes<'a, M::TypeRegistry>;
+
+    type MetaStructureError = M::MetaStructureError;
+
+    fn types(&self) -> Self::TypeRegistry {
+        RegistryWithCustomTypes {
+            registry: self.metadata.types(),
+            custom_types: self.custom_types,
+        }
+    }
+
+    fn spec_name_version(&self) -> Result<SpecNameVersion, Self::MetaStructureError> {
+        self.metadata.spec_name_version()
+    }
+
+    fn extrinsic(&self) -> Result<ExtrinsicMetadata<PortableForm>, Self::MetaStructureError> {
+        self.metadata.extrinsic()
+    }
+
+    fn pallet_error_ty(
+        &self,
+        pallet_index: u8,
+    ) -> Result<Option<PalletErrorTy>, Self::MetaStructureError> {
+        self.metadata.pallet_error_ty(pallet_index)
+    }
+
+    fn storage_entry(
+        &self,
+        pallet_name: &str,
+        entry_name: &str,
+    ) -> Option<StorageEntryMetadata<PortableForm>> {
+        self.metadata.storage_entry(pallet_name, entry_name)
+    }
+
+    fn pallet_constants(
+        &self,
+        pallet_name: &str,
+    ) -> Option<Vec<PalletConstantMetadata<PortableForm>>> {
+        self.metadata.pallet_constants(pallet_name)
+    }
+
+    fn header_ty(&self) -> Option<UntrackedSymbol<TypeId>> {
+        self.metadata.header_ty()
+    }
+}
+
+/// Find in types registry the unchecked extrinsic type matching the `V15`
+/// extrinsic metadata.
+///
+/// Unchecked extrinsic type is recognized by its [`Path`](scale_info::Path),
+/// its address, call, signature and extra type parameters must be identical to
+/// the ones declared in [`ExtrinsicMetadataV15`].
+fn unchecked_extrinsic_ty(
+    types: &PortableRegistry,
+    extrinsic: &ExtrinsicMetadataV15<PortableForm>,
+) -> Result<UntrackedSymbol<TypeId>, MetaVersionErrorPallets> {
+    for portable_type in types.types.iter() {
+        if let SpecialtyTypeHinted::UncheckedExtrinsic =
+            SpecialtyTypeHinted::from_type(&portable_type.ty)
+        {
+            let mut found_address = None;
+            let mut found_signature = None;
+            let mut found_extra = None;
+            let mut found_call = None;
+            for param in portable_type.ty.type_params.iter() {
+                match param.name.as_str() {
+                    ADDRESS_INDICATOR => found_address = param.ty.map(|ty| ty.id),
+                    SIGNATURE_INDICATOR => found_signature = param.ty.map(|ty| ty.id),
+                    EXTRA_INDICATOR => found_extra = param.ty.map(|ty| ty.id),
+                    CALL_INDICATOR => found_call = param.ty.map(|ty| ty.id),
+                    _ => (),
+                }
+            }
+            if found_address == Some(extrinsic.address_ty.id)
+                && found_signature == Some(extrinsic.signature_ty.id)
+                && found_extra == Some(extrinsic.extra_ty.id)
+                && found_call == Some(extrinsic.call_ty.id)
+            {
+                return Ok(portable_type.id.into());
+            }
+        }
+    }
+    Err(MetaVersionErrorPallets::NoUncheckedExtrinsicType)
+}
+
+/// Find in types registry the block header type.
+///
+/// Block header type is recognized by its [`Path`](scale_info::Path).
+fn header_ty(types: &PortableRegistry) -> Option<UntrackedSymbol<TypeId>> {
+    types
+        .types
+        .iter()
+        .find(|portable_type| portable_type.ty.path.segments == HEADER_PATH)
+        .map(|portable_type| portable_type.id.into())
+}
+
+/// Find storage entry metadata by pallet name and entry name.
+fn storage_entry<'a, I>(
+    pallets: I,
+    pallet_name: &str,
+    entry_name: &str,
+) -> Option<StorageEntryMetadata<PortableForm>>
+where
+    I: Iterator<Item = (&'a str, Option<&'a PalletStorageMetadata<PortableForm>>)>,
+{
+    for (found_pallet_name, storage) in pallets {
+        if found_pallet_name == pallet_name {
+            return storage?
+                .entries
+                .iter()
+                .find(|entry| entry.name == entry_name)
+                .cloned();
+        }
+    }
+    None
+}
+
+/// Find constants metadata of a pallet by pallet name.
+fn pallet_constants<'a, I>(
+    pallets: I,
+    pallet_name: &str,
+) -> Option<Vec<PalletConstantMetadata<PortableForm>>>
+where
+    I: Iterator<Item = (&'a str, &'a [PalletConstantMetadata<PortableForm>])>,
+{
+    for (found_pallet_name, constants) in pallets {
+        if found_pallet_name == pallet_name {
+            return Some(constants.to_vec());
+        }
+    }
+    None
+}
+
+/// Find `Version` constant in `System` pallet, get its encoded value and type.
+///
+/// Pallets are provided as a set of pallet names with corresponding constants,
+/// so that pallets from all supported metadata versions could be processed.
+fn runtime_version_data_and_ty<'a, I>(
+    pallets: I,
+) -> Result<(Vec<u8>, UntrackedSymbol<TypeId>), MetaVersionErrorPallets>
+where
+    I: Iterator<Item = (&'a str, &'a [PalletConstantMetadata<PortableForm>])>,
+{
     let mut runtime_version_data_and_ty = None;
     let mut system_block = false;
-    for pallet in pallets.iter() {
-        if pallet.name == "System" {
+    for (pallet_name, constants) in pallets {
+        if pallet_name == "System" {
             system_block = true;
-            for constant in pallet.constants.iter() {
+            for constant in constants.iter() {
                 if constant.name == "Version" {
                     runtime_version_data_and_ty = Some((constant.value.to_vec(), constant.ty))
                 }
@@ -112,6 +688,9 @@ fn spec_name_version_from_runtime_version_data(
 ) -> Result<SpecNameVersion, MetaVersionErrorPallets> {
     let mut printed_spec_version = None;
     let mut spec_name = None;
+    let mut printed_tx_version = None;
+    let mut impl_version = None;
+    let mut state_version = None;
 
     if let ParsedData::Composite(fields) = parsed_data {
         for field in fields.iter() {
@@ -176,8 +755,23 @@ fn spec_name_version_from_runtime_version_data(
                         return Err(MetaVersionErrorPallets::SpecNameIdentifierTwice);
                     }
                 }
+                ParsedData::PrimitiveU32 { value, .. } if matches!(field.field_name.as_deref(), Some(a) if IMPL_VERSION_ID_SET.contains(&a)) => {
+                    impl_version = Some(*value)
+                }
+                ParsedData::PrimitiveU8 { value, .. } if matches!(field.field_name.as_deref(), Some(a) if STATE_VERSION_ID_SET.contains(&a)) => {
+                    state_version = Some(*value)
+                }
                 _ => (),
             }
+            if let Some(value) =
+                printed_unsigned(&field.data.data, SpecialtyUnsignedInteger::TxVersion)
+            {
+                if printed_tx_version.is_none() {
+                    printed_tx_version = Some(value)
+                } else {
+                    return Err(MetaVersionErrorPallets::TxVersionIdentifierTwice);
+                }
+            }
         }
     } else {
         return Err(MetaVersionErrorPallets::UnexpectedRuntimeVersionFormat);
@@ -193,5 +787,39 @@ fn spec_name_version_from_runtime_version_data(
     Ok(SpecNameVersion {
         printed_spec_version,
         spec_name,
+        printed_tx_version,
+        impl_version,
+        state_version,
     })
 }
+
+/// Printed value of unsigned integer with given specialty, if `ParsedData` is
+/// such an integer.
+fn printed_unsigned(
+    parsed_data: &ParsedData,
+    specialty: SpecialtyUnsignedInteger,
+) -> Option<String> {
+    match parsed_data {
+        ParsedData::PrimitiveU8 {
+            value,
+            specialty: found,
+        } if *found == specialty => Some(value.to_string()),
+        ParsedData::PrimitiveU16 {
+            value,
+            specialty: found,
+        } if *found == specialty => Some(value.to_string()),
+        ParsedData::PrimitiveU32 {
+            value,
+            specialty: found,
+        } if *found == specialty => Some(value.to_string()),
+        ParsedData::PrimitiveU64 {
+            value,
+            specialty: found,
+        } if *found == specialty => Some(value.to_string()),
+        ParsedData::PrimitiveU128 {
+            value,
+            specialty: found,
+        } if *found == specialty => Some(value.to_string()),
+        _ => None,
+    }
+}
@@ -1,4 +1,4 @@
-//! Decode unchecked extrinsics, signed or unsigned.
+//! Decode unchecked extrinsics, signed, unsigned, or general.
 //!
 //! Here in decoding it is assumed that the unchecked extrinsic is an encoded
 //! opaque `Vec<u8>`, its general structure described
@@ -34,46 +34,116 @@
 //!   </tr>
 //! </table>
 //!
-//! Signed and unsigned unchecked extrinsics are differentiated by version byte.
-//! The first bit in the version byte is `0` if the extrinsic is unsigned and
-//! `1` if the extrinsic is signed. Other 7 bits must match the extrinsic
-//! `version` from [`ExtrinsicMetadata`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/v14/struct.ExtrinsicMetadata.html).
-//! Currently the `version` has a constant value of `4` (see
-//! [`EXTRINSIC_FORMAT_VERSION`](https://docs.rs/sp-runtime/9.0.0/src/sp_runtime/generic/unchecked_extrinsic.rs.html#39)
-//! in `sp_runtime`, thus version byte is `0x04` for unsigned extrinsics and
+//! General unchecked extrinsic structure (extrinsic format version 5):
+//!
+//! <table>
+//!   <tr>
+//!     <td>compact length of whole extrinsic</td>
+//!     <td>version byte</td>
+//!     <td>extension version byte</td>
+//!     <td>extensions data</td>
+//!     <td>call</td>
+//!   </tr>
+//! </table>
+//!
+//! Unchecked extrinsic types are differentiated by version byte. Last 6 bits
+//! of the version byte are the extrinsic format version, first 2 bits are the
+//! extrinsic type.
+//!
+//! For format version `4` the first bit in the version byte is `0` if the
+//! extrinsic is unsigned and `1` if the extrinsic is signed, and the version
+//! must match the extrinsic `version` from [`ExtrinsicMetadata`](https://docs.rs/frame-metadata/15.0.0/frame_metadata/v14/struct.ExtrinsicMetadata.html)
+//! (see [`EXTRINSIC_FORMAT_VERSION`](https://docs.rs/sp-runtime/9.0.0/src/sp_runtime/generic/unchecked_extrinsic.rs.html#39)
+//! in `sp_runtime`), thus version byte is `0x04` for unsigned extrinsics and
 //! `0x84` for signed extrinsics.
+//!
+//! Format version `5` has bare extrinsics with version byte `0x05`, decoded
+//! same as unsigned ones, and general extrinsics with version byte `0x45`,
+//! which carry extensions but no signature. Metadata `V14` and `V15` declare
+//! version `4` even for chains that accept version `5` extrinsics, so version
+//! `5` extrinsics are accepted regardless of the metadata version. Other
+//! format versions are not supported.
+//!
+//! Positions of the call and the extra data in the input are recorded during
+//! decoding, so that the extrinsic hash and the call hash are calculated for
+//! the original input bytes. Signed payload, i.e. the exact data
+//! that gets signed, is assembled from the original call and extra data bytes,
+//! and the `additional_signed` data, which is not a part of the extrinsic and
+//! is built from the caller-provided [`AdditionalSignedInfo`]. With `std` feature,
+//! signature of decoded signed extrinsic could be verified with
+//! [`UncheckedExtrinsic::verify_signature`].
 #[cfg(feature = "std")]
-use std::cmp::Ordering;
+use std::{any::TypeId, cmp::Ordering, ops::Range};
 
 #[cfg(not(feature = "std"))]
-use core::cmp::Ordering;
+use core::{any::TypeId, cmp::Ordering, ops::Range};
 
 use external_memory_tools::{AddressableBuffer, BufferError, ExternalMemory};
+use parity_scale_codec::Encode;
+use primitive_types::H256;
+use scale_info::interner::UntrackedSymbol;
+#[cfg(feature = "std")]
+use sp_core::{ed25519, sr25519, Pair};
+use sp_core_hashing::blake2_256;
+#[cfg(feature = "std")]
+use sp_runtime::generic::Era;
+
+use crate::std::{borrow::ToOwned, string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use crate::additional_types::Era;
 
 use crate::cards::{Call, ExtendedData, ParsedData};
 use crate::compacts::get_compact;
 use crate::decode_as_type_at_position;
 use crate::decoding_sci::{extrinsic_type_params, CALL_INDICATOR};
-use crate::error::{ParserError, UncheckedExtrinsicError};
+use crate::error::{ParserError, SignatureError, UncheckedExtrinsicError};
+use crate::skipping::fixed_encoded_len;
+use crate::special_indicators::{
+    CHECK_GENESIS, CHECK_MORTALITY, CHECK_SPEC_VERSION, CHECK_TX_VERSION,
+};
 use crate::traits::AsMetadata;
 
 /// Length of version indicator, 1 byte.
 const VERSION_LENGTH: usize = 1;
 
-/// Version byte mask, to separate version and signed/unsigned information.
-const VERSION_MASK: u8 = 0b0111_1111;
+/// Version byte mask, to separate extrinsic format version and extrinsic type
+/// information.
+const VERSION_MASK: u8 = 0b0011_1111;
+
+/// Version byte mask for extrinsic type bits.
+const TYPE_MASK: u8 = 0b1100_0000;
+
+/// Extrinsic type bits for bare (unsigned) extrinsic.
+const TYPE_BARE: u8 = 0b0000_0000;
+
+/// Extrinsic type bits for signed extrinsic, extrinsic format version 4 only.
+const TYPE_SIGNED: u8 = 0b1000_0000;
 
-/// Version value for unsigned extrinsic, after `VERSION_MASK` is applied.
-const VERSION_UNSIGNED: u8 = 0;
+/// Extrinsic type bits for general extrinsic, extrinsic format version 5 only.
+const TYPE_GENERAL: u8 = 0b0100_0000;
+
+/// Extrinsic format version 4, with signed and unsigned extrinsics.
+const EXTRINSIC_VERSION_4: u8 = 4;
+
+/// Extrinsic format version 5, with bare and general extrinsics.
+///
+/// Metadata `V14` and `V15` could declare only a single extrinsic version,
+/// and the chains supporting version 5 still declare version 4 there.
+const EXTRINSIC_VERSION_5: u8 = 5;
 
 /// [`TypeParameter`](scale_info::TypeParameter) name for `address`.
-const ADDRESS_INDICATOR: &str = "Address";
+pub const ADDRESS_INDICATOR: &str = "Address";
 
 /// [`TypeParameter`](scale_info::TypeParameter) name for `signature`.
-const SIGNATURE_INDICATOR: &str = "Signature";
+pub const SIGNATURE_INDICATOR: &str = "Signature";
 
 /// [`TypeParameter`](scale_info::TypeParameter) name for `extra`.
-const EXTRA_INDICATOR: &str = "Extra";
+pub const EXTRA_INDICATOR: &str = "Extra";
+
+/// Maximum length of signed payload that is signed as is. Longer payloads are
+/// `blake2_256`-hashed before signing.
+pub const MAX_SIGNED_PAYLOAD_LEN: usize = 256;
 
 /// Decode an unchecked extrinsic.
 pub fn decode_as_unchecked_extrinsic<B, E, M>(
@@ -122,7 +192,7 @@ where
 
     let mut position = extrinsic_start;
 
-    // version byte from extrinsic, to diffirentiate signed and unsigned extrinsics
+    // version byte from extrinsic, to diffirentiate extrinsic types
     let version_byte = input
         .read_byte(ext_memory, position)
         .map_err(|e| UncheckedExtrinsicError::Parsing(ParserError::Buffer(e)))?;
@@ -130,118 +200,489 @@ where
 
     let version = extrinsic.version;
 
-    // First bit of `version_byte` is `0` if the transaction is unsigned and `1` if the transaction is signed.
-    // Other 7 bits must match the `version` from the metadata.
-    if version_byte & VERSION_MASK != version {
-        Err(UncheckedExtrinsicError::VersionMismatch {
-            version_byte,
-            version,
-        })
-    } else if version_byte & !VERSION_MASK == VERSION_UNSIGNED {
-        let mut found_call = None;
+    let mut found_address = None;
+    let mut found_signature = None;
+    let mut found_extra = None;
+    let mut found_call = None;
 
-        // Need the call parameter from unchecked extrinsic parameters.
-        for param in extrinsic_type_params.iter() {
-            if param.name == CALL_INDICATOR {
-                found_call = param.ty
-            }
+    // Unchecked extrinsic parameters typically contain address, signature,
+    // extensions, and call. Only the call is needed for unsigned extrinsics.
+    for param in extrinsic_type_params.iter() {
+        match param.name.as_str() {
+            ADDRESS_INDICATOR => found_address = param.ty,
+            SIGNATURE_INDICATOR => found_signature = param.ty,
+            EXTRA_INDICATOR => found_extra = param.ty,
+            CALL_INDICATOR => found_call = param.ty,
+            _ => (),
         }
+    }
 
-        let call_ty = found_call.ok_or(UncheckedExtrinsicError::Parsing(
-            ParserError::ExtrinsicNoCallParam,
-        ))?;
-        let call_extended_data = decode_as_type_at_position::<B, E, M>(
-            &call_ty,
-            input,
-            ext_memory,
-            &meta_v14_types,
-            &mut position,
-        )?;
-        if let ParsedData::Call(call) = call_extended_data.data {
-            Ok(UncheckedExtrinsic::Unsigned { call })
-        } else {
-            Err(UncheckedExtrinsicError::UnexpectedCallTy {
-                call_ty_id: call_ty.id,
+    // Last 6 bits of `version_byte` are the extrinsic format version, first 2
+    // bits are the extrinsic type. Version 4 extrinsics are either unsigned
+    // or signed, and must match the `version` from the metadata. Version 5
+    // extrinsics are either bare or general.
+    let extrinsic_version = version_byte & VERSION_MASK;
+    if extrinsic_version != EXTRINSIC_VERSION_4 && extrinsic_version != EXTRINSIC_VERSION_5 {
+        return Err(UncheckedExtrinsicError::UnsupportedVersion { version_byte });
+    }
+    if extrinsic_version != version && extrinsic_version != EXTRINSIC_VERSION_5 {
+        return Err(UncheckedExtrinsicError::VersionMismatch {
+            version_byte,
+            version,
+        });
+    }
+    match (extrinsic_version, version_byte & TYPE_MASK) {
+        (_, TYPE_BARE) => {
+            let call_ty = found_call.ok_or(UncheckedExtrinsicError::Parsing(
+                ParserError::ExtrinsicNoCallParam,
+            ))?;
+            let call_start = position;
+            let call = decode_call::<B, E, M>(
+                &call_ty,
+                input,
+                ext_memory,
+                &meta_v14_types,
+                &mut position,
+            )?;
+            Ok(UncheckedExtrinsic::Unsigned {
+                call,
+                call_range: call_start..position,
             })
         }
-    } else {
-        let mut found_address = None;
-        let mut found_signature = None;
-        let mut found_extra = None;
-        let mut found_call = None;
-
-        // Unchecked extrinsic parameters typically contain address, signature,
-        // and extensions. Expect to find all entries.
-        for param in extrinsic_type_params.iter() {
-            match param.name.as_str() {
-                ADDRESS_INDICATOR => found_address = param.ty,
-                SIGNATURE_INDICATOR => found_signature = param.ty,
-                EXTRA_INDICATOR => found_extra = param.ty,
-                CALL_INDICATOR => found_call = param.ty,
-                _ => (),
-            }
-        }
+        (EXTRINSIC_VERSION_4, TYPE_SIGNED) => {
+            let address_ty = found_address.ok_or(UncheckedExtrinsicError::NoAddressParam)?;
+            let address = decode_as_type_at_position::<B, E, M>(
+                &address_ty,
+                input,
+                ext_memory,
+                &meta_v14_types,
+                &mut position,
+            )?;
+
+            let signature_ty = found_signature.ok_or(UncheckedExtrinsicError::NoSignatureParam)?;
+            let signature = decode_as_type_at_position::<B, E, M>(
+                &signature_ty,
+                input,
+                ext_memory,
+                &meta_v14_types,
+                &mut position,
+            )?;
 
-        let address_ty = found_address.ok_or(UncheckedExtrinsicError::NoAddressParam)?;
-        let address = decode_as_type_at_position::<B, E, M>(
-            &address_ty,
-            input,
-            ext_memory,
-            &meta_v14_types,
-            &mut position,
-        )?;
-
-        let signature_ty = found_signature.ok_or(UncheckedExtrinsicError::NoSignatureParam)?;
-        let signature = decode_as_type_at_position::<B, E, M>(
-            &signature_ty,
-            input,
-            ext_memory,
-            &meta_v14_types,
-            &mut position,
-        )?;
-
-        let extra_ty = found_extra.ok_or(UncheckedExtrinsicError::NoExtraParam)?;
-        let extra = decode_as_type_at_position::<B, E, M>(
-            &extra_ty,
-            input,
-            ext_memory,
-            &meta_v14_types,
-            &mut position,
-        )?;
-
-        let call_ty = found_call.ok_or(UncheckedExtrinsicError::NoCallParam)?;
-        let call_extended_data = decode_as_type_at_position::<B, E, M>(
-            &call_ty,
-            input,
-            ext_memory,
-            &meta_v14_types,
-            &mut position,
-        )?;
-        if let ParsedData::Call(call) = call_extended_data.data {
+            let extra_ty = found_extra.ok_or(UncheckedExtrinsicError::NoExtraParam)?;
+            let extra_start = position;
+            let extra = decode_as_type_at_position::<B, E, M>(
+                &extra_ty,
+                input,
+                ext_memory,
+                &meta_v14_types,
+                &mut position,
+            )?;
+            let extra_range = extra_start..position;
+
+            let call_ty = found_call.ok_or(UncheckedExtrinsicError::NoCallParam)?;
+            let call_start = position;
+            let call = decode_call::<B, E, M>(
+                &call_ty,
+                input,
+                ext_memory,
+                &meta_v14_types,
+                &mut position,
+            )?;
             Ok(UncheckedExtrinsic::Signed {
                 address,
                 signature,
                 extra,
                 call,
+                extra_range,
+                call_range: call_start..position,
             })
-        } else {
-            Err(UncheckedExtrinsicError::UnexpectedCallTy {
-                call_ty_id: call_ty.id,
+        }
+        (EXTRINSIC_VERSION_5, TYPE_GENERAL) => {
+            let extension_version = input
+                .read_byte(ext_memory, position)
+                .map_err(|e| UncheckedExtrinsicError::Parsing(ParserError::Buffer(e)))?;
+            position += VERSION_LENGTH;
+
+            let extra_ty = found_extra.ok_or(UncheckedExtrinsicError::NoExtraParam)?;
+            let extensions = decode_as_type_at_position::<B, E, M>(
+                &extra_ty,
+                input,
+                ext_memory,
+                &meta_v14_types,
+                &mut position,
+            )?;
+
+            let call_ty = found_call.ok_or(UncheckedExtrinsicError::NoCallParam)?;
+            let call_start = position;
+            let call = decode_call::<B, E, M>(
+                &call_ty,
+                input,
+                ext_memory,
+                &meta_v14_types,
+                &mut position,
+            )?;
+            Ok(UncheckedExtrinsic::General {
+                extension_version,
+                extensions,
+                call,
+                call_range: call_start..position,
             })
         }
+        _ => Err(UncheckedExtrinsicError::UnsupportedVersion { version_byte }),
+    }
+}
+
+/// Decode call part of the unchecked extrinsic.
+fn decode_call<B, E, M>(
+    call_ty: &UntrackedSymbol<TypeId>,
+    input: &B,
+    ext_memory: &mut E,
+    meta_v14_types: &M::TypeRegistry,
+    position: &mut usize,
+) -> Result<Call, UncheckedExtrinsicError<E, M>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let call_extended_data = decode_as_type_at_position::<B, E, M>(
+        call_ty,
+        input,
+        ext_memory,
+        meta_v14_types,
+        position,
+    )?;
+    if let ParsedData::Call(call) = call_extended_data.data {
+        Ok(call)
+    } else {
+        Err(UncheckedExtrinsicError::UnexpectedCallTy {
+            call_ty_id: call_ty.id,
+        })
     }
 }
 
 /// Decoded unchecked extrinsic.
 #[derive(Debug, Eq, PartialEq)]
 pub enum UncheckedExtrinsic {
+    /// Signed extrinsic of format version 4.
+    ///
+    /// Ranges are the positions of the extra data and the call in the decoded
+    /// input.
     Signed {
         address: ExtendedData,
         signature: ExtendedData,
         extra: ExtendedData,
         call: Call,
+        extra_range: Range<usize>,
+        call_range: Range<usize>,
     },
+    /// Unsigned extrinsic, or bare extrinsic of format version 5.
+    ///
+    /// Range is the position of the call in the decoded input.
     Unsigned {
         call: Call,
+        call_range: Range<usize>,
     },
+    /// General extrinsic of format version 5, with extensions but without
+    /// signature.
+    ///
+    /// Range is the position of the call in the decoded input.
+    General {
+        extension_version: u8,
+        extensions: ExtendedData,
+        call: Call,
+        call_range: Range<usize>,
+    },
+}
+
+/// Chain data for `additional_signed` part of the signed extensions.
+///
+/// `additional_signed` data is not included in the unchecked extrinsic, but is
+/// a part of the signed payload.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdditionalSignedInfo {
+    /// Chain genesis hash.
+    pub genesis_hash: H256,
+
+    /// Hash of the block the mortality period starts at. Needed only for
+    /// mortal extrinsics, immortal extrinsics use genesis hash instead.
+    pub block_hash: Option<H256>,
+
+    /// Runtime spec version.
+    pub spec_version: u32,
+
+    /// Transaction version.
+    pub tx_version: u32,
+
+    /// Encoded `additional_signed` data for other signed extensions, e.g.
+    /// chain-specific ones.
+    pub other: Vec<ExtensionAdditionalSigned>,
+}
+
+/// Encoded `additional_signed` data for a signed extension.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExtensionAdditionalSigned {
+    /// Signed extension identifier, as in the metadata.
+    pub identifier: String,
+
+    /// SCALE-encoded `additional_signed` data.
+    pub encoded: Vec<u8>,
+}
+
+impl UncheckedExtrinsic {
+    /// `blake2_256` hash of the SCALE-encoded unchecked extrinsic, including
+    /// the compact length prefix.
+    ///
+    /// Hash is calculated for the extrinsic bytes of the `input` the extrinsic
+    /// was decoded from. Extrinsic starts at the beginning of the `input` and
+    /// ends with the call.
+    pub fn extrinsic_hash<B, E>(
+        &self,
+        input: &B,
+        ext_memory: &mut E,
+    ) -> Result<H256, ParserError<E>>
+    where
+        B: AddressableBuffer<E>,
+        E: ExternalMemory,
+    {
+        let encoded = read_range::<B, E>(input, ext_memory, &(0..self.call_range().end))?;
+        Ok(H256(blake2_256(&encoded)))
+    }
+
+    /// `blake2_256` hash of the SCALE-encoded call.
+    ///
+    /// Hash is calculated for the call bytes of the `input` the extrinsic was
+    /// decoded from.
+    pub fn call_hash<B, E>(&self, input: &B, ext_memory: &mut E) -> Result<H256, ParserError<E>>
+    where
+        B: AddressableBuffer<E>,
+        E: ExternalMemory,
+    {
+        let encoded_call = read_range::<B, E>(input, ext_memory, self.call_range())?;
+        Ok(H256(blake2_256(&encoded_call)))
+    }
+
+    /// Position of the call in the decoded input.
+    fn call_range(&self) -> &Range<usize> {
+        match self {
+            UncheckedExtrinsic::Signed { call_range, .. } => call_range,
+            UncheckedExtrinsic::Unsigned { call_range, .. } => call_range,
+            UncheckedExtrinsic::General { call_range, .. } => call_range,
+        }
+    }
+
+    /// Signed payload of a signed unchecked extrinsic, the data that gets
+    /// signed.
+    ///
+    /// Payload consists of the call and extra data bytes from the `input` the
+    /// extrinsic was decoded from, and `additional_signed` data built from
+    /// [`AdditionalSignedInfo`]. Payloads longer than
+    /// [`MAX_SIGNED_PAYLOAD_LEN`] are hashed.
+    pub fn signing_payload<B, E, M>(
+        &self,
+        input: &B,
+        ext_memory: &mut E,
+        meta_v14: &M,
+        additional_signed_info: &AdditionalSignedInfo,
+    ) -> Result<Vec<u8>, SignatureError<E, M>>
+    where
+        B: AddressableBuffer<E>,
+        E: ExternalMemory,
+        M: AsMetadata<E>,
+    {
+        match self {
+            UncheckedExtrinsic::Signed {
+                extra,
+                extra_range,
+                call_range,
+                ..
+            } => signed_payload::<B, E, M>(
+                input,
+                extra,
+                extra_range,
+                call_range,
+                ext_memory,
+                meta_v14,
+                additional_signed_info,
+            ),
+            UncheckedExtrinsic::Unsigned { .. } | UncheckedExtrinsic::General { .. } => {
+                Err(SignatureError::Unsigned)
+            }
+        }
+    }
+
+    /// Verify signature of a signed unchecked extrinsic, decoded from
+    /// `input`.
+    ///
+    /// Signed payload is assembled from the call and extra data bytes of the
+    /// `input`, and from the `additional_signed` data built from
+    /// [`AdditionalSignedInfo`]. Payloads longer than
+    /// [`MAX_SIGNED_PAYLOAD_LEN`] are hashed.
+    ///
+    /// Address must be an account id, possibly wrapped into `MultiAddress::Id`.
+    /// Signature must be sr25519, ed25519 or ecdsa `MultiSignature`. For
+    /// ecdsa, the account id is `blake2_256` hash of the recovered public key.
+    #[cfg(feature = "std")]
+    pub fn verify_signature<B, E, M>(
+        &self,
+        input: &B,
+        ext_memory: &mut E,
+        meta_v14: &M,
+        additional_signed_info: &AdditionalSignedInfo,
+    ) -> Result<(), SignatureError<E, M>>
+    where
+        B: AddressableBuffer<E>,
+        E: ExternalMemory,
+        M: AsMetadata<E>,
+    {
+        let (address, signature) = match self {
+            UncheckedExtrinsic::Signed {
+                address, signature, ..
+            } => (address, signature),
+            UncheckedExtrinsic::Unsigned { .. } | UncheckedExtrinsic::General { .. } => {
+                return Err(SignatureError::Unsigned)
+            }
+        };
+        let account_id = match single_value(&address.data) {
+            ParsedData::Id(account_id) => <[u8; 32]>::from(account_id.to_owned()),
+            _ => return Err(SignatureError::UnsupportedAddress),
+        };
+        let payload =
+            self.signing_payload::<B, E, M>(input, ext_memory, meta_v14, additional_signed_info)?;
+        let is_valid = match single_value(&signature.data) {
+            ParsedData::SignatureSr25519(signature) => {
+                sr25519::Pair::verify(signature, &payload, &sr25519::Public::from_raw(account_id))
+            }
+            ParsedData::SignatureEd25519(signature) => {
+                ed25519::Pair::verify(signature, &payload, &ed25519::Public::from_raw(account_id))
+            }
+            ParsedData::SignatureEcdsa(signature) => match signature.recover(&payload) {
+                Some(public) => blake2_256(public.as_ref()) == account_id,
+                None => false,
+            },
+            _ => return Err(SignatureError::UnsupportedSignature),
+        };
+        if is_valid {
+            Ok(())
+        } else {
+            Err(SignatureError::Invalid)
+        }
+    }
+}
+
+/// Innermost value of single-field enums and structs.
+#[cfg(feature = "std")]
+fn single_value(parsed_data: &ParsedData) -> &ParsedData {
+    match parsed_data {
+        ParsedData::Composite(fields) if fields.len() == 1 => single_value(&fields[0].data.data),
+        ParsedData::Variant(variant_data) if variant_data.fields.len() == 1 => {
+            single_value(&variant_data.fields[0].data.data)
+        }
+        _ => parsed_data,
+    }
+}
+
+/// Find [`Era`] in decoded extra data.
+fn find_era(parsed_data: &ParsedData) -> Option<Era> {
+    match parsed_data {
+        ParsedData::Era(era) => Some(*era),
+        ParsedData::Composite(fields) => fields
+            .iter()
+            .find_map(|field_data| find_era(&field_data.data.data)),
+        ParsedData::Tuple(set) => set
+            .iter()
+            .find_map(|extended_data| find_era(&extended_data.data)),
+        _ => None,
+    }
+}
+
+/// Build signed payload for a signed unchecked extrinsic: call bytes, extra
+/// data bytes, and `additional_signed` data.
+///
+/// `additional_signed` data is built for genesis hash, block hash, spec
+/// version, and tx version extensions. Other extensions must either have
+/// `additional_signed` data in [`AdditionalSignedInfo::other`], or have empty
+/// `additional_signed` data.
+///
+/// Payloads longer than [`MAX_SIGNED_PAYLOAD_LEN`] are hashed.
+fn signed_payload<B, E, M>(
+    input: &B,
+    extra: &ExtendedData,
+    extra_range: &Range<usize>,
+    call_range: &Range<usize>,
+    ext_memory: &mut E,
+    meta_v14: &M,
+    additional_signed_info: &AdditionalSignedInfo,
+) -> Result<Vec<u8>, SignatureError<E, M>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+    M: AsMetadata<E>,
+{
+    let extrinsic = meta_v14
+        .extrinsic()
+        .map_err(SignatureError::MetaStructure)?;
+    let meta_v14_types = meta_v14.types();
+
+    let mut payload = read_range::<B, E>(input, ext_memory, call_range)?;
+    payload.extend(read_range::<B, E>(input, ext_memory, extra_range)?);
+
+    for signed_extension in extrinsic.signed_extensions.iter() {
+        match signed_extension.identifier.as_str() {
+            CHECK_SPEC_VERSION => additional_signed_info.spec_version.encode_to(&mut payload),
+            CHECK_TX_VERSION => additional_signed_info.tx_version.encode_to(&mut payload),
+            CHECK_GENESIS => {
+                payload.extend_from_slice(additional_signed_info.genesis_hash.as_bytes())
+            }
+            CHECK_MORTALITY => {
+                let block_hash = match find_era(&extra.data).ok_or(SignatureError::NoEra)? {
+                    Era::Immortal => additional_signed_info.genesis_hash,
+                    Era::Mortal(_, _) => additional_signed_info
+                        .block_hash
+                        .ok_or(SignatureError::BlockHashNeeded)?,
+                };
+                payload.extend_from_slice(block_hash.as_bytes())
+            }
+            identifier => {
+                if let Some(extension_additional_signed) = additional_signed_info
+                    .other
+                    .iter()
+                    .find(|other| other.identifier == identifier)
+                {
+                    payload.extend_from_slice(&extension_additional_signed.encoded)
+                } else if fixed_encoded_len::<E, M>(
+                    &signed_extension.additional_signed,
+                    ext_memory,
+                    &meta_v14_types,
+                )? != Some(0)
+                {
+                    return Err(SignatureError::AdditionalSignedUnknown {
+                        identifier: identifier.to_owned(),
+                    });
+                }
+            }
+        }
+    }
+
+    if payload.len() > MAX_SIGNED_PAYLOAD_LEN {
+        Ok(blake2_256(&payload).to_vec())
+    } else {
+        Ok(payload)
+    }
+}
+
+/// Read bytes within the range from the input.
+fn read_range<B, E>(
+    input: &B,
+    ext_memory: &mut E,
+    range: &Range<usize>,
+) -> Result<Vec<u8>, ParserError<E>>
+where
+    B: AddressableBuffer<E>,
+    E: ExternalMemory,
+{
+    Ok(input
+        .read_slice(ext_memory, range.start, range.end - range.start)?
+        .as_ref()
+        .to_vec())
 }
@@ -0,0 +1,408 @@
+//! Visitor interface for decoding.
+//!
+//! Decoder resolves types from the metadata types registry and walks through
+//! the data, calling [`Visitor`] methods as it goes. Values of inner types are
+//! produced first, and are passed to the visitor along with the outer type
+//! description (fields, variant, etc.). Visitor decides what gets constructed
+//! from the values.
+//!
+//! [`ExtendedDataBuilder`] is the visitor used in
+//! [`decode_with_type`](crate::decoding_sci::decode_with_type), it assembles
+//! complete [`ExtendedData`] with all available type information.
+//!
+//! [`TolerantDataBuilder`] is the same visitor with [`Tolerant`] failure
+//! policy, used in
+//! [`decode_with_type_tolerant`](crate::decoding_sci::decode_with_type_tolerant),
+//! it records decoding failures as [`ParsedData::Error`] nodes and keeps the
+//! data decoded before the failure.
+//!
+//! Visitors that need no [`Info`] could opt out of its collection with
+//! [`Visitor::needs_info`], so that no type docs and paths get cloned. With
+//! `Value = ()` the data is walked through without any allocations for
+//! composites, variants and sequences.
+use external_memory_tools::ExternalMemory;
+use scale_info::{form::PortableForm, Field, Variant};
+
+use crate::std::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::cards::{
+    Call, Documented, Event, ExtendedData, FieldData, Info, PalletSpecificData, ParsedData,
+    SequenceData, SequenceRawData, VariantData,
+};
+use crate::error::ParserError;
+use crate::special_indicators::PalletSpecificItem;
+use crate::special_types::wrap_sequence;
+
+/// Callbacks for decoded data.
+///
+/// Each method gets the values for inner types (if any) and returns the value
+/// for the type itself. `info` contains [`Info`] collected while resolving
+/// the type, and is always empty if [`Visitor::needs_info`] is `false`.
+pub trait Visitor<E: ExternalMemory> {
+    /// Value produced for each decoded type.
+    type Value;
+
+    /// Collect [`Info`] for types encountered during decoding.
+    fn needs_info(&self) -> bool {
+        true
+    }
+
+    /// Record decoding failures with [`Visitor::visit_error`] instead of
+    /// returning them.
+    fn is_tolerant(&self) -> bool {
+        false
+    }
+
+    /// Decoding was halted after an unrecoverable failure, no more data is
+    /// decoded. Relevant only for tolerant visitors.
+    fn is_halted(&self) -> bool {
+        false
+    }
+
+    /// Struct (`TypeDef::Composite(_)`), with values for each of the `fields`.
+    fn visit_composite(
+        &mut self,
+        fields: &[Field<PortableForm>],
+        values: Vec<Self::Value>,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// Enum (`TypeDef::Variant(_)`), with found variant and values for each
+    /// of the variant fields.
+    fn visit_variant(
+        &mut self,
+        variant: &Variant<PortableForm>,
+        values: Vec<Self::Value>,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// Enum with no variants.
+    fn visit_empty_enum(&mut self, info: Vec<Info>) -> Result<Self::Value, ParserError<E>>;
+
+    /// Vector or array, with values for each element.
+    ///
+    /// `element_info` is [`Info`] collected while resolving element type.
+    fn visit_sequence(
+        &mut self,
+        element_info: Vec<Info>,
+        values: Vec<Self::Value>,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// Tuple, with values for each tuple element.
+    fn visit_tuple(
+        &mut self,
+        values: Vec<Self::Value>,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// Primitive (`TypeDef::Primitive(_)`), possibly compact.
+    fn visit_primitive(
+        &mut self,
+        parsed_data: ParsedData,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// `BitVec` (`TypeDef::BitSequence(_)`).
+    fn visit_bit_sequence(
+        &mut self,
+        parsed_data: ParsedData,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// Special type, recognized by type path: `AccountId32`, `Era`, hashes,
+    /// `PerThing` items, public keys and signatures, or user-registered
+    /// [`CustomType`](crate::special_indicators::CustomType) decoded into
+    /// [`ParsedData::Custom`].
+    fn visit_special(
+        &mut self,
+        parsed_data: ParsedData,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// Call or event, with values for each of the call or event fields.
+    fn visit_pallet_specific(
+        &mut self,
+        item: PalletSpecificItem,
+        pallet_name: String,
+        pallet_info: Info,
+        variant: &Variant<PortableForm>,
+        values: Vec<Self::Value>,
+        info: Vec<Info>,
+    ) -> Result<Self::Value, ParserError<E>>;
+
+    /// Decoding failure for item starting at `position`, called only for
+    /// tolerant visitors.
+    ///
+    /// `raw_remaining` is the data skipped because of the failure. If
+    /// `resync` is `true`, only the failed item was skipped and decoding
+    /// proceeds, otherwise all remaining data was skipped and decoding halts.
+    fn visit_error(
+        &mut self,
+        position: usize,
+        error: ParserError<E>,
+        raw_remaining: Vec<u8>,
+        resync: bool,
+    ) -> Result<Self::Value, ParserError<E>> {
+        let _ = (position, raw_remaining, resync);
+        Err(error)
+    }
+}
+
+/// Assemble [`FieldData`] set from fields and corresponding decoded values.
+pub(crate) fn field_data_set(
+    fields: &[Field<PortableForm>],
+    values: Vec<ExtendedData>,
+) -> Vec<FieldData> {
+    fields
+        .iter()
+        .zip(values)
+        .map(|(field, data)| FieldData {
+            field_name: field.name.to_owned(),
+            type_name: field.type_name.to_owned(),
+            field_docs: field.collect_docs(),
+            data,
+        })
+        .collect()
+}
+
+/// Assemble [`VariantData`] from variant and corresponding decoded field
+/// values.
+pub(crate) fn variant_data(
+    variant: &Variant<PortableForm>,
+    values: Vec<ExtendedData>,
+) -> VariantData {
+    VariantData {
+        variant_name: variant.name.to_owned(),
+        variant_docs: variant.collect_docs(),
+        fields: field_data_set(&variant.fields, values),
+    }
+}
+
+/// How [`ExtendedDataBuilder`] handles decoding failures.
+pub trait FailurePolicy: Default {
+    /// Decoding failures are recorded as [`ParsedData::Error`] nodes instead
+    /// of being returned.
+    fn is_tolerant(&self) -> bool;
+
+    /// Decoding was halted after an unrecoverable failure.
+    fn is_halted(&self) -> bool;
+
+    /// Decoding failure was recorded. If `resync` is `false`, decoding halts.
+    fn record_failure(&mut self, resync: bool);
+}
+
+/// Decoding failures are returned as errors.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Strict;
+
+impl FailurePolicy for Strict {
+    fn is_tolerant(&self) -> bool {
+        false
+    }
+
+    fn is_halted(&self) -> bool {
+        false
+    }
+
+    fn record_failure(&mut self, _resync: bool) {}
+}
+
+/// Decoding failures are recorded as [`ParsedData::Error`] nodes. After the
+/// first failure that could not be resynchronized, decoding halts, and the
+/// already decoded data is assembled into partial [`ExtendedData`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Tolerant {
+    halted: bool,
+}
+
+impl FailurePolicy for Tolerant {
+    fn is_tolerant(&self) -> bool {
+        true
+    }
+
+    fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    fn record_failure(&mut self, resync: bool) {
+        if !resync {
+            self.halted = true;
+        }
+    }
+}
+
+/// [`Visitor`] assembling [`ExtendedData`], with decoding failures handled
+/// according to [`FailurePolicy`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ExtendedDataBuilder<P: FailurePolicy = Strict> {
+    policy: P,
+}
+
+/// [`ExtendedDataBuilder`] in tolerant mode, see [`Tolerant`].
+pub type TolerantDataBuilder = ExtendedDataBuilder<Tolerant>;
+
+impl<P: FailurePolicy> ExtendedDataBuilder<P> {
+    /// New `ExtendedDataBuilder`, with no failures recorded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<E: ExternalMemory, P: FailurePolicy> Visitor<E> for ExtendedDataBuilder<P> {
+    type Value = ExtendedData;
+
+    fn is_tolerant(&self) -> bool {
+        self.policy.is_tolerant()
+    }
+
+    fn is_halted(&self) -> bool {
+        self.policy.is_halted()
+    }
+
+    fn visit_composite(
+        &mut self,
+        fields: &[Field<PortableForm>],
+        values: Vec<ExtendedData>,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        Ok(ExtendedData {
+            data: ParsedData::Composite(field_data_set(fields, values)),
+            info,
+        })
+    }
+
+    fn visit_variant(
+        &mut self,
+        variant: &Variant<PortableForm>,
+        values: Vec<ExtendedData>,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        Ok(ExtendedData {
+            data: ParsedData::Variant(variant_data(variant, values)),
+            info,
+        })
+    }
+
+    fn visit_empty_enum(&mut self, info: Vec<Info>) -> Result<ExtendedData, ParserError<E>> {
+        Ok(ExtendedData {
+            data: ParsedData::EmptyEnum,
+            info,
+        })
+    }
+
+    fn visit_sequence(
+        &mut self,
+        element_info: Vec<Info>,
+        values: Vec<ExtendedData>,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        let set: Vec<ParsedData> = values
+            .into_iter()
+            .map(|extended_data| extended_data.data)
+            .collect();
+        let data = match wrap_sequence(&set) {
+            Some(sequence) => ParsedData::Sequence(SequenceData {
+                element_info,
+                data: sequence,
+            }),
+            None => ParsedData::SequenceRaw(SequenceRawData {
+                element_info,
+                data: set,
+            }),
+        };
+        Ok(ExtendedData { data, info })
+    }
+
+    fn visit_tuple(
+        &mut self,
+        values: Vec<ExtendedData>,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        Ok(ExtendedData {
+            data: ParsedData::Tuple(values),
+            info,
+        })
+    }
+
+    fn visit_primitive(
+        &mut self,
+        parsed_data: ParsedData,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        Ok(ExtendedData {
+            data: parsed_data,
+            info,
+        })
+    }
+
+    fn visit_bit_sequence(
+        &mut self,
+        parsed_data: ParsedData,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        Ok(ExtendedData {
+            data: parsed_data,
+            info,
+        })
+    }
+
+    fn visit_special(
+        &mut self,
+        parsed_data: ParsedData,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        Ok(ExtendedData {
+            data: parsed_data,
+            info,
+        })
+    }
+
+    fn visit_pallet_specific(
+        &mut self,
+        item: PalletSpecificItem,
+        pallet_name: String,
+        pallet_info: Info,
+        variant: &Variant<PortableForm>,
+        values: Vec<ExtendedData>,
+        info: Vec<Info>,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        let pallet_specific_data = PalletSpecificData {
+            pallet_info,
+            variant_docs: variant.collect_docs(),
+            pallet_name,
+            variant_name: variant.name.to_owned(),
+            fields: field_data_set(&variant.fields, values),
+        };
+        let data = match item {
+            PalletSpecificItem::Call => ParsedData::Call(Call(pallet_specific_data)),
+            PalletSpecificItem::Event => ParsedData::Event(Event(pallet_specific_data)),
+        };
+        Ok(ExtendedData { data, info })
+    }
+
+    fn visit_error(
+        &mut self,
+        position: usize,
+        error: ParserError<E>,
+        raw_remaining: Vec<u8>,
+        resync: bool,
+    ) -> Result<ExtendedData, ParserError<E>> {
+        if !self.policy.is_tolerant() {
+            return Err(error);
+        }
+        self.policy.record_failure(resync);
+        Ok(ExtendedData {
+            data: ParsedData::Error {
+                position,
+                error: error.to_string(),
+                raw_remaining,
+            },
+            info: Vec::new(),
+        })
+    }
+}